@@ -0,0 +1,237 @@
+//! Tool argument extraction that reports every missing/invalid parameter at once.
+
+use rmcp::model::CallToolRequestParam;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Parameter validation failure for a tool call. The router maps this to `INVALID_PARAMS`.
+#[derive(Debug, Default)]
+pub struct InvalidParams {
+    pub missing: Vec<String>,
+    /// (parameter, reason) pairs for values that were present but unusable
+    pub invalid: Vec<(String, String)>,
+}
+
+impl InvalidParams {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.invalid.is_empty()
+    }
+
+    /// Structured form for the MCP error `data` field
+    pub fn to_json(&self) -> Value {
+        let invalid: Vec<_> = self
+            .invalid
+            .iter()
+            .map(|(param, reason)| serde_json::json!({ "param": param, "reason": reason }))
+            .collect();
+        serde_json::json!({
+            "missing": self.missing,
+            "invalid": invalid,
+        })
+    }
+}
+
+impl fmt::Display for InvalidParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if !self.missing.is_empty() {
+            parts.push(format!(
+                "Missing required parameter{}: {}",
+                if self.missing.len() == 1 { "" } else { "s" },
+                self.missing.join(", ")
+            ));
+        }
+        if !self.invalid.is_empty() {
+            let invalid: Vec<_> = self
+                .invalid
+                .iter()
+                .map(|(param, reason)| format!("{} ({})", param, reason))
+                .collect();
+            parts.push(format!("Invalid parameters: {}", invalid.join(", ")));
+        }
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+impl std::error::Error for InvalidParams {}
+
+/// Reads tool arguments, collecting problems until `finish()` so callers see them all together.
+///
+/// ```ignore
+/// let mut args = Args::new(&req);
+/// let last_name = args.required_str("last_name");
+/// let amount = args.required_f64("amount");
+/// args.finish()?;
+/// ```
+pub struct Args<'a> {
+    map: Option<&'a Map<String, Value>>,
+    problems: InvalidParams,
+}
+
+impl<'a> Args<'a> {
+    pub fn new(req: &'a CallToolRequestParam) -> Self {
+        Self::from_map(req.arguments.as_ref())
+    }
+
+    pub fn from_map(map: Option<&'a Map<String, Value>>) -> Self {
+        Self {
+            map,
+            problems: InvalidParams::default(),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&'a Value> {
+        self.map.and_then(|m| m.get(key)).filter(|v| !v.is_null())
+    }
+
+    /// Required string, trimmed; records a problem and returns an empty string if absent,
+    /// blank, or not a string.
+    pub fn required_str(&mut self, key: &str) -> String {
+        match self.get(key) {
+            Some(Value::String(s)) if !s.trim().is_empty() => s.trim().to_string(),
+            Some(Value::String(_)) => {
+                self.problems.missing.push(key.to_string());
+                String::new()
+            }
+            Some(_) => {
+                self.invalid(key, "must be a string");
+                String::new()
+            }
+            None => {
+                self.problems.missing.push(key.to_string());
+                String::new()
+            }
+        }
+    }
+
+    /// Optional string, trimmed; blank and non-string values are treated as absent.
+    pub fn optional_str(&self, key: &str) -> Option<String> {
+        self.get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    }
+
+    /// Required number; records a problem and returns 0.0 if absent or not numeric.
+    pub fn required_f64(&mut self, key: &str) -> f64 {
+        match self.get(key) {
+            Some(Value::Number(n)) => n.as_f64().unwrap_or_default(),
+            Some(_) => {
+                self.invalid(key, "must be a number");
+                0.0
+            }
+            None => {
+                self.problems.missing.push(key.to_string());
+                0.0
+            }
+        }
+    }
+
+    /// Required integer; records a problem and returns 0 if absent or not an integer.
+    pub fn required_i64(&mut self, key: &str) -> i64 {
+        match self.get(key) {
+            Some(Value::Number(n)) if n.is_i64() => n.as_i64().unwrap_or_default(),
+            Some(_) => {
+                self.invalid(key, "must be an integer");
+                0
+            }
+            None => {
+                self.problems.missing.push(key.to_string());
+                0
+            }
+        }
+    }
+
+    /// Optional number; non-numeric values are recorded as invalid.
+    pub fn optional_f64(&mut self, key: &str) -> Option<f64> {
+        match self.get(key) {
+            Some(Value::Number(n)) => n.as_f64(),
+            Some(_) => {
+                self.invalid(key, "must be a number");
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Raw JSON value for arguments that need custom parsing (arrays, objects).
+    pub fn optional_value(&self, key: &str) -> Option<&'a Value> {
+        self.get(key)
+    }
+
+    /// Record a required argument the handler found absent (for types without a reader).
+    pub fn missing(&mut self, key: &str) {
+        self.problems.missing.push(key.to_string());
+    }
+
+    /// Record a handler-specific validation failure.
+    pub fn invalid(&mut self, key: &str, reason: &str) {
+        self.problems
+            .invalid
+            .push((key.to_string(), reason.to_string()));
+    }
+
+    /// Ok if every required argument was usable, otherwise all problems in one error.
+    pub fn finish(self) -> Result<(), InvalidParams> {
+        if self.problems.is_empty() {
+            Ok(())
+        } else {
+            Err(self.problems)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Args;
+
+    #[test]
+    fn finish_reports_every_missing_and_invalid_param() {
+        let map = serde_json::json!({ "last_name": "Smith", "amount": "forty" });
+        let mut args = Args::from_map(map.as_object());
+        assert_eq!(args.required_str("last_name"), "Smith");
+        args.required_str("shoot_name");
+        args.required_f64("amount");
+        args.required_str("competition_name");
+
+        let err = args.finish().unwrap_err();
+        assert_eq!(err.missing, vec!["shoot_name", "competition_name"]);
+        assert_eq!(
+            err.to_string(),
+            "Missing required parameters: shoot_name, competition_name; \
+             Invalid parameters: amount (must be a number)"
+        );
+    }
+
+    #[test]
+    fn finish_is_ok_when_all_present() {
+        let map = serde_json::json!({ "name": "Minis", "amount": 12.5, "notes": null });
+        let mut args = Args::from_map(map.as_object());
+        assert_eq!(args.required_str("name"), "Minis");
+        assert_eq!(args.required_f64("amount"), 12.5);
+        assert_eq!(args.optional_str("notes"), None);
+        assert!(args.finish().is_ok());
+    }
+
+    #[test]
+    fn no_arguments_at_all_lists_everything() {
+        let mut args = Args::from_map(None);
+        args.required_str("name");
+        args.required_str("shoot_type");
+        assert_eq!(
+            args.finish().unwrap_err().to_string(),
+            "Missing required parameters: name, shoot_type"
+        );
+    }
+
+    #[test]
+    fn strings_are_trimmed_and_blank_counts_as_missing() {
+        let map = serde_json::json!({ "last_name": "  Smith ", "shoot_name": "   ", "notes": " " });
+        let mut args = Args::from_map(map.as_object());
+        assert_eq!(args.required_str("last_name"), "Smith");
+        assert_eq!(args.required_str("shoot_name"), "");
+        assert_eq!(args.optional_str("notes"), None);
+        assert_eq!(args.finish().unwrap_err().missing, vec!["shoot_name"]);
+    }
+}
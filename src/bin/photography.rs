@@ -66,6 +66,9 @@ enum Commands {
         amount: f64,
         #[arg(default_value = DEFAULT_COMPETITION)]
         competition: String,
+        /// Record an amount above PHOTO_MAX_PURCHASE_AMOUNT
+        #[arg(long)]
+        force: bool,
     },
     /// Check delivery status for a competition
     CheckStatus {
@@ -149,6 +152,9 @@ enum UpdateCommands {
         /// Purchase amount (for purchased status)
         #[arg(long)]
         amount: Option<f64>,
+        /// Record an amount above PHOTO_MAX_PURCHASE_AMOUNT
+        #[arg(long)]
+        force: bool,
     },
 }
 
@@ -210,6 +216,7 @@ async fn main() -> Result<()> {
                 status,
                 url,
                 amount,
+                force,
             } => {
                 photography_mind::photography::commands::update_gallery(
                     &db,
@@ -218,6 +225,7 @@ async fn main() -> Result<()> {
                     &status,
                     url.as_deref(),
                     amount,
+                    force,
                 )
                 .await?;
             }
@@ -252,12 +260,14 @@ async fn main() -> Result<()> {
             last_name,
             amount,
             competition,
+            force,
         } => {
             photography_mind::photography::commands::record_purchase(
                 &db,
                 &last_name,
                 amount,
                 &competition,
+                force,
             )
             .await?;
         }
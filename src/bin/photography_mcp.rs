@@ -1,6 +1,8 @@
+use arc_swap::ArcSwap;
 use axum::{
-    Json, Router as AxumRouter,
+    BoxError, Json, Router as AxumRouter,
     body::Body,
+    error_handling::HandleErrorLayer,
     extract::State,
     http::{Request, StatusCode},
     middleware,
@@ -18,13 +20,18 @@ use rmcp::{
 };
 use serde_json::json;
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::signal;
+use tower::ServiceBuilder;
+use tower::limit::GlobalConcurrencyLimitLayer;
+use tower_http::timeout::TimeoutLayer;
 use tracing_subscriber::EnvFilter;
 
+/// Auth reads the live config so a reload can rotate the bearer token.
 #[derive(Clone)]
 struct AuthState {
-    token: Option<String>,
-    allow_query: bool,
+    config: Arc<ArcSwap<Config>>,
 }
 
 async fn auth_layer(
@@ -32,13 +39,27 @@ async fn auth_layer(
     req: Request<Body>,
     next: middleware::Next,
 ) -> Result<Response, StatusCode> {
+    let (token, allow_query, base_path) = {
+        let cfg = state.config.load();
+        (
+            cfg.bearer_token.clone(),
+            cfg.allow_token_in_url,
+            cfg.base_path.clone(),
+        )
+    };
+
     // Allow open healthz
-    if req.uri().path().starts_with("/healthz") {
+    if req
+        .uri()
+        .path()
+        .strip_prefix(base_path.as_str())
+        .is_some_and(|rest| rest.starts_with("/healthz"))
+    {
         return Ok(next.run(req).await);
     }
 
     // If no token configured, allow all
-    let Some(expected) = state.token else {
+    let Some(expected) = token else {
         return Ok(next.run(req).await);
     };
 
@@ -51,7 +72,7 @@ async fn auth_layer(
 
     let mut query_ok = false;
     if !header_ok
-        && state.allow_query
+        && allow_query
         && let Some(q) = req.uri().query()
     {
         for pair in q.split('&') {
@@ -76,20 +97,83 @@ async fn auth_layer(
     }
 }
 
+/// Bind the HTTP listener; accepted connections inherit the TCP keepalive settings.
+fn bind_listener(
+    addr: SocketAddr,
+    keepalive: Option<Duration>,
+) -> anyhow::Result<tokio::net::TcpListener> {
+    use socket2::{Domain, Protocol, Socket, TcpKeepalive, Type};
+
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    socket.set_reuse_address(true)?;
+    if let Some(idle) = keepalive {
+        socket.set_tcp_keepalive(
+            &TcpKeepalive::new()
+                .with_time(idle)
+                .with_interval(Duration::from_secs(15)),
+        )?;
+    }
+    socket.bind(&addr.into())?;
+    socket.listen(1024)?;
+    socket.set_nonblocking(true)?;
+    Ok(tokio::net::TcpListener::from_std(socket.into())?)
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Logging (respect RUST_LOG, default warn)
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env().add_directive("warn".parse()?))
-        .with_ansi(false)
-        .init();
+    // Load .env before reading PHOTO_LOG_FORMAT (Config::load does this too, but runs later)
+    dotenvy::dotenv().ok();
+
+    // Logging (respect RUST_LOG, default warn; PHOTO_LOG_FORMAT=json for structured output)
+    let filter = EnvFilter::from_default_env().add_directive("warn".parse()?);
+    let json_logs = std::env::var("PHOTO_LOG_FORMAT")
+        .map(|v| v.eq_ignore_ascii_case("json"))
+        .unwrap_or(false);
+    if json_logs {
+        tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .json()
+            .init();
+    } else {
+        tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .with_ansi(false)
+            .init();
+    }
 
     let cfg = Config::load()?;
     let server = PhotoMindServer::new(cfg.clone()).await?;
     let router = Router(server.clone());
+    for name in router.unknown_disabled_tools() {
+        tracing::warn!(tool = %name, "PHOTO_DISABLED_TOOLS entry is not a known tool; ignoring");
+    }
 
     tracing::info!(http_addr=?cfg.http_addr, "config loaded");
 
+    // Background pings so connection drops show up in `health` even between tool calls
+    tokio::spawn(photography_mind::db::monitor_connection(
+        server.db.clone(),
+        server.connection.clone(),
+        photography_mind::db::CONNECTION_PROBE_INTERVAL,
+    ));
+
+    // SIGHUP reloads hot settings (token, limits, pending statuses) in place
+    #[cfg(unix)]
+    {
+        let server = server.clone();
+        tokio::spawn(async move {
+            let Ok(mut hangup) = signal::unix::signal(signal::unix::SignalKind::hangup()) else {
+                tracing::warn!("could not install SIGHUP handler; use the reload_config tool");
+                return;
+            };
+            while hangup.recv().await.is_some() {
+                if let Err(e) = server.reload_config() {
+                    tracing::error!(error = %e, "config reload failed; keeping current config");
+                }
+            }
+        });
+    }
+
     if let Some(http_addr) = cfg.http_addr.clone() {
         let addr: SocketAddr = http_addr.parse()?;
         let session_mgr = std::sync::Arc::new(LocalSessionManager::default());
@@ -99,17 +183,41 @@ async fn main() -> anyhow::Result<()> {
             StreamableHttpServerConfig::default(),
         );
         let auth_state = AuthState {
-            token: cfg.bearer_token.clone(),
-            allow_query: cfg.allow_token_in_url,
+            config: server.config_handle(),
         };
 
-        let app = AxumRouter::new()
-            .route("/healthz", get(|| async { "ok" }))
-            .nest_service("/mcp", service)
-            .layer(middleware::from_fn_with_state(auth_state, auth_layer));
+        let mut app = AxumRouter::new().nest_service("/mcp", service);
+        // The timeout bounds time-to-response; streaming exports are merged after it so
+        // whole-database downloads aren't cut off
+        if let Some(timeout) = cfg.http_timeout {
+            app = app.layer(TimeoutLayer::new(timeout));
+        }
+        let mut app = app.merge(photography_mind::export::routes(server.clone()));
+        // Shed load instead of queueing: requests past the limit get 503 immediately.
+        // Router::layer clones the layer into every route, so the limit is global (one
+        // shared semaphore) rather than per route. /healthz is routed after this layer
+        // so probes still answer under load.
+        if let Some(max) = cfg.max_concurrency {
+            app = app.layer(
+                ServiceBuilder::new()
+                    .layer(HandleErrorLayer::new(|_: BoxError| async {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    }))
+                    .load_shed()
+                    .layer(GlobalConcurrencyLimitLayer::new(max)),
+            );
+        }
+        let app = app.route("/healthz", get(|| async { "ok" }));
+        // PHOTO_BASE_PATH mounts everything under a prefix for path-based reverse proxies
+        let app = if cfg.base_path.is_empty() {
+            app
+        } else {
+            AxumRouter::new().nest(&cfg.base_path, app)
+        };
+        let app = app.layer(middleware::from_fn_with_state(auth_state, auth_layer));
 
-        tracing::info!(%addr, "starting HTTP MCP server");
-        let listener = tokio::net::TcpListener::bind(addr).await?;
+        tracing::info!(%addr, base_path = %cfg.base_path, timeout = ?cfg.http_timeout, keepalive = ?cfg.http_keepalive, max_concurrency = ?cfg.max_concurrency, "starting HTTP MCP server");
+        let listener = bind_listener(addr, cfg.http_keepalive)?;
         axum::serve(listener, app.into_make_service())
             .with_graceful_shutdown(async {
                 let _ = signal::ctrl_c().await;
@@ -35,7 +35,12 @@ async fn main() -> Result<()> {
         ns, dbname
     );
 
-    // Define the schema for photography client and competition tracking
+    // Family table follows PHOTO_FAMILY_TABLE so relations and record links match the server
+    let family_table = photography_mind::config::Config::load()?.family_table;
+    println!("Family table: {}", family_table);
+
+    // Define the schema for photography client and competition tracking;
+    // `{family}` stands for the configured family table
     let schema_queries = vec![
         "DEFINE TABLE client SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
         "DEFINE FIELD first_name ON client TYPE string;",
@@ -51,17 +56,25 @@ async fn main() -> Result<()> {
         "DEFINE FIELD birth_date ON skater TYPE option<datetime>;",
         "DEFINE FIELD notes ON skater TYPE option<string>;",
         "DEFINE FIELD created_at ON skater TYPE datetime DEFAULT time::now();",
-        "DEFINE TABLE family SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
-        "DEFINE FIELD name ON family TYPE string;",
-        "DEFINE FIELD primary_contact ON family TYPE option<record<client>>;",
-        "DEFINE FIELD delivery_email ON family TYPE string;",
-        "DEFINE FIELD notes ON family TYPE option<string>;",
+        "DEFINE TABLE {family} SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
+        "DEFINE FIELD name ON {family} TYPE string;",
+        "DEFINE FIELD primary_contact ON {family} TYPE option<record<client>>;",
+        "DEFINE FIELD delivery_email ON {family} TYPE string;",
+        "DEFINE FIELD delivery_emails ON {family} TYPE option<array<string>>;",
+        "DEFINE FIELD last_name_raw ON {family} TYPE option<string>;",
+        "DEFINE FIELD referred_by ON {family} TYPE option<record<{family}>>;",
+        "DEFINE FIELD notes ON {family} TYPE option<string>;",
+        "DEFINE FIELD tags ON {family} TYPE option<array<string>>;",
         "DEFINE TABLE competition SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
         "DEFINE FIELD name ON competition TYPE string;",
         "DEFINE FIELD venue ON competition TYPE string;",
         "DEFINE FIELD start_date ON competition TYPE option<datetime>;",
         "DEFINE FIELD end_date ON competition TYPE option<datetime>;",
+        "DEFINE FIELD date ON competition TYPE option<datetime>;",
+        "DEFINE FIELD location ON competition TYPE option<string>;",
         "DEFINE FIELD notes ON competition TYPE option<string>;",
+        "DEFINE FIELD archived ON competition TYPE option<bool>;",
+        "DEFINE FIELD archived_at ON competition TYPE option<datetime>;",
         "DEFINE TABLE event SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
         "DEFINE FIELD competition ON event TYPE record<competition>;",
         "DEFINE FIELD event_number ON event TYPE int;",
@@ -82,7 +95,7 @@ async fn main() -> Result<()> {
         "DEFINE TABLE parent_of TYPE RELATION FROM client TO skater SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
         "DEFINE FIELD relationship ON parent_of TYPE string DEFAULT 'parent/guardian';",
         "DEFINE FIELD created_at ON parent_of TYPE datetime DEFAULT time::now();",
-        "DEFINE TABLE family_member TYPE RELATION FROM skater TO family SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
+        "DEFINE TABLE family_member TYPE RELATION FROM skater TO {family} SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
         "DEFINE FIELD created_at ON family_member TYPE datetime DEFAULT time::now();",
         "DEFINE TABLE competed_in TYPE RELATION FROM skater TO event SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
         "DEFINE FIELD skate_order ON competed_in TYPE option<int>;",
@@ -94,14 +107,22 @@ async fn main() -> Result<()> {
         "DEFINE FIELD purchase_date ON competed_in TYPE option<datetime>;",
         "DEFINE FIELD notes ON competed_in TYPE option<string>;",
         "DEFINE FIELD created_at ON competed_in TYPE datetime DEFAULT time::now();",
-        "DEFINE TABLE family_competition TYPE RELATION FROM family TO competition SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
+        "DEFINE TABLE family_competition TYPE RELATION FROM {family} TO competition SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
         "DEFINE FIELD gallery_status ON family_competition TYPE string DEFAULT 'pending' ASSERT $value INSIDE ['pending', 'culling', 'processing', 'sent', 'purchased', 'not_shot', 'needs_research'];",
         "DEFINE FIELD sent_date ON family_competition TYPE option<datetime>;",
         "DEFINE FIELD request_status ON family_competition TYPE option<string>;",
         "DEFINE FIELD ty_requested ON family_competition TYPE bool DEFAULT false;",
         "DEFINE FIELD ty_sent ON family_competition TYPE bool DEFAULT false;",
         "DEFINE FIELD ty_sent_date ON family_competition TYPE option<datetime>;",
+        "DEFINE FIELD purchase_amount ON family_competition TYPE option<float>;",
+        "DEFINE FIELD purchase_date ON family_competition TYPE option<datetime>;",
+        "DEFINE FIELD resends ON family_competition TYPE option<array<object>>;",
+        "DEFINE FIELD resends[*].at ON family_competition TYPE datetime;",
+        "DEFINE FIELD resends[*].reason ON family_competition TYPE string;",
+        "DEFINE FIELD resends[*].prior_status ON family_competition TYPE string;",
+        "DEFINE FIELD resends[*].prior_sent_date ON family_competition TYPE option<datetime>;",
         "DEFINE FIELD created_at ON family_competition TYPE datetime DEFAULT time::now();",
+        "DEFINE FIELD archived ON family_competition TYPE option<bool>;",
         // Shoot table - all non-competition photography work
         "DEFINE TABLE shoot SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
         "DEFINE FIELD name ON shoot TYPE string;",
@@ -109,9 +130,19 @@ async fn main() -> Result<()> {
         "DEFINE FIELD shoot_date ON shoot TYPE option<datetime>;",
         "DEFINE FIELD location ON shoot TYPE option<string>;",
         "DEFINE FIELD notes ON shoot TYPE option<string>;",
+        "DEFINE FIELD tags ON shoot TYPE option<array<string>>;",
+        "DEFINE FIELD packages ON shoot TYPE option<array<object>>;",
+        "DEFINE FIELD packages[*].name ON shoot TYPE string;",
+        "DEFINE FIELD packages[*].price_cents ON shoot TYPE int;",
+        "DEFINE FIELD external_key ON shoot TYPE option<string>;",
+        "DEFINE INDEX shoot_external_key ON shoot FIELDS external_key UNIQUE;",
+        "DEFINE FIELD slug ON shoot TYPE option<string>;",
+        "DEFINE INDEX shoot_slug ON shoot FIELDS slug UNIQUE;",
         "DEFINE FIELD created_at ON shoot TYPE datetime DEFAULT time::now();",
+        "DEFINE FIELD archived ON shoot TYPE option<bool>;",
+        "DEFINE FIELD archived_at ON shoot TYPE option<datetime>;",
         // Family-shoot relationship (parallel to family_competition)
-        "DEFINE TABLE family_shoot TYPE RELATION FROM family TO shoot SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
+        "DEFINE TABLE family_shoot TYPE RELATION FROM {family} TO shoot SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
         "DEFINE FIELD gallery_status ON family_shoot TYPE string DEFAULT 'pending' ASSERT $value INSIDE ['pending', 'culling', 'processing', 'sent', 'purchased', 'not_shot', 'needs_research'];",
         "DEFINE FIELD sent_date ON family_shoot TYPE option<datetime>;",
         "DEFINE FIELD request_status ON family_shoot TYPE option<string>;",
@@ -120,7 +151,21 @@ async fn main() -> Result<()> {
         "DEFINE FIELD ty_sent_date ON family_shoot TYPE option<datetime>;",
         "DEFINE FIELD purchase_amount ON family_shoot TYPE option<float>;",
         "DEFINE FIELD purchase_date ON family_shoot TYPE option<datetime>;",
+        "DEFINE FIELD package ON family_shoot TYPE option<string>;",
+        "DEFINE FIELD resends ON family_shoot TYPE option<array<object>>;",
+        "DEFINE FIELD resends[*].at ON family_shoot TYPE datetime;",
+        "DEFINE FIELD resends[*].reason ON family_shoot TYPE string;",
+        "DEFINE FIELD resends[*].prior_status ON family_shoot TYPE string;",
+        "DEFINE FIELD resends[*].prior_sent_date ON family_shoot TYPE option<datetime>;",
         "DEFINE FIELD created_at ON family_shoot TYPE datetime DEFAULT time::now();",
+        "DEFINE FIELD archived ON family_shoot TYPE option<bool>;",
+        // Family notes timeline (family.notes stays as the legacy first entry)
+        "DEFINE TABLE note SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
+        "DEFINE FIELD family ON note TYPE record;",
+        "DEFINE FIELD text ON note TYPE string;",
+        "DEFINE FIELD author ON note TYPE option<string>;",
+        "DEFINE FIELD created_at ON note TYPE datetime DEFAULT time::now();",
+        "DEFINE INDEX note_family ON note FIELDS family;",
         // Skater-shoot relationship (parallel to competed_in)
         "DEFINE TABLE shot_in TYPE RELATION FROM skater TO shoot SCHEMAFULL PERMISSIONS FOR select, create, update FULL;",
         "DEFINE FIELD gallery_status ON shot_in TYPE string DEFAULT 'pending' ASSERT $value INSIDE ['pending', 'culling', 'processing', 'sent', 'purchased', 'not_shot', 'needs_research'];",
@@ -131,6 +176,7 @@ async fn main() -> Result<()> {
 
     // Execute each schema query
     for query in schema_queries {
+        let query = query.replace("{family}", &family_table);
         println!("Executing: {}", query);
         let _resp = db.query(query).await?;
     }
@@ -1,6 +1,63 @@
+use crate::photography::{DEFAULT_PENDING_STATUSES, GALLERY_STATUSES};
 use anyhow::Result;
 use std::env;
 
+/// How new family record ids are generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FamilyIdStrategy {
+    /// `family:smith_jones` derived from the last name (default, matches the CLI)
+    NameSlug,
+    /// Random UUID key; lookups by name go through the `last_name` field
+    Uuid,
+}
+
+/// Per-signal weights for the `attention_list` family score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttentionWeights {
+    /// Per pending gallery older than the stale threshold
+    pub stale_pending: f64,
+    /// Per week a sent gallery has gone unpurchased
+    pub sent_not_purchased: f64,
+    /// Family has no delivery email
+    pub missing_email: f64,
+    /// No send, purchase, or thank-you inside the contact window
+    pub no_recent_contact: f64,
+}
+
+impl Default for AttentionWeights {
+    fn default() -> Self {
+        Self {
+            stale_pending: 3.0,
+            sent_not_purchased: 1.0,
+            missing_email: 5.0,
+            no_recent_contact: 2.0,
+        }
+    }
+}
+
+impl AttentionWeights {
+    /// Applies `key=value` overrides (comma-separated) on top of `self`.
+    pub fn with_overrides(mut self, raw: &str) -> Result<Self> {
+        for pair in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("expected key=value, got {:?}", pair))?;
+            let value: f64 = value
+                .trim()
+                .parse()
+                .map_err(|_| anyhow::anyhow!("weight for {} must be a number", key.trim()))?;
+            match key.trim() {
+                "stale_pending" => self.stale_pending = value,
+                "sent_not_purchased" => self.sent_not_purchased = value,
+                "missing_email" => self.missing_email = value,
+                "no_recent_contact" => self.no_recent_contact = value,
+                other => anyhow::bail!("unknown attention weight {:?}", other),
+            }
+        }
+        Ok(self)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub db_url: String,
@@ -9,8 +66,58 @@ pub struct Config {
     pub db_user: String,
     pub db_pass: String,
     pub http_addr: Option<String>,
+    /// Prefix for every HTTP route (`/photo` serves `/photo/mcp`, `/photo/healthz`); empty = none.
+    pub base_path: String,
     pub bearer_token: Option<String>,
     pub allow_token_in_url: bool,
+    /// Time allowed for an HTTP request to produce a response (`None` = no limit).
+    pub http_timeout: Option<std::time::Duration>,
+    /// TCP keepalive idle time for HTTP connections (`None` = OS default, keepalive off).
+    pub http_keepalive: Option<std::time::Duration>,
+    /// Max in-flight HTTP requests before new ones get 503 (`None` = unlimited).
+    pub max_concurrency: Option<usize>,
+    /// Max items returned in detail arrays (e.g. sync `matched_details`) before truncation.
+    pub max_detail_items: usize,
+    /// Rows returned by list/search tools when the call gives no `limit`.
+    pub default_limit: usize,
+    /// Gallery statuses the pending-list tools treat as outstanding.
+    pub pending_statuses: Vec<String>,
+    /// Status `resend_gallery` moves a sent gallery back to (default `pending`).
+    pub resend_status: String,
+    /// Optional family display template, e.g. `{last_name} Family`.
+    pub family_name_template: Option<String>,
+    /// Table holding family/household records (default `family`).
+    pub family_table: String,
+    pub family_id_strategy: FamilyIdStrategy,
+    /// Tables counted by `status`/`dashboard`; `None` keeps the built-in seven.
+    pub status_tables: Option<Vec<String>>,
+    /// Row count above which export tools hand back a streaming URL instead of inline CSV.
+    pub export_max_inline_rows: usize,
+    pub attention_weights: AttentionWeights,
+    /// Purchase amounts above this need `force` (`None` = no ceiling).
+    pub max_purchase_amount: Option<f64>,
+    /// Import files larger than this are refused before reading (`None` = no limit).
+    pub max_import_bytes: Option<u64>,
+    /// Expose admin tools such as `reload_config` (`PHOTO_ENABLE_ADMIN_TOOLS=1`).
+    pub admin_tools: bool,
+    /// Expose the read-only `raw_query` tool (`PHOTO_ENABLE_RAW_QUERY=1`).
+    pub raw_query: bool,
+    /// Tools hidden from `list_tools` and refused by `call_tool`.
+    pub disabled_tools: Vec<String>,
+    /// Keep each list/get tool's original record keys next to the envelope's `data`.
+    pub result_legacy_keys: bool,
+    /// Accept an `at` timestamp on mutating tools in place of `time::now()`
+    /// (`PHOTO_ALLOW_CLOCK_OVERRIDE=1`; for tests and backfills).
+    pub allow_clock_override: bool,
+    /// Title-case surnames typed all lower/upper case when families are created
+    /// (`PHOTO_NORMALIZE_NAME_CASE=1`).
+    pub normalize_name_case: bool,
+    /// Exact spellings that win over title-casing (`PHOTO_NAME_CASE_OVERRIDES`).
+    pub name_case_overrides: Vec<String>,
+    /// Extra attempts for a sync row whose DB work fails (`PHOTO_SYNC_RETRIES`).
+    pub sync_retries: u32,
+    /// Wait before the first sync retry, doubled for each later one (`PHOTO_SYNC_RETRY_BACKOFF_MS`).
+    pub sync_retry_backoff_ms: u64,
 }
 
 impl Config {
@@ -25,6 +132,12 @@ impl Config {
 
         // Enable HTTP transport only when explicitly set (e.g., "0.0.0.0:8788")
         let http_addr = env::var("PHOTO_HTTP_ADDR").ok();
+        let base_path = match env::var("PHOTO_BASE_PATH") {
+            Ok(raw) => {
+                parse_base_path(&raw).map_err(|e| anyhow::anyhow!("PHOTO_BASE_PATH: {}", e))?
+            }
+            Err(_) => String::new(),
+        };
 
         let bearer_token = env::var("PHOTO_BEARER_TOKEN").ok().or_else(|| {
             let home = env::var("HOME").ok()?;
@@ -36,6 +149,166 @@ impl Config {
             .map(|v| v != "0" && !v.eq_ignore_ascii_case("false"))
             .unwrap_or(true);
 
+        // 0 disables either setting
+        let http_timeout_ms: u64 = env::var("PHOTO_HTTP_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60_000);
+        let http_timeout =
+            (http_timeout_ms > 0).then(|| std::time::Duration::from_millis(http_timeout_ms));
+        let http_keepalive_secs: u64 = env::var("PHOTO_HTTP_KEEPALIVE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60);
+        let http_keepalive =
+            (http_keepalive_secs > 0).then(|| std::time::Duration::from_secs(http_keepalive_secs));
+        let max_concurrency: usize = env::var("PHOTO_MAX_CONCURRENCY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(32);
+        let max_concurrency = (max_concurrency > 0).then_some(max_concurrency);
+
+        let max_detail_items = env::var("PHOTO_MAX_DETAIL_ITEMS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(200);
+
+        let default_limit = env::var("PHOTO_DEFAULT_LIMIT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|l| *l > 0)
+            .unwrap_or(50);
+
+        let pending_statuses = env::var("PHOTO_PENDING_STATUSES")
+            .ok()
+            .map(|v| parse_status_list(&v))
+            .filter(|list| !list.is_empty())
+            .unwrap_or_else(|| {
+                DEFAULT_PENDING_STATUSES
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect()
+            });
+        // The edge schema only stores GALLERY_STATUSES, so any other name would match nothing
+        if let Some(unknown) = pending_statuses
+            .iter()
+            .find(|s| !GALLERY_STATUSES.contains(&s.as_str()))
+        {
+            anyhow::bail!(
+                "PHOTO_PENDING_STATUSES entries must be gallery statuses ({}), got {:?}",
+                GALLERY_STATUSES.join(", "),
+                unknown
+            );
+        }
+
+        let resend_status = env::var("PHOTO_RESEND_STATUS")
+            .map(|v| v.trim().to_lowercase())
+            .unwrap_or_else(|_| "pending".to_string());
+        if resend_status == "sent" || !GALLERY_STATUSES.contains(&resend_status.as_str()) {
+            anyhow::bail!(
+                "PHOTO_RESEND_STATUS must be a gallery status other than 'sent' ({}), got {:?}",
+                GALLERY_STATUSES.join(", "),
+                resend_status
+            );
+        }
+
+        let family_name_template = env::var("PHOTO_FAMILY_NAME_TEMPLATE")
+            .ok()
+            .filter(|t| !t.trim().is_empty());
+        if let Some(template) = &family_name_template
+            && !template.contains("{last_name}")
+            && !template.contains("{name}")
+        {
+            anyhow::bail!(
+                "PHOTO_FAMILY_NAME_TEMPLATE must contain {{last_name}} or {{name}}, got {:?}",
+                template
+            );
+        }
+
+        let family_table = env::var("PHOTO_FAMILY_TABLE").unwrap_or_else(|_| "family".to_string());
+        // Interpolated into a few queries, so keep it to a plain identifier
+        if family_table.is_empty()
+            || !family_table
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            anyhow::bail!(
+                "PHOTO_FAMILY_TABLE must be a plain table name (letters, digits, underscore): {:?}",
+                family_table
+            );
+        }
+
+        let family_id_strategy = match env::var("PHOTO_FAMILY_ID_STRATEGY")
+            .unwrap_or_else(|_| "name_slug".to_string())
+            .as_str()
+        {
+            "name_slug" => FamilyIdStrategy::NameSlug,
+            "uuid" => FamilyIdStrategy::Uuid,
+            other => anyhow::bail!(
+                "PHOTO_FAMILY_ID_STRATEGY must be 'name_slug' or 'uuid', got {:?}",
+                other
+            ),
+        };
+
+        let status_tables = match env::var("PHOTO_STATUS_TABLES") {
+            Ok(raw) => Some(
+                parse_table_list(&raw)
+                    .map_err(|e| anyhow::anyhow!("PHOTO_STATUS_TABLES: {}", e))?,
+            ),
+            Err(_) => None,
+        };
+
+        let export_max_inline_rows = env::var("PHOTO_EXPORT_MAX_INLINE_ROWS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5000);
+
+        let max_purchase_amount = max_purchase_amount();
+        let max_import_bytes = max_import_bytes();
+
+        let admin_tools = env::var("PHOTO_ENABLE_ADMIN_TOOLS")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        let disabled_tools = env::var("PHOTO_DISABLED_TOOLS")
+            .map(|raw| parse_status_list(&raw))
+            .unwrap_or_default();
+        let result_legacy_keys = env::var("PHOTO_RESULT_LEGACY_KEYS")
+            .map(|v| v != "0" && !v.eq_ignore_ascii_case("false"))
+            .unwrap_or(true);
+        let allow_clock_override = env::var("PHOTO_ALLOW_CLOCK_OVERRIDE")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        let normalize_name_case = env::var("PHOTO_NORMALIZE_NAME_CASE")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        // Comma-separated like the status lists, but case is the point here
+        let name_case_overrides = env::var("PHOTO_NAME_CASE_OVERRIDES")
+            .map(|raw| {
+                raw.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let sync_retries = env::var("PHOTO_SYNC_RETRIES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(2);
+        let sync_retry_backoff_ms = env::var("PHOTO_SYNC_RETRY_BACKOFF_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(250);
+        let raw_query = env::var("PHOTO_ENABLE_RAW_QUERY")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+
+        let attention_weights = match env::var("PHOTO_ATTENTION_WEIGHTS") {
+            Ok(raw) => AttentionWeights::default()
+                .with_overrides(&raw)
+                .map_err(|e| anyhow::anyhow!("PHOTO_ATTENTION_WEIGHTS: {}", e))?,
+            Err(_) => AttentionWeights::default(),
+        };
+
         Ok(Self {
             db_url,
             db_namespace,
@@ -43,8 +316,219 @@ impl Config {
             db_user,
             db_pass,
             http_addr,
+            base_path,
             bearer_token,
             allow_token_in_url,
+            http_timeout,
+            http_keepalive,
+            max_concurrency,
+            max_detail_items,
+            default_limit,
+            pending_statuses,
+            resend_status,
+            family_name_template,
+            family_table,
+            family_id_strategy,
+            status_tables,
+            export_max_inline_rows,
+            attention_weights,
+            max_purchase_amount,
+            max_import_bytes,
+            admin_tools,
+            raw_query,
+            disabled_tools,
+            result_legacy_keys,
+            allow_clock_override,
+            normalize_name_case,
+            name_case_overrides,
+            sync_retries,
+            sync_retry_backoff_ms,
         })
     }
+
+    /// `load` for a running server: values in `.env` override the process environment,
+    /// so edits made since startup are picked up.
+    pub fn reload() -> Result<Self> {
+        dotenvy::dotenv_override().ok();
+        Self::load()
+    }
+
+    /// Copies the hot-reloadable fields (token, limits, status sets, display settings) from
+    /// `fresh` onto a clone of `self`. Returns the new config, the hot fields that changed,
+    /// and the restart-only fields whose new value was ignored.
+    pub fn apply_reload(&self, fresh: &Config) -> (Config, Vec<&'static str>, Vec<&'static str>) {
+        let mut next = self.clone();
+        let mut changed = Vec::new();
+        let mut needs_restart = Vec::new();
+
+        macro_rules! hot {
+            ($($field:ident),*) => {$(
+                if next.$field != fresh.$field {
+                    next.$field = fresh.$field.clone();
+                    changed.push(stringify!($field));
+                }
+            )*};
+        }
+        macro_rules! restart_only {
+            ($($field:ident),*) => {$(
+                if self.$field != fresh.$field {
+                    needs_restart.push(stringify!($field));
+                }
+            )*};
+        }
+
+        hot!(
+            bearer_token,
+            allow_token_in_url,
+            max_detail_items,
+            default_limit,
+            export_max_inline_rows,
+            pending_statuses,
+            resend_status,
+            family_name_template,
+            attention_weights,
+            max_purchase_amount,
+            max_import_bytes,
+            result_legacy_keys,
+            allow_clock_override,
+            normalize_name_case,
+            name_case_overrides,
+            sync_retries,
+            sync_retry_backoff_ms
+        );
+        restart_only!(
+            db_url,
+            db_namespace,
+            db_name,
+            db_user,
+            db_pass,
+            http_addr,
+            base_path,
+            http_timeout,
+            http_keepalive,
+            max_concurrency,
+            family_table,
+            family_id_strategy,
+            status_tables,
+            admin_tools,
+            raw_query,
+            disabled_tools
+        );
+
+        (next, changed, needs_restart)
+    }
+}
+
+/// `PHOTO_MAX_PURCHASE_AMOUNT` (default 5000, `0` disables). Also read directly by the CLI,
+/// which doesn't load the full `Config`.
+pub fn max_purchase_amount() -> Option<f64> {
+    let ceiling: f64 = env::var("PHOTO_MAX_PURCHASE_AMOUNT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(5000.0);
+    (ceiling > 0.0).then_some(ceiling)
+}
+
+/// `PHOTO_MAX_IMPORT_BYTES` (default 50 MB, `0` disables). Also read directly by the CLI.
+pub fn max_import_bytes() -> Option<u64> {
+    let limit: u64 = env::var("PHOTO_MAX_IMPORT_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(50 * 1024 * 1024);
+    (limit > 0).then_some(limit)
+}
+
+/// Splits a comma-separated list (statuses, tool names), trimming and lowercasing entries.
+pub fn parse_status_list(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Normalizes a route prefix to `/segment[/segment...]` without a trailing slash; `""` and
+/// `"/"` mean no prefix.
+pub fn parse_base_path(raw: &str) -> Result<String> {
+    let trimmed = raw.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        return Ok(String::new());
+    }
+    if let Some(bad) = trimmed
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '/' | '-' | '_' | '.')))
+    {
+        anyhow::bail!("invalid character {:?} in {:?}", bad, raw);
+    }
+    if trimmed.split('/').any(|segment| segment.is_empty()) {
+        anyhow::bail!("empty path segment in {:?}", raw);
+    }
+    Ok(format!("/{}", trimmed))
+}
+
+/// Splits a comma-separated table list; names are interpolated into queries, so only `[a-z_]`.
+pub fn parse_table_list(raw: &str) -> Result<Vec<String>> {
+    let tables: Vec<String> = raw
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if tables.is_empty() {
+        anyhow::bail!("no table names given");
+    }
+    if let Some(bad) = tables
+        .iter()
+        .find(|t| !t.chars().all(|c| c.is_ascii_lowercase() || c == '_'))
+    {
+        anyhow::bail!("invalid table name {:?} (allowed: a-z and _)", bad);
+    }
+    Ok(tables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AttentionWeights, parse_base_path, parse_table_list};
+
+    #[test]
+    fn base_path_is_normalized() {
+        assert_eq!(parse_base_path("/photo/").unwrap(), "/photo");
+        assert_eq!(parse_base_path("apps/photo").unwrap(), "/apps/photo");
+        assert_eq!(parse_base_path(" / ").unwrap(), "");
+        assert!(parse_base_path("/photo//mcp").is_err());
+        assert!(parse_base_path("/photo?x=1").is_err());
+    }
+
+    #[test]
+    fn table_list_only_allows_plain_names() {
+        assert_eq!(
+            parse_table_list("family, skater ,audit_log,").unwrap(),
+            vec!["family", "skater", "audit_log"]
+        );
+        assert!(parse_table_list("family; DELETE skater").is_err());
+        assert!(parse_table_list("Family").is_err());
+        assert!(parse_table_list(" , ").is_err());
+    }
+
+    #[test]
+    fn attention_weight_overrides() {
+        let weights = AttentionWeights::default()
+            .with_overrides("missing_email=0, stale_pending=4.5")
+            .unwrap();
+        assert_eq!(weights.missing_email, 0.0);
+        assert_eq!(weights.stale_pending, 4.5);
+        assert_eq!(
+            weights.sent_not_purchased,
+            AttentionWeights::default().sent_not_purchased
+        );
+
+        assert!(
+            AttentionWeights::default()
+                .with_overrides("typo=1")
+                .is_err()
+        );
+        assert!(
+            AttentionWeights::default()
+                .with_overrides("missing_email=lots")
+                .is_err()
+        );
+    }
 }
@@ -1,5 +1,8 @@
 use crate::config::Config;
 use anyhow::Result;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use surrealdb::{
     Surreal,
     engine::remote::ws::{Client, Ws},
@@ -14,19 +17,194 @@ pub async fn connect_db(cfg: &Config) -> Result<Surreal<Client>> {
         .trim_start_matches("wss://")
         .to_string();
 
-    let db = Surreal::new::<Ws>(&addr).await?;
+    // Each step gets its own message so a stale password doesn't look like a dead server.
+    let db = Surreal::new::<Ws>(&addr).await.map_err(|e| {
+        anyhow::anyhow!(
+            "Could not connect to SurrealDB at {} — is it running and is PHOTO_DB_URL correct? ({})",
+            cfg.db_url,
+            e
+        )
+    })?;
     db.signin(Root {
         username: &cfg.db_user,
         password: &cfg.db_pass,
     })
-    .await?;
-    db.use_ns(&cfg.db_namespace).use_db(&cfg.db_name).await?;
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "SurrealDB authentication failed for user {} — check PHOTO_DB_USER/PHOTO_DB_PASS ({})",
+            cfg.db_user,
+            e
+        )
+    })?;
+    db.use_ns(&cfg.db_namespace)
+        .use_db(&cfg.db_name)
+        .await
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "Could not select namespace {} / database {} — check PHOTO_DB_NS/PHOTO_DB_NAME ({})",
+                cfg.db_namespace,
+                cfg.db_name,
+                e
+            )
+        })?;
     Ok(db)
 }
 
+/// Confirms the namespace/database chosen in `connect_db` took effect: the session reports
+/// them back and `INFO FOR DB` answers. `use_ns`/`use_db` accept any name, so without this a
+/// typo only surfaces on the first query. An empty database is warned about, not refused,
+/// since a fresh install has no tables until `photography_schema` runs.
+pub async fn verify_selection(db: &Surreal<Client>, cfg: &Config) -> Result<()> {
+    let mut res = db
+        .query("RETURN session::ns(); RETURN session::db(); INFO FOR DB;")
+        .await?;
+    let ns: Option<String> = res.take(0)?;
+    let name: Option<String> = res.take(1)?;
+    if ns.as_deref() != Some(cfg.db_namespace.as_str())
+        || name.as_deref() != Some(cfg.db_name.as_str())
+    {
+        anyhow::bail!(
+            "Namespace/database selection did not take: session is {} / {}, expected {} / {} — check PHOTO_DB_NS/PHOTO_DB_NAME",
+            ns.as_deref().unwrap_or("(none)"),
+            name.as_deref().unwrap_or("(none)"),
+            cfg.db_namespace,
+            cfg.db_name
+        );
+    }
+    let info: Option<serde_json::Value> = res.take(2).map_err(|e| {
+        anyhow::anyhow!(
+            "INFO FOR DB failed on namespace {} / database {} — check PHOTO_DB_NS/PHOTO_DB_NAME ({})",
+            cfg.db_namespace,
+            cfg.db_name,
+            e
+        )
+    })?;
+
+    let tables = info
+        .as_ref()
+        .and_then(|info| info.get("tables"))
+        .and_then(|tables| tables.as_object())
+        .map_or(0, |tables| tables.len());
+    if tables == 0 {
+        tracing::warn!(
+            ns = %cfg.db_namespace,
+            db = %cfg.db_name,
+            "selected database has no tables; check PHOTO_DB_NS/PHOTO_DB_NAME or run photography_schema"
+        );
+    }
+    tracing::info!(ns = %cfg.db_namespace, db = %cfg.db_name, tables, "database selected");
+    Ok(())
+}
+
 pub async fn healthcheck(db: &Surreal<Client>) -> Result<bool> {
     // simple ping via info query
     let mut res = db.query("RETURN 1;").await?;
     let val: Option<i32> = res.take(0)?;
     Ok(val == Some(1))
 }
+
+/// Write probe: creates and deletes a scratch `_healthcheck` record.
+/// Fails if the DB is read-only or the configured user lacks write permission.
+pub async fn write_check(db: &Surreal<Client>) -> Result<()> {
+    db.query(
+        "LET $rec = type::thing('_healthcheck', rand::uuid::v4());
+         CREATE $rec SET checked_at = time::now();
+         DELETE $rec;",
+    )
+    .await?
+    .check()?;
+    Ok(())
+}
+
+/// How often `monitor_connection` pings the database.
+pub const CONNECTION_PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Connection health as observed by probes. The SDK re-establishes a dropped WebSocket on
+/// its own; this records how often the link went down, came back, and why it failed.
+#[derive(Debug)]
+pub struct ConnectionStats {
+    started: Instant,
+    down: AtomicBool,
+    failures: AtomicU64,
+    reconnects: AtomicU64,
+    last_error: Mutex<Option<(String, Instant)>>,
+}
+
+impl Default for ConnectionStats {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            down: AtomicBool::new(false),
+            failures: AtomicU64::new(0),
+            reconnects: AtomicU64::new(0),
+            last_error: Mutex::new(None),
+        }
+    }
+}
+
+impl ConnectionStats {
+    /// A probe or connection attempt failed.
+    pub fn record_failure(&self, error: &str) {
+        self.failures.fetch_add(1, Ordering::Relaxed);
+        if !self.down.swap(true, Ordering::Relaxed) {
+            tracing::warn!(error = %error, "database connection lost");
+        }
+        if let Ok(mut last) = self.last_error.lock() {
+            *last = Some((error.to_string(), Instant::now()));
+        }
+    }
+
+    /// A probe succeeded; counts a reconnect if the previous one had failed.
+    pub fn record_success(&self) {
+        if self.down.swap(false, Ordering::Relaxed) {
+            let reconnects = self.reconnects.fetch_add(1, Ordering::Relaxed) + 1;
+            tracing::info!(reconnects, "database connection restored");
+        }
+    }
+
+    /// Snapshot for the `health` tool.
+    pub fn to_json(&self) -> serde_json::Value {
+        let last_error = self.last_error.lock().ok().and_then(|last| last.clone());
+        serde_json::json!({
+            "connected": !self.down.load(Ordering::Relaxed),
+            "reconnects": self.reconnects.load(Ordering::Relaxed),
+            "failed_probes": self.failures.load(Ordering::Relaxed),
+            "last_error": last_error.as_ref().map(|(error, _)| error),
+            "last_error_secs_ago": last_error.map(|(_, at)| at.elapsed().as_secs()),
+            "uptime_secs": self.started.elapsed().as_secs(),
+        })
+    }
+}
+
+/// `healthcheck` that also updates `stats`.
+pub async fn probe(db: &Surreal<Client>, stats: &ConnectionStats) -> bool {
+    match healthcheck(db).await {
+        Ok(true) => {
+            stats.record_success();
+            true
+        }
+        Ok(false) => {
+            stats.record_failure("health query returned an unexpected value");
+            false
+        }
+        Err(e) => {
+            stats.record_failure(&e.to_string());
+            false
+        }
+    }
+}
+
+/// Ping the database every `interval` so drops and recoveries are noticed between calls.
+pub async fn monitor_connection(
+    db: Surreal<Client>,
+    stats: std::sync::Arc<ConnectionStats>,
+    interval: Duration,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        probe(&db, &stats).await;
+    }
+}
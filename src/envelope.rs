@@ -0,0 +1,168 @@
+//! Common result envelope for the list/get tools.
+//!
+//! On top of each tool's own keys, every list/get response carries `ok` (false on a miss),
+//! `count` (items in `data`), and `data`, always an array of records, plus `message` when
+//! nothing matched. The router applies it after the handler runs, so handlers keep building
+//! their historical shapes and `PHOTO_RESULT_LEGACY_KEYS=0` can drop the duplicated keys.
+
+use serde_json::{Map, Value};
+
+/// Where a tool's records live in its response.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataShape {
+    /// An array under this key (`families`, `shoots`, ...).
+    List(&'static str),
+    /// One record under this key (`shoot`, `family`).
+    Record(&'static str),
+    /// One record spread across the top-level keys (`get_family`, `get_contact`).
+    Flat,
+}
+
+/// Keys that describe the response rather than the record, for `DataShape::Flat`.
+const META_KEYS: &[&str] = &[
+    "ok",
+    "count",
+    "data",
+    "found",
+    "success",
+    "message",
+    "ambiguous",
+];
+
+/// The list/get tools that use the envelope; others pass through unchanged.
+pub fn data_shape(tool: &str) -> Option<DataShape> {
+    use DataShape::*;
+    Some(match tool {
+        "list_families"
+        | "list_incomplete_families"
+        | "list_pending_galleries"
+        | "list_pending_shoot_galleries"
+        | "sent_not_purchased"
+        | "attention_list" => List("families"),
+        "find_skater" | "orphan_skaters" => List("skaters"),
+        "get_contacts_bulk" => List("contacts"),
+        "list_shoots" | "find_shoots" => List("shoots"),
+        "list_competitions" => List("competitions"),
+        "list_notes" => List("notes"),
+        "tag_summary" => List("tags"),
+        "distinct_statuses" => List("statuses"),
+        "get_skater_gallery" => List("galleries"),
+        "recent_activity" => List("events"),
+        "get_shoot" => Record("shoot"),
+        "find_family_by_sp_gallery" => Record("family"),
+        "get_family" | "get_contact" | "get_competition" => Flat,
+        _ => return None,
+    })
+}
+
+/// Adds `ok`/`count`/`data` (and `message` on a miss). With `legacy_keys` off, the
+/// original record keys are removed so records appear only under `data`.
+pub fn apply(body: &mut Map<String, Value>, shape: DataShape, legacy_keys: bool) {
+    let ok = !matches!(body.get("found"), Some(Value::Bool(false)))
+        && !matches!(body.get("success"), Some(Value::Bool(false)));
+
+    let data: Vec<Value> = if !ok {
+        Vec::new()
+    } else {
+        match shape {
+            DataShape::List(key) => {
+                let items = match body.get(key) {
+                    Some(Value::Array(items)) => items.clone(),
+                    _ => Vec::new(),
+                };
+                if !legacy_keys {
+                    body.remove(key);
+                }
+                items
+            }
+            DataShape::Record(key) => {
+                let record = body.get(key).filter(|v| !v.is_null()).cloned();
+                if !legacy_keys {
+                    body.remove(key);
+                }
+                record.into_iter().collect()
+            }
+            DataShape::Flat => {
+                let keys: Vec<String> = body
+                    .keys()
+                    .filter(|k| !META_KEYS.contains(&k.as_str()))
+                    .cloned()
+                    .collect();
+                let mut record = Map::new();
+                for key in keys {
+                    let value = if legacy_keys {
+                        body[&key].clone()
+                    } else {
+                        body.remove(&key).unwrap_or_default()
+                    };
+                    record.insert(key, value);
+                }
+                vec![Value::Object(record)]
+            }
+        }
+    };
+
+    if data.is_empty() && !body.contains_key("message") {
+        body.insert("message".into(), Value::from("No matching records"));
+    }
+    body.insert("ok".into(), Value::from(ok));
+    body.insert("count".into(), Value::from(data.len()));
+    body.insert("data".into(), Value::Array(data));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DataShape, apply};
+    use serde_json::json;
+
+    fn run(body: serde_json::Value, shape: DataShape, legacy: bool) -> serde_json::Value {
+        let mut map = body.as_object().unwrap().clone();
+        apply(&mut map, shape, legacy);
+        serde_json::Value::Object(map)
+    }
+
+    #[test]
+    fn list_keeps_legacy_key_and_adds_data() {
+        let out = run(
+            json!({ "families": [{ "id": "family:a" }, { "id": "family:b" }], "limit": 50 }),
+            DataShape::List("families"),
+            true,
+        );
+        assert_eq!(out["ok"], true);
+        assert_eq!(out["count"], 2);
+        assert_eq!(out["data"], out["families"]);
+        assert_eq!(out["limit"], 50);
+    }
+
+    #[test]
+    fn miss_is_not_ok_with_empty_data_and_message() {
+        let out = run(
+            json!({ "found": false, "message": "No shoot found matching: x" }),
+            DataShape::Record("shoot"),
+            true,
+        );
+        assert_eq!(out["ok"], false);
+        assert_eq!(out["count"], 0);
+        assert_eq!(out["data"], json!([]));
+        assert_eq!(out["message"], "No shoot found matching: x");
+
+        let empty = run(json!({ "shoots": [] }), DataShape::List("shoots"), true);
+        assert_eq!(empty["ok"], true);
+        assert_eq!(empty["message"], "No matching records");
+    }
+
+    #[test]
+    fn flat_record_without_legacy_keys() {
+        let out = run(
+            json!({ "found": true, "family_id": "family:a", "email": "a@x.com" }),
+            DataShape::Flat,
+            false,
+        );
+        assert_eq!(
+            out["data"],
+            json!([{ "family_id": "family:a", "email": "a@x.com" }])
+        );
+        assert!(out.get("family_id").is_none());
+        assert_eq!(out["found"], true);
+    }
+}
@@ -0,0 +1,192 @@
+//! Streaming export routes for the HTTP transport.
+//!
+//! Mounted next to `/mcp` behind the same bearer-auth layer. Rows are read from SurrealDB a
+//! page at a time and written to the response body as they arrive, so whole-database exports
+//! never sit in memory. Export tools hand back these paths when a result is too large to inline.
+
+use crate::server::{PhotoMindServer, SALE_EDGES, SaleRow, sales_csv_chunk};
+use axum::{
+    Router,
+    body::Body,
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use futures::TryStreamExt;
+use std::collections::VecDeque;
+
+pub const SALES_CSV_PATH: &str = "/exports/sales.csv";
+
+/// Rows fetched per database round trip.
+const PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct SalesQuery {
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+}
+
+pub fn routes(server: PhotoMindServer) -> Router {
+    Router::new()
+        .route(SALES_CSV_PATH, get(sales_csv))
+        .with_state(server)
+}
+
+/// Path (with query string) for the streaming sales export over a date range.
+pub fn sales_csv_path(start_date: &Option<String>, end_date: &Option<String>) -> String {
+    let params: Vec<String> = [("start_date", start_date), ("end_date", end_date)]
+        .into_iter()
+        .filter_map(|(key, value)| value.as_ref().map(|v| format!("{}={}", key, encode(v))))
+        .collect();
+    if params.is_empty() {
+        SALES_CSV_PATH.to_string()
+    } else {
+        format!("{}?{}", SALES_CSV_PATH, params.join("&"))
+    }
+}
+
+/// Fetched-but-unsent rows of one edge table and where its next page starts.
+#[derive(Default)]
+struct EdgeBuffer {
+    rows: VecDeque<SaleRow>,
+    /// (date, id) of the last row fetched; the keyset for the next page
+    after: Option<(String, String)>,
+    exhausted: bool,
+}
+
+/// Position in the sales stream: one buffer per `SALE_EDGES` table, and running totals.
+struct SalesCursor {
+    server: PhotoMindServer,
+    query: SalesQuery,
+    edges: Vec<EdgeBuffer>,
+    count: usize,
+    total: f64,
+    header_sent: bool,
+    done: bool,
+}
+
+async fn sales_csv(
+    State(server): State<PhotoMindServer>,
+    Query(query): Query<SalesQuery>,
+) -> Response {
+    let cursor = SalesCursor {
+        server,
+        query,
+        edges: SALE_EDGES.iter().map(|_| EdgeBuffer::default()).collect(),
+        count: 0,
+        total: 0.0,
+        header_sent: false,
+        done: false,
+    };
+    let stream = futures::stream::try_unfold(cursor, next_sales_chunk)
+        .inspect_err(|e| tracing::warn!(error = %e, "sales export stream failed"));
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"sales.csv\"",
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response()
+}
+
+/// Next CSV fragment: header + first rows, further rows, then the TOTAL row. Each edge
+/// table is paged in (date, id) order and the tables are merged by date, so the stream comes
+/// out in the same date order as the inline export.
+async fn next_sales_chunk(
+    mut cursor: SalesCursor,
+) -> anyhow::Result<Option<(String, SalesCursor)>> {
+    if cursor.done {
+        return Ok(None);
+    }
+
+    // Top up every table whose buffer ran dry
+    for (buffer, &(edge_table, event_type)) in cursor.edges.iter_mut().zip(SALE_EDGES.iter()) {
+        if !buffer.rows.is_empty() || buffer.exhausted {
+            continue;
+        }
+        let after = buffer
+            .after
+            .as_ref()
+            .map(|(date, id)| (date.as_str(), id.as_str()));
+        let rows = cursor
+            .server
+            .fetch_sales(
+                edge_table,
+                event_type,
+                &cursor.query.start_date,
+                &cursor.query.end_date,
+                Some((after, PAGE_SIZE)),
+            )
+            .await?;
+        buffer.exhausted = rows.len() < PAGE_SIZE;
+        if let Some(last) = rows.last() {
+            buffer.after = Some((last.date.clone(), last.id.clone()));
+        }
+        buffer.rows.extend(rows);
+    }
+
+    // Merge by date until a table that may still have rows runs dry (its next page could
+    // hold earlier dates than the other table's buffer)
+    let mut rows = Vec::new();
+    while !cursor
+        .edges
+        .iter()
+        .any(|buffer| buffer.rows.is_empty() && !buffer.exhausted)
+    {
+        let earliest = cursor
+            .edges
+            .iter_mut()
+            .filter(|buffer| !buffer.rows.is_empty())
+            .min_by(|a, b| a.rows[0].date.cmp(&b.rows[0].date));
+        match earliest.and_then(|buffer| buffer.rows.pop_front()) {
+            Some(row) => rows.push(row),
+            None => break,
+        }
+    }
+
+    if rows.is_empty() {
+        cursor.done = true;
+        let chunk = sales_csv_chunk(&[], !cursor.header_sent, Some((cursor.count, cursor.total)))?;
+        return Ok(Some((chunk, cursor)));
+    }
+
+    cursor.count += rows.len();
+    cursor.total += rows.iter().map(|r| r.amount).sum::<f64>();
+
+    let chunk = sales_csv_chunk(&rows, !cursor.header_sent, None)?;
+    cursor.header_sent = true;
+    Ok(Some((chunk, cursor)))
+}
+
+/// Percent-encodes a query parameter value (RFC 3986 unreserved characters pass through).
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sales_csv_path;
+
+    #[test]
+    fn sales_csv_path_encodes_date_filters() {
+        assert_eq!(sales_csv_path(&None, &None), "/exports/sales.csv");
+        assert_eq!(
+            sales_csv_path(&Some("2025-01-01".into()), &Some("2025 12/31".into())),
+            "/exports/sales.csv?start_date=2025-01-01&end_date=2025%2012%2F31"
+        );
+    }
+}
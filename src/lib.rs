@@ -1,5 +1,9 @@
+pub mod args;
 pub mod config;
 pub mod db;
+pub mod envelope;
+pub mod export;
 pub mod photography;
 pub mod router;
 pub mod server;
+pub mod summary;
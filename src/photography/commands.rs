@@ -34,6 +34,9 @@ pub async fn import_roster(db: &Surreal<Client>, competition: &str, file_path: &
     comp_resp.check()?;
 
     // Read CSV
+    let len = std::fs::metadata(file_path)?.len();
+    check_import_size(file_path, len, crate::config::max_import_bytes())
+        .map_err(anyhow::Error::msg)?;
     let file = File::open(file_path)?;
     let mut rdr = csv::Reader::from_reader(file);
 
@@ -109,12 +112,7 @@ pub async fn import_roster(db: &Surreal<Client>, competition: &str, file_path: &
 
         // For each skater
         for skater in &parsed.skaters {
-            let skater_id = format!(
-                "{}_{}",
-                skater.last_name.to_lowercase(),
-                skater.first_name.to_lowercase()
-            )
-            .replace('-', "_");
+            let skater_id = skater_key(&skater.first_name, &skater.last_name);
 
             // Upsert skater
             let skater_resp = db
@@ -410,7 +408,10 @@ pub async fn record_purchase(
     last_name: &str,
     amount: f64,
     comp: &str,
+    force: bool,
 ) -> Result<()> {
+    validate_purchase_amount(amount, crate::config::max_purchase_amount(), force)
+        .map_err(|e| anyhow::anyhow!("Invalid purchase amount: {}", e))?;
     let comp_resolved = resolve_competition(db, comp).await?;
     let family_id_full = format_family_id(last_name);
     let family_id_only = last_name.to_lowercase().replace(" ", "_");
@@ -593,7 +594,12 @@ pub async fn update_gallery(
     status: &str,
     url: Option<&str>,
     amount: Option<f64>,
+    force: bool,
 ) -> Result<()> {
+    if let Some(a) = amount {
+        validate_purchase_amount(a, crate::config::max_purchase_amount(), force)
+            .map_err(|e| anyhow::anyhow!("Invalid purchase amount: {}", e))?;
+    }
     // Parse skater names
     let parsed = parse_skater_names(skater)?;
     if parsed.skaters.len() != 1 {
@@ -603,12 +609,7 @@ pub async fn update_gallery(
     let skater = &parsed.skaters[0];
 
     // Find the relation
-    let skater_id = format!(
-        "{}_{}",
-        skater.last_name.to_lowercase(),
-        skater.first_name.to_lowercase()
-    )
-    .replace('-', "_");
+    let skater_id = skater_key(&skater.first_name, &skater.last_name);
 
     let mut resp = db
         .query(
@@ -3,3 +3,17 @@ pub mod models;
 pub mod utils;
 
 pub const DEFAULT_COMPETITION: &str = "2025_fall_fling";
+
+/// Allowed `gallery_status` values on family_competition / family_shoot edges (mirrors the schema ASSERT).
+pub const GALLERY_STATUSES: &[&str] = &[
+    "pending",
+    "culling",
+    "processing",
+    "sent",
+    "purchased",
+    "not_shot",
+    "needs_research",
+];
+
+/// Gallery statuses treated as "pending" when `PHOTO_PENDING_STATUSES` is unset.
+pub const DEFAULT_PENDING_STATUSES: &[&str] = &["pending", "culling", "processing"];
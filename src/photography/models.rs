@@ -9,6 +9,60 @@ pub struct Shoot {
     pub shoot_date: Option<String>,
     pub location: Option<String>,
     pub notes: Option<String>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub packages: Option<Vec<ShootPackage>>,
+    #[serde(default)]
+    pub external_key: Option<String>,
+    /// Unique handle (`fall-minis-2025-10-04`) that shoot lookups match before the name
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// Set by `archive_season`; archived shoots are left out of listings by default
+    #[serde(default)]
+    pub archived: Option<bool>,
+}
+
+/// Priced package offered at a shoot (e.g. "Mini Digital", 7500 cents)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShootPackage {
+    pub name: String,
+    pub price_cents: i64,
+}
+
+/// Competition record; `date`/`location` are absent on competitions created by older imports,
+/// which only carry the legacy `start_date`/`venue` (see `event_date`/`event_location`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Competition {
+    pub id: Thing,
+    pub name: String,
+    #[serde(default)]
+    pub date: Option<String>,
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub venue: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Set by `archive_season`; archived competitions are left out of listings by default
+    #[serde(default)]
+    pub archived: Option<bool>,
+}
+
+impl Competition {
+    /// `date`, else the legacy `start_date`.
+    pub fn event_date(&self) -> Option<&str> {
+        self.date.as_deref().or(self.start_date.as_deref())
+    }
+
+    /// `location`, else the legacy `venue` (left blank by imports that had none).
+    pub fn event_location(&self) -> Option<&str> {
+        self.location
+            .as_deref()
+            .or(self.venue.as_deref().filter(|v| !v.trim().is_empty()))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -103,6 +157,13 @@ pub struct ParsedName {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PendingFamily {
     pub family: Option<String>,
+    /// Primary delivery address
     pub email: Option<String>,
+    /// Every delivery address, primary first
+    #[serde(
+        default,
+        deserialize_with = "crate::photography::utils::deserialize_delivery_emails"
+    )]
+    pub emails: Vec<String>,
     pub gallery_status: Option<String>,
 }
@@ -1,20 +1,278 @@
 // Extracted from src/bin/photography.rs
 // Helper functions for photography module
 
+use crate::config::FamilyIdStrategy;
 use anyhow::Result;
 use strsim::jaro_winkler;
 use surrealdb::{Surreal, engine::remote::ws::Client};
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
 
 /// Formats a family ID for use in SurrealDB queries.
 /// Ensures underscores instead of spaces, and backticks if non-alphanumeric characters are present.
 pub fn format_family_id(last_name: &str) -> String {
-    let lower = last_name.to_lowercase().replace(" ", "_");
+    format_record_id("family", &family_slug(last_name))
+}
+
+/// Formats `table:key`, backtick-quoting the key if it has non-alphanumeric characters.
+pub fn format_record_id(table: &str, key: &str) -> String {
     // Check for non-alphanumeric characters (excluding underscore)
-    if lower.chars().any(|c| !c.is_alphanumeric() && c != '_') {
-        format!("family:`{}`", lower)
+    if key.chars().any(|c| !c.is_alphanumeric() && c != '_') {
+        format!("{}:`{}`", table, key)
     } else {
-        format!("family:{}", lower)
+        format!("{}:{}", table, key)
+    }
+}
+
+/// Skater record key as written by the roster import: `last_first`, lowercase, hyphens as
+/// underscores ("Mary-Kate", "Olsen" -> "olsen_mary_kate").
+pub fn skater_key(first_name: &str, last_name: &str) -> String {
+    format!("{}_{}", last_name.to_lowercase(), first_name.to_lowercase()).replace('-', "_")
+}
+
+/// Name-derived family key: lowercase with spaces as underscores ("Ruiz Peace" -> "ruiz_peace").
+pub fn family_slug(last_name: &str) -> String {
+    last_name.to_lowercase().replace(' ', "_")
+}
+
+/// Checks a purchase amount before it is stored: it must be positive, and above `ceiling`
+/// it is only accepted with `force`, so a fat-fingered entry can't skew revenue totals.
+pub fn validate_purchase_amount(
+    amount: f64,
+    ceiling: Option<f64>,
+    force: bool,
+) -> std::result::Result<(), String> {
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err(format!("must be a positive amount, got {}", amount));
+    }
+    if let Some(max) = ceiling
+        && amount > max
+        && !force
+    {
+        return Err(format!(
+            "{} is above the {} sanity ceiling (PHOTO_MAX_PURCHASE_AMOUNT); use force to record it anyway",
+            amount, max
+        ));
+    }
+    Ok(())
+}
+
+/// Checks an import file's size (from metadata) before it is read into memory, so one
+/// oversized or runaway file can't exhaust the server.
+pub fn check_import_size(
+    path: &str,
+    len: u64,
+    limit: Option<u64>,
+) -> std::result::Result<(), String> {
+    match limit {
+        Some(max) if len > max => Err(format!(
+            "{} is {} bytes, above the {}-byte import limit (PHOTO_MAX_IMPORT_BYTES)",
+            path, len, max
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Display name for a family: `last_name`, else `name`, else "Unknown". A template such as
+/// `"{last_name} Family"` is applied to known names (`{last_name}` and `{name}` placeholders).
+pub fn family_display_name(
+    last_name: Option<&str>,
+    name: Option<&str>,
+    template: Option<&str>,
+) -> String {
+    let non_empty = |s: Option<&str>| s.map(str::trim).filter(|s| !s.is_empty());
+    let Some(base) = non_empty(last_name).or_else(|| non_empty(name)) else {
+        return "Unknown".to_string();
+    };
+    match template {
+        Some(template) => template
+            .replace("{last_name}", base)
+            .replace("{name}", non_empty(name).unwrap_or(base)),
+        None => base.to_string(),
+    }
+}
+
+/// Record key for a new family under the given id strategy.
+pub fn new_family_key(last_name: &str, strategy: FamilyIdStrategy) -> String {
+    match strategy {
+        FamilyIdStrategy::NameSlug => family_slug(last_name),
+        FamilyIdStrategy::Uuid => uuid::Uuid::new_v4().to_string(),
+    }
+}
+
+/// Folds a name for loose matching: lowercases and strips accents ("Müller" -> "muller").
+pub fn fold_name(name: &str) -> String {
+    name.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// URL-style shoot handle from its name and optional date: folded, non-alphanumerics as
+/// single hyphens ("Fall Minis", "2025-10-04" -> "fall-minis-2025-10-04").
+pub fn shoot_slug(name: &str, date: Option<&str>) -> String {
+    let date = date.map(|d| d.trim().get(..10).unwrap_or(d.trim()));
+    let raw = match date {
+        Some(date) => format!("{} {}", name, date),
+        None => name.to_string(),
+    };
+    fold_name(&raw)
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// `base`, or `base-2`, `base-3`, ... for the first one not already in `taken`.
+pub fn unique_slug(base: &str, taken: &[String]) -> String {
+    if !taken.iter().any(|t| t == base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("unbounded suffix range")
+}
+
+/// Trims and sanity-checks a delivery address: one `@`, a dotted domain, no whitespace.
+pub fn validate_email(raw: &str) -> std::result::Result<String, String> {
+    let email = raw.trim();
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+    if valid {
+        Ok(email.to_string())
+    } else {
+        Err(format!("'{}' is not a valid email address", email))
+    }
+}
+
+/// Every delivery address for a family: the primary `delivery_email` first, then the
+/// `delivery_emails` list, without blanks or case-insensitive repeats.
+pub fn merge_delivery_emails(primary: Option<&str>, extra: &[String]) -> Vec<String> {
+    let mut emails: Vec<String> = Vec::new();
+    for email in primary.into_iter().chain(extra.iter().map(String::as_str)) {
+        let email = email.trim();
+        if !email.is_empty() && !emails.iter().any(|e| e.eq_ignore_ascii_case(email)) {
+            emails.push(email.to_string());
+        }
+    }
+    emails
+}
+
+/// `deserialize_with` for address lists built query-side (`IN_DELIVERY_EMAILS`): runs them
+/// through `merge_delivery_emails` so rows match the Rust merge (no blanks, case-insensitive).
+pub fn deserialize_delivery_emails<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw: Option<Vec<String>> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(merge_delivery_emails(None, &raw.unwrap_or_default()))
+}
+
+/// Surname similarity above which `create_family` reports a possible duplicate.
+pub const DUPLICATE_SURNAME_THRESHOLD: f64 = 0.9;
+
+/// Jaro-Winkler similarity of two surnames after folding case and accents (1.0 = same).
+pub fn surname_similarity(a: &str, b: &str) -> f64 {
+    jaro_winkler(&fold_name(a.trim()), &fold_name(b.trim()))
+}
+
+/// Presentable surname for storage when `PHOTO_NORMALIZE_NAME_CASE` is on. An `overrides`
+/// entry matching case-insensitively wins ("DeLuca", "van der Berg"). Otherwise names typed
+/// all lowercase or all uppercase are title-cased, with a capital after `-` and `'` and after
+/// a leading "Mc" ("o'brien" -> "O'Brien", "MCDONALD" -> "McDonald"); mixed case is kept.
+pub fn normalize_surname(raw: &str, overrides: &[String]) -> String {
+    let name = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if let Some(exact) = overrides
+        .iter()
+        .find(|o| o.trim().to_lowercase() == name.to_lowercase())
+    {
+        return exact.trim().to_string();
+    }
+    let letters = || name.chars().filter(|c| c.is_alphabetic());
+    let uniform = letters().all(char::is_lowercase) || letters().all(char::is_uppercase);
+    if !uniform {
+        return name;
+    }
+
+    let mut out = String::with_capacity(name.len());
+    let mut capitalize = true;
+    let mut segment = String::new();
+    for c in name.chars() {
+        if capitalize && c.is_alphabetic() {
+            out.extend(c.to_uppercase());
+            capitalize = false;
+        } else {
+            out.extend(c.to_lowercase());
+        }
+        segment.extend(c.to_lowercase());
+        if matches!(c, ' ' | '-' | '\'') {
+            capitalize = true;
+            segment.clear();
+        } else if segment == "mc" {
+            capitalize = true;
+        }
+    }
+    out
+}
+
+/// Median of `values` (the mean of the middle two for an even count); `None` when empty.
+pub fn median(values: &[f64]) -> Option<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        n if n % 2 == 0 => Some((sorted[mid - 1] + sorted[mid]) / 2.0),
+        _ => Some(sorted[mid]),
+    }
+}
+
+/// Longest span `calendar_months` will expand, so a typo'd year can't build a huge series.
+pub const MAX_TREND_MONTHS: usize = 240;
+
+/// Every calendar month (`YYYY-MM`) from `start` to `end` inclusive. Both accept `YYYY-MM`
+/// or a longer `YYYY-MM-DD...` date; only the year and month are used.
+pub fn calendar_months(start: &str, end: &str) -> std::result::Result<Vec<String>, String> {
+    fn year_month(date: &str) -> Option<(i32, u32)> {
+        let year = date.get(0..4)?.parse().ok()?;
+        if date.get(4..5)? != "-" {
+            return None;
+        }
+        let month: u32 = date.get(5..7)?.parse().ok()?;
+        (1..=12).contains(&month).then_some((year, month))
+    }
+
+    let (mut year, mut month) =
+        year_month(start.trim()).ok_or_else(|| format!("invalid start date: {}", start))?;
+    let end = year_month(end.trim()).ok_or_else(|| format!("invalid end date: {}", end))?;
+    if (year, month) > end {
+        return Err("start date is after end date".to_string());
+    }
+
+    let mut months = Vec::new();
+    while (year, month) <= end {
+        if months.len() == MAX_TREND_MONTHS {
+            return Err(format!("range spans more than {} months", MAX_TREND_MONTHS));
+        }
+        months.push(format!("{:04}-{:02}", year, month));
+        (year, month) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
     }
+    Ok(months)
 }
 
 /// Parses skater names from a string, handling families, synchro, and multiple skaters.
@@ -191,3 +449,195 @@ pub async fn resolve_competition(db: &Surreal<Client>, input: &str) -> Result<St
         available
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DUPLICATE_SURNAME_THRESHOLD, FamilyIdStrategy, calendar_months, check_import_size,
+        deserialize_delivery_emails, family_display_name, fold_name, format_family_id, median,
+        merge_delivery_emails, new_family_key, normalize_surname, shoot_slug, skater_key,
+        surname_similarity, unique_slug, validate_email, validate_purchase_amount,
+    };
+
+    #[test]
+    fn email_validation() {
+        assert_eq!(
+            validate_email(" mom@example.com ").unwrap(),
+            "mom@example.com"
+        );
+        assert!(validate_email("mom@example").is_err());
+        assert!(validate_email("mom example@x.com").is_err());
+        assert!(validate_email("@example.com").is_err());
+        assert!(validate_email("a@b@example.com").is_err());
+    }
+
+    #[test]
+    fn delivery_emails_put_primary_first_without_repeats() {
+        let extra = vec!["Dad@example.com".to_string(), "mom@example.com".to_string()];
+        assert_eq!(
+            merge_delivery_emails(Some("MOM@example.com"), &extra),
+            vec!["MOM@example.com", "Dad@example.com"]
+        );
+        assert_eq!(merge_delivery_emails(None, &[]), Vec::<String>::new());
+    }
+
+    #[test]
+    fn query_side_email_lists_get_the_same_merge() {
+        let emails =
+            |json: serde_json::Value| deserialize_delivery_emails(json).map_err(|e| e.to_string());
+        assert_eq!(
+            emails(serde_json::json!([
+                "mom@example.com",
+                "",
+                "MOM@example.com",
+                " dad@x.com "
+            ])),
+            Ok(vec!["mom@example.com".to_string(), "dad@x.com".to_string()])
+        );
+        assert_eq!(emails(serde_json::Value::Null), Ok(vec![]));
+    }
+
+    #[test]
+    fn near_duplicate_surnames() {
+        assert!(surname_similarity("Müller", "muller ") > 0.99);
+        assert!(surname_similarity("Johnson", "Jonson") >= DUPLICATE_SURNAME_THRESHOLD);
+        assert!(surname_similarity("Smith", "Clements") < DUPLICATE_SURNAME_THRESHOLD);
+    }
+
+    #[test]
+    fn import_size_limit() {
+        assert!(check_import_size("g.json", 1024, Some(1024)).is_ok());
+        assert!(check_import_size("g.json", 1025, Some(1024)).is_err());
+        assert!(check_import_size("g.json", u64::MAX, None).is_ok());
+    }
+
+    #[test]
+    fn fold_name_strips_accents_and_case() {
+        assert_eq!(fold_name("Müller"), "muller");
+        assert_eq!(fold_name("JOSÉ"), "jose");
+        assert_eq!(fold_name("Zoë Brontë-Ćorić"), "zoe bronte-coric");
+    }
+
+    #[test]
+    fn fold_name_matches_accented_names_from_plain_query() {
+        let roster = ["Müller", "Muller", "Nuñez", "Smith"];
+        let query = fold_name("muller");
+        let hits: Vec<_> = roster
+            .iter()
+            .filter(|n| fold_name(n).contains(&query))
+            .collect();
+        assert_eq!(hits, [&"Müller", &"Muller"]);
+        assert!(fold_name("Nuñez").contains(&fold_name("Nunez")));
+    }
+
+    #[test]
+    fn family_keys_follow_strategy() {
+        assert_eq!(format_family_id("Ruiz Peace"), "family:ruiz_peace");
+        assert_eq!(format_family_id("O'Brien"), "family:`o'brien`");
+        assert_eq!(
+            new_family_key("Ruiz Peace", FamilyIdStrategy::NameSlug),
+            "ruiz_peace"
+        );
+        let a = new_family_key("Smith", FamilyIdStrategy::Uuid);
+        let b = new_family_key("Smith", FamilyIdStrategy::Uuid);
+        assert_eq!(a.len(), 36);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn family_display_name_fallbacks_and_template() {
+        assert_eq!(
+            family_display_name(Some("Smith"), Some("smith"), None),
+            "Smith"
+        );
+        assert_eq!(
+            family_display_name(Some("  "), Some("Ruiz Peace"), None),
+            "Ruiz Peace"
+        );
+        assert_eq!(
+            family_display_name(None, None, Some("{last_name} Family")),
+            "Unknown"
+        );
+        assert_eq!(
+            family_display_name(Some("Knox"), None, Some("{last_name} Family")),
+            "Knox Family"
+        );
+    }
+
+    #[test]
+    fn purchase_amount_must_be_positive_and_under_ceiling() {
+        assert!(validate_purchase_amount(120.0, Some(5000.0), false).is_ok());
+        assert!(validate_purchase_amount(0.0, Some(5000.0), false).is_err());
+        assert!(validate_purchase_amount(-45.0, None, true).is_err());
+        assert!(validate_purchase_amount(f64::NAN, None, false).is_err());
+        assert!(validate_purchase_amount(1e9, Some(5000.0), false).is_err());
+        assert!(validate_purchase_amount(1e9, Some(5000.0), true).is_ok());
+        assert!(validate_purchase_amount(1e9, None, false).is_ok());
+    }
+
+    #[test]
+    fn skater_key_matches_import_format() {
+        assert_eq!(skater_key("Jane", "Smith"), "smith_jane");
+        assert_eq!(skater_key("Mary-Kate", "Olsen"), "olsen_mary_kate");
+    }
+
+    #[test]
+    fn calendar_months_spans_year_boundary() {
+        assert_eq!(
+            calendar_months("2024-11-15", "2025-02-01").unwrap(),
+            vec!["2024-11", "2024-12", "2025-01", "2025-02"]
+        );
+        assert_eq!(
+            calendar_months("2024-03", "2024-03").unwrap(),
+            vec!["2024-03"]
+        );
+        assert!(calendar_months("2024-05-01", "2024-04-30").is_err());
+        assert!(calendar_months("2024-13-01", "2025-01-01").is_err());
+        assert!(calendar_months("May 2024", "2025-01-01").is_err());
+        assert!(calendar_months("1900-01", "2100-01").is_err());
+    }
+
+    #[test]
+    fn median_of_odd_even_and_empty() {
+        assert_eq!(median(&[9.0, 1.0, 4.0]), Some(4.0));
+        assert_eq!(median(&[3.0, 1.0, 4.0, 2.0]), Some(2.5));
+        assert_eq!(median(&[]), None);
+    }
+
+    #[test]
+    fn shoot_slug_folds_name_and_date() {
+        assert_eq!(
+            shoot_slug("Fall Minis", Some("2025-10-04T00:00:00Z")),
+            "fall-minis-2025-10-04"
+        );
+        assert_eq!(shoot_slug("  Café   Headshots! ", None), "cafe-headshots");
+        assert_eq!(shoot_slug("Camp", Some("20250704")), "camp-20250704");
+    }
+
+    #[test]
+    fn unique_slug_appends_first_free_suffix() {
+        let taken = vec!["fall-minis".to_string(), "fall-minis-2".to_string()];
+        assert_eq!(unique_slug("fall-minis", &taken), "fall-minis-3");
+        assert_eq!(unique_slug("spring-minis", &taken), "spring-minis");
+    }
+
+    #[test]
+    fn normalize_surname_title_cases_uniform_input() {
+        assert_eq!(normalize_surname("smith", &[]), "Smith");
+        assert_eq!(normalize_surname("SMITH", &[]), "Smith");
+        assert_eq!(normalize_surname("o'brien", &[]), "O'Brien");
+        assert_eq!(normalize_surname("MCDONALD", &[]), "McDonald");
+        assert_eq!(normalize_surname("ruiz-peace", &[]), "Ruiz-Peace");
+        assert_eq!(normalize_surname("  van  berg ", &[]), "Van Berg");
+        // Deliberate mixed case is left alone
+        assert_eq!(normalize_surname("DeLuca", &[]), "DeLuca");
+
+        let overrides = ["van der Berg".to_string(), "MacLeod".to_string()];
+        assert_eq!(
+            normalize_surname("VAN DER BERG", &overrides),
+            "van der Berg"
+        );
+        assert_eq!(normalize_surname("macleod", &overrides), "MacLeod");
+        assert_eq!(normalize_surname("mack", &overrides), "Mack");
+    }
+}
@@ -1,14 +1,18 @@
+use crate::args::InvalidParams;
+use crate::photography::GALLERY_STATUSES;
 use crate::server::PhotoMindServer;
 use rmcp::{
     ErrorData as McpError,
     handler::server::ServerHandler,
     model::{
-        CallToolRequestParam, CallToolResult, Implementation, InitializeRequestParam,
+        CallToolRequestParam, CallToolResult, Content, Implementation, InitializeRequestParam,
         InitializeResult, ListToolsResult, PaginatedRequestParam, ProtocolVersion,
         ServerCapabilities, ServerInfo, Tool, ToolsCapability,
     },
     service::RequestContext,
 };
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
 #[derive(Clone)]
 pub struct Router(pub PhotoMindServer);
@@ -49,6 +53,138 @@ impl ServerHandler for Router {
         _request: Option<PaginatedRequestParam>,
         _context: RequestContext<rmcp::service::RoleServer>,
     ) -> std::result::Result<ListToolsResult, McpError> {
+        let mut tools = Self::tool_definitions();
+        tools.retain(|tool| self.tool_enabled(&tool.name));
+
+        Ok(ListToolsResult {
+            tools,
+            ..Default::default()
+        })
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<rmcp::service::RoleServer>,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        // Per-call id: in the tracing span, on every error, and in structured results
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let span = tracing::info_span!("call_tool", request_id = %request_id, tool = %request.name);
+        let request_name = request.name.to_string();
+
+        let result = if self.tool_enabled(&request.name) {
+            self.dispatch(request, &context.ct)
+                .instrument(span.clone())
+                .await
+        } else {
+            Err(McpError {
+                code: rmcp::model::ErrorCode::METHOD_NOT_FOUND,
+                message: format!("Unknown tool: {}", request.name).into(),
+                data: None,
+            })
+        };
+
+        match result {
+            Ok(mut result) => {
+                if let Some(serde_json::Value::Object(mut body)) = result.structured_content.take()
+                {
+                    if let Some(shape) = crate::envelope::data_shape(&request_name) {
+                        let legacy_keys = self.0.cfg().result_legacy_keys;
+                        crate::envelope::apply(&mut body, shape, legacy_keys);
+                    }
+                    body.insert("_request_id".into(), serde_json::json!(request_id));
+                    // Text-only clients get a readable summary ahead of the JSON text
+                    let summary = crate::summary::text_summary(&request_name, &body);
+                    let is_error = result.is_error;
+                    result = CallToolResult::structured(serde_json::Value::Object(body));
+                    result.content.insert(0, Content::text(summary));
+                    result.is_error = is_error;
+                }
+                Ok(result)
+            }
+            Err(mut err) => {
+                span.in_scope(
+                    || tracing::warn!(code = ?err.code, error = %err.message, "tool call failed"),
+                );
+                match err.data.as_mut() {
+                    Some(serde_json::Value::Object(data)) => {
+                        data.insert("request_id".into(), serde_json::json!(request_id));
+                    }
+                    _ => err.data = Some(serde_json::json!({ "request_id": request_id })),
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+impl Router {
+    /// Whether `name` is exposed: not listed in `PHOTO_DISABLED_TOOLS`, and admin tools only
+    /// when their flag is set. Disabled tools are hidden and answer `METHOD_NOT_FOUND`.
+    fn tool_enabled(&self, name: &str) -> bool {
+        let cfg = self.0.cfg();
+        let gated_on = match name {
+            "reload_config" => cfg.admin_tools,
+            "raw_query" => cfg.raw_query,
+            _ => true,
+        };
+        gated_on && !cfg.disabled_tools.iter().any(|t| t == name)
+    }
+
+    /// `PHOTO_DISABLED_TOOLS` entries that don't name a tool (checked at startup).
+    pub fn unknown_disabled_tools(&self) -> Vec<String> {
+        let known: Vec<String> = Self::tool_definitions()
+            .into_iter()
+            .map(|tool| tool.name.to_string())
+            .collect();
+        self.0
+            .cfg()
+            .disabled_tools
+            .iter()
+            .filter(|t| !known.contains(t))
+            .cloned()
+            .collect()
+    }
+
+    /// `list_tools` as OpenAI function definitions: same source and the same
+    /// `tool_enabled` filtering, with `input_schema` becoming `parameters`.
+    fn handle_export_function_specs(
+        &self,
+        request: CallToolRequestParam,
+    ) -> anyhow::Result<CallToolResult> {
+        let mut args = crate::args::Args::new(&request);
+        let style = args
+            .optional_str("style")
+            .unwrap_or_else(|| "functions".to_string());
+        if style != "functions" && style != "tools" {
+            args.invalid("style", "must be functions or tools");
+        }
+        args.finish()?;
+
+        let specs: Vec<serde_json::Value> = Self::tool_definitions()
+            .iter()
+            .filter(|tool| self.tool_enabled(&tool.name))
+            .map(|tool| {
+                let spec = function_spec(tool);
+                if style == "tools" {
+                    serde_json::json!({ "type": "function", "function": spec })
+                } else {
+                    spec
+                }
+            })
+            .collect();
+
+        // Results sit under the same key the target API uses: `functions` or `tools`
+        let mut body = serde_json::json!({
+            "style": style,
+            "count": specs.len(),
+        });
+        body[style.as_str()] = serde_json::Value::Array(specs);
+        Ok(CallToolResult::structured(body))
+    }
+
+    /// Every tool this server implements, before `tool_enabled` filtering.
+    fn tool_definitions() -> Vec<Tool> {
         // Helper to create schema Arc from JSON
         fn schema(
             json: serde_json::Value,
@@ -78,6 +214,22 @@ impl ServerHandler for Router {
                 "name": {
                     "type": "string",
                     "description": "Name to search for (first or last)"
+                },
+                "include_context": {
+                    "type": "boolean",
+                    "description": "Also return each skater's families and the competitions/shoots they appear in"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Max skaters returned (default PHOTO_DEFAULT_LIMIT)"
+                },
+                "use_cursor": {
+                    "type": "boolean",
+                    "description": "Start cursor paging: results come back in id order with a next_cursor for the following page"
+                },
+                "cursor": {
+                    "type": "string",
+                    "description": "next_cursor from the previous page; continues in id order after that record"
                 }
             },
             "required": ["name"]
@@ -107,10 +259,64 @@ impl ServerHandler for Router {
             "required": ["shoot_name"]
         }));
 
+        // Optional override of the pending-status set for the pending-list tools
+        let statuses_property = serde_json::json!({
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "Gallery statuses to treat as pending (default: PHOTO_PENDING_STATUSES or pending, culling, processing)"
+        });
+
+        let pending_competition_schema = schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "competition_name": {
+                    "type": "string",
+                    "description": "Competition name to query"
+                },
+                "statuses": statuses_property.clone()
+            },
+            "required": ["competition_name"]
+        }));
+
+        let pending_shoot_schema = schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "shoot_name": {
+                    "type": "string",
+                    "description": "Shoot name to query"
+                },
+                "statuses": statuses_property.clone()
+            },
+            "required": ["shoot_name"]
+        }));
+
+        // Schema for get_shoot (shoot_name + optional family list)
+        let get_shoot_schema = schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "shoot_name": {
+                    "type": "string",
+                    "description": "Shoot name to query"
+                },
+                "include_families": {
+                    "type": "boolean",
+                    "description": "If true, include linked families (name, email, gallery_status) with a has_more flag"
+                }
+            },
+            "required": ["shoot_name"]
+        }));
+
         // Schema for mark_gallery_sent (last_name + competition_name)
+        // Injected write timestamp, accepted only with PHOTO_ALLOW_CLOCK_OVERRIDE=1
+        let at_property = serde_json::json!({
+            "type": "string",
+            "description": "RFC 3339 timestamp (e.g. 2025-11-02T18:00:00Z) to record instead of now (requires PHOTO_ALLOW_CLOCK_OVERRIDE=1; for tests and backfills)"
+        });
+
         let mark_gallery_schema = schema(serde_json::json!({
             "type": "object",
             "properties": {
+                "at": at_property.clone(),
                 "last_name": {
                     "type": "string",
                     "description": "Family last name"
@@ -118,6 +324,10 @@ impl ServerHandler for Router {
                 "competition_name": {
                     "type": "string",
                     "description": "Competition name"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "If true, resolve targets and report the change without writing"
                 }
             },
             "required": ["last_name", "competition_name"]
@@ -127,6 +337,7 @@ impl ServerHandler for Router {
         let mark_shoot_schema = schema(serde_json::json!({
             "type": "object",
             "properties": {
+                "at": at_property.clone(),
                 "last_name": {
                     "type": "string",
                     "description": "Family last name"
@@ -134,6 +345,10 @@ impl ServerHandler for Router {
                 "shoot_name": {
                     "type": "string",
                     "description": "Shoot name"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "If true, resolve targets and report the change without writing"
                 }
             },
             "required": ["last_name", "shoot_name"]
@@ -158,6 +373,30 @@ impl ServerHandler for Router {
                 "location": {
                     "type": "string",
                     "description": "Shoot location (optional)"
+                },
+                "external_key": {
+                    "type": "string",
+                    "description": "Optional stable id from an external system; if a shoot already has it, that shoot is returned instead of creating a duplicate"
+                },
+                "slug": {
+                    "type": "string",
+                    "description": "Optional unique handle; derived from name and date when omitted (with -2, -3, ... on collision). Shoot lookups match an exact slug before fuzzy name matching"
+                },
+                "packages": {
+                    "type": "array",
+                    "description": "Optional price list for the shoot",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "price_cents": { "type": "integer", "minimum": 0 }
+                        },
+                        "required": ["name", "price_cents"]
+                    }
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "If true, resolve targets and report the change without writing"
                 }
             },
             "required": ["name", "shoot_type"]
@@ -178,6 +417,22 @@ impl ServerHandler for Router {
                 "phone": {
                     "type": "string",
                     "description": "Phone number (optional)"
+                },
+                "referred_by": {
+                    "type": "string",
+                    "description": "Last name of the existing family that referred this one (kept as-is on update when omitted)"
+                },
+                "check_duplicates": {
+                    "type": "boolean",
+                    "description": "If true, look for an existing family with a similar surname first and return possible_duplicate instead of creating"
+                },
+                "force": {
+                    "type": "boolean",
+                    "description": "Create even if check_duplicates finds a close match"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "If true, resolve targets and report the change without writing"
                 }
             },
             "required": ["last_name", "delivery_email"]
@@ -187,6 +442,7 @@ impl ServerHandler for Router {
         let link_family_shoot_schema = schema(serde_json::json!({
             "type": "object",
             "properties": {
+                "at": at_property.clone(),
                 "last_name": {
                     "type": "string",
                     "description": "Family last name"
@@ -194,6 +450,10 @@ impl ServerHandler for Router {
                 "shoot_name": {
                     "type": "string",
                     "description": "Shoot name to link"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "If true, resolve targets and report the change without writing"
                 }
             },
             "required": ["last_name", "shoot_name"]
@@ -203,20 +463,120 @@ impl ServerHandler for Router {
         let record_purchase_schema = schema(serde_json::json!({
             "type": "object",
             "properties": {
+                "at": at_property.clone(),
                 "last_name": {
                     "type": "string",
                     "description": "Family last name"
                 },
                 "amount": {
                     "type": "number",
-                    "description": "Purchase amount in dollars"
+                    "description": "Purchase amount in dollars, must be positive (optional when package is given)"
+                },
+                "force": {
+                    "type": "boolean",
+                    "description": "Accept an amount above PHOTO_MAX_PURCHASE_AMOUNT"
+                },
+                "set_status": {
+                    "type": "boolean",
+                    "description": "Mark the gallery 'purchased' (default true); false records the amount and leaves the status as is"
+                },
+                "status_override": {
+                    "type": "string",
+                    "description": "Gallery status to set instead of 'purchased' (e.g. keep 'sent' for a deposit)"
+                },
+                "package": {
+                    "type": "string",
+                    "description": "Package name from the shoot's price list; sets amount when omitted"
                 },
                 "shoot_name": {
                     "type": "string",
                     "description": "Shoot name"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "If true, resolve targets and report the change without writing"
+                }
+            },
+            "required": ["last_name", "shoot_name"]
+        }));
+
+        // Schema for move_purchase (last_name + from_event + to_event)
+        let move_purchase_schema = schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "last_name": {
+                    "type": "string",
+                    "description": "Family last name"
+                },
+                "from_event": {
+                    "type": "string",
+                    "description": "Shoot the purchase was recorded against"
+                },
+                "to_event": {
+                    "type": "string",
+                    "description": "Shoot the purchase belongs to (linked if not already)"
+                },
+                "force": {
+                    "type": "boolean",
+                    "description": "Overwrite a purchase already recorded on the destination shoot"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "If true, resolve targets and report the change without writing"
+                }
+            },
+            "required": ["last_name", "from_event", "to_event"]
+        }));
+
+        // Schema for bulk_transition (shoot_name or competition_name + statuses)
+        let bulk_transition_schema = schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "at": at_property.clone(),
+                "shoot_name": {
+                    "type": "string",
+                    "description": "Shoot name (provide this or competition_name)"
+                },
+                "competition_name": {
+                    "type": "string",
+                    "description": "Competition name (provide this or shoot_name)"
+                },
+                "from_status": {
+                    "type": "string",
+                    "enum": GALLERY_STATUSES,
+                    "description": "Current gallery status to move from"
+                },
+                "to_status": {
+                    "type": "string",
+                    "enum": GALLERY_STATUSES,
+                    "description": "Gallery status to move to"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "If true, resolve targets and report the change without writing"
+                }
+            },
+            "required": ["from_status", "to_status"]
+        }));
+
+        // Schema for merge_skaters (source_id -> target_id)
+        let merge_skaters_schema = schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "source_id": {
+                    "type": "string",
+                    "description": "Duplicate skater id to merge away (e.g. skater:jane_m_smith); deleted after merge"
+                },
+                "target_id": {
+                    "type": "string",
+                    "description": "Skater id to keep (e.g. skater:jane_smith)"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "If true, resolve targets and report the change without writing"
                 }
             },
-            "required": ["last_name", "amount", "shoot_name"]
+            "required": ["source_id", "target_id"]
         }));
 
         // Schema for list_families (optional search)
@@ -226,16 +586,130 @@ impl ServerHandler for Router {
                 "search": {
                     "type": "string",
                     "description": "Optional search term to filter families"
+                },
+                "tag": {
+                    "type": "string",
+                    "description": "Optional tag to filter families (e.g. 'instagram-referral')"
+                },
+                "at_competition": {
+                    "type": "string",
+                    "description": "Optional competition name; only families linked to it"
+                },
+                "at_shoot": {
+                    "type": "string",
+                    "description": "Optional shoot name; only families linked to it"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Max families returned (default PHOTO_DEFAULT_LIMIT)"
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Families to skip for offset paging (default 0); not with cursor paging. total_count and has_more tell whether to fetch another page"
+                },
+                "sort_by": {
+                    "type": "string",
+                    "enum": ["last_name", "email", "created_at"],
+                    "description": "Sort field (default last_name); not with cursor paging"
+                },
+                "sort_dir": {
+                    "type": "string",
+                    "enum": ["asc", "desc"],
+                    "description": "Sort direction (default asc); created_at + desc lists newest clients first"
+                },
+                "with_status_summary": {
+                    "type": "boolean",
+                    "description": "Add per-family pending/sent/purchased edge counts across shoots and competitions"
+                },
+                "show_incomplete": {
+                    "type": "boolean",
+                    "description": "Show families lacking both last_name and name with name: null and data_incomplete: true instead of \"Unknown\""
+                },
+                "use_cursor": {
+                    "type": "boolean",
+                    "description": "Start cursor paging: results come back in id order with a next_cursor for the following page"
+                },
+                "cursor": {
+                    "type": "string",
+                    "description": "next_cursor from the previous page; continues in id order after that record"
+                }
+            }
+        }));
+
+        // Schema for list_shoots (optional tag filter and sort)
+        let list_shoots_schema = schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "tag": {
+                    "type": "string",
+                    "description": "Optional tag to filter shoots"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Max shoots returned (default PHOTO_DEFAULT_LIMIT)"
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Shoots to skip for offset paging (default 0); total_count and has_more tell whether to fetch another page"
+                },
+                "sort_by": {
+                    "type": "string",
+                    "enum": ["date", "name", "type"],
+                    "description": "Sort field (default date); ties are broken by name"
+                },
+                "sort_dir": {
+                    "type": "string",
+                    "enum": ["asc", "desc"],
+                    "description": "Sort direction (default desc)"
+                },
+                "include_archived": {
+                    "type": "boolean",
+                    "description": "Also list shoots archived by archive_season"
                 }
             }
         }));
 
-        let tools = vec![
+        // Schema for add_tag/remove_tag (family or shoot target)
+        let tag_schema = schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "tag": {
+                    "type": "string",
+                    "description": "Tag/label (stored lowercase, e.g. 'vip', 'instagram-referral')"
+                },
+                "last_name": {
+                    "type": "string",
+                    "description": "Family last name (tag a family)"
+                },
+                "shoot_name": {
+                    "type": "string",
+                    "description": "Shoot name (tag a shoot)"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "If true, resolve targets and report the change without writing"
+                }
+            },
+            "required": ["tag"]
+        }));
+
+        let mut tools = vec![
             Tool {
                 name: "health".into(),
                 title: Some("Health".into()),
-                description: Some("Check SurrealDB connectivity and config surface".into()),
-                input_schema: empty_schema.clone(),
+                description: Some(
+                    "Check SurrealDB connectivity and config surface (optionally verify writes)"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "check_write": {
+                            "type": "boolean",
+                            "description": "If true, create and delete a scratch record and report write_ok"
+                        }
+                    }
+                })),
                 icons: None,
                 annotations: None,
                 output_schema: None,
@@ -252,73 +726,627 @@ impl ServerHandler for Router {
                 meta: None,
             },
             Tool {
-                name: "get_contact".into(),
-                title: Some("Get Contact".into()),
-                description: Some("Get email and phone for a family by last name".into()),
-                input_schema: last_name_schema.clone(),
+                name: "dashboard".into(),
+                title: Some("Dashboard".into()),
+                description: Some(
+                    "One-call overview: table counts, lifetime and this-month revenue, pending gallery totals, and the 5 stalest pending galleries"
+                        .into(),
+                ),
+                input_schema: empty_schema.clone(),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
             Tool {
-                name: "find_skater".into(),
-                title: Some("Find Skater".into()),
+                name: "weekly_summary".into(),
+                title: Some("Weekly Summary".into()),
                 description: Some(
-                    "Search for skaters by partial name match (first or last)".into(),
+                    "Weekly snapshot: shoots/competitions this week (Mon-Sun, UTC), galleries sent and purchases (with total) in the last 7 days, and families created this week"
+                        .into(),
                 ),
-                input_schema: name_schema.clone(),
+                input_schema: empty_schema.clone(),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
             Tool {
-                name: "get_family".into(),
-                title: Some("Get Family".into()),
-                description: Some("Get complete family record including all family members".into()),
-                input_schema: last_name_schema.clone(),
+                name: "recent_activity".into(),
+                title: Some("Recent Activity".into()),
+                description: Some(
+                    "Chronological feed of the newest events: families, shoots, and skater links created, galleries linked/sent/purchased/thanked, and notes added, each with its record id"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum events to return (default 50)"
+                        },
+                        "since": {
+                            "type": "string",
+                            "description": "Only events on or after this date (YYYY-MM-DD)"
+                        }
+                    }
+                })),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
             Tool {
-                name: "mark_gallery_sent".into(),
-                title: Some("Mark Gallery Sent".into()),
-                description: Some("Mark gallery as sent for a family at a competition".into()),
-                input_schema: mark_gallery_schema,
+                name: "get_contact".into(),
+                title: Some("Get Contact".into()),
+                description: Some("Get email and phone for a family by last name".into()),
+                input_schema: last_name_schema.clone(),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
             Tool {
-                name: "list_pending_galleries".into(),
-                title: Some("List Pending Galleries".into()),
+                name: "get_contacts_bulk".into(),
+                title: Some("Get Contacts (Bulk)".into()),
                 description: Some(
-                    "List all families with pending galleries for a competition".into(),
+                    "Email (primary plus all delivery emails) and phone for a list of family last names in one call, in input order, plus not_found"
+                        .into(),
                 ),
-                input_schema: competition_schema.clone(),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "last_names": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Family last names (e.g. pasted mailing list)"
+                        }
+                    },
+                    "required": ["last_names"]
+                })),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
             Tool {
-                name: "competition_status".into(),
-                title: Some("Competition Status".into()),
-                description: Some("Get status overview and counts for a competition".into()),
-                input_schema: competition_schema.clone(),
+                name: "add_delivery_email".into(),
+                title: Some("Add Delivery Email".into()),
+                description: Some(
+                    "Add another gallery delivery address to a family (e.g. both parents); the primary delivery_email is kept unless make_primary is set"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "last_name": {
+                            "type": "string",
+                            "description": "Family last name"
+                        },
+                        "email": {
+                            "type": "string",
+                            "description": "Address to add (validated)"
+                        },
+                        "make_primary": {
+                            "type": "boolean",
+                            "description": "Also make this the primary delivery_email"
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "If true, resolve targets and report the change without writing"
+                        }
+                    },
+                    "required": ["last_name", "email"]
+                })),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
             Tool {
-                name: "create_shoot".into(),
-                title: Some("Create Shoot".into()),
-                description: Some("Create a new shoot (portrait, commercial, event, etc.)".into()),
+                name: "remove_delivery_email".into(),
+                title: Some("Remove Delivery Email".into()),
+                description: Some(
+                    "Remove a gallery delivery address from a family; removing the primary promotes the next address, and the last address can't be removed"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "last_name": {
+                            "type": "string",
+                            "description": "Family last name"
+                        },
+                        "email": {
+                            "type": "string",
+                            "description": "Address to remove"
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "If true, resolve targets and report the change without writing"
+                        }
+                    },
+                    "required": ["last_name", "email"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "find_skater".into(),
+                title: Some("Find Skater".into()),
+                description: Some(
+                    "Search for skaters by partial name match (first or last; case- and accent-insensitive)".into(),
+                ),
+                input_schema: name_schema.clone(),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "merge_skaters".into(),
+                title: Some("Merge Skaters".into()),
+                description: Some(
+                    "Merge a duplicate skater into another: repoint belongs_to/shot_in/competed_in edges and delete the duplicate"
+                        .into(),
+                ),
+                input_schema: merge_skaters_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "orphan_skaters".into(),
+                title: Some("Orphan Skaters".into()),
+                description: Some(
+                    "List skaters with no belongs_to family edge (missing from family views)".into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "limit": {
+                            "type": "integer",
+                            "description": "Max skaters returned (default PHOTO_DEFAULT_LIMIT)"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "suggest_skater_families".into(),
+                title: Some("Suggest Skater Families".into()),
+                description: Some(
+                    "For skaters with no belongs_to edge, propose families with a matching last name (confidence high/medium/ambiguous); apply: true links the high-confidence matches"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "apply": {
+                            "type": "boolean",
+                            "description": "If true, create belongs_to edges for high-confidence (single exact surname) matches"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "validate_dataset".into(),
+                title: Some("Validate Dataset".into()),
+                description: Some(
+                    "Full read-only data audit: counts and sample ids for invalid gallery statuses, dangling edges, families without an email, orphan skaters, likely duplicate families, and edges missing created_at, each with the tool to investigate or fix it"
+                        .into(),
+                ),
+                input_schema: empty_schema.clone(),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "find_dangling_edges".into(),
+                title: Some("Find Dangling Edges".into()),
+                description: Some(
+                    "List family_shoot/family_competition/belongs_to edges whose in or out record no longer exists, with counts by edge type"
+                        .into(),
+                ),
+                input_schema: empty_schema.clone(),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "prune_dangling_edges".into(),
+                title: Some("Prune Dangling Edges".into()),
+                description: Some(
+                    "Delete edges whose in or out record no longer exists; requires confirm: true (otherwise only reports counts)"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "confirm": {
+                            "type": "boolean",
+                            "description": "Must be true to delete; otherwise the counts are returned without changes"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "backfill_edge_timestamps".into(),
+                title: Some("Backfill Edge Timestamps".into()),
+                description: Some(
+                    "Set created_at on family_shoot/family_competition/belongs_to edges missing it, using the edge's sent/purchase/thank-you date (skater created_at for belongs_to) or default_date; requires confirm: true (otherwise only reports counts)"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "default_date": {
+                            "type": "string",
+                            "description": "Fallback date (YYYY-MM-DD or RFC 3339) for edges with no other date; without it those edges are skipped"
+                        },
+                        "confirm": {
+                            "type": "boolean",
+                            "description": "Must be true to update; otherwise the counts are returned without changes"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "archive_season".into(),
+                title: Some("Archive Season".into()),
+                description: Some(
+                    "Mark all shoots and competitions dated in a year or date range as archived (optionally their family edges too) so list tools hide them; requires confirm: true (otherwise only reports counts)"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "year": {
+                            "type": "integer",
+                            "description": "Season year (Jan 1 - Dec 31); or give start_date and end_date"
+                        },
+                        "start_date": {
+                            "type": "string",
+                            "description": "First event date to archive (YYYY-MM-DD)"
+                        },
+                        "end_date": {
+                            "type": "string",
+                            "description": "Last event date to archive (YYYY-MM-DD, inclusive)"
+                        },
+                        "include_edges": {
+                            "type": "boolean",
+                            "description": "Also set archived on the family_shoot/family_competition edges of those events"
+                        },
+                        "confirm": {
+                            "type": "boolean",
+                            "description": "Must be true to archive; otherwise the counts are returned without changes"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "link_skater_family".into(),
+                title: Some("Link Skater to Family".into()),
+                description: Some("Attach a skater to a family (belongs_to edge)".into()),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "skater_id": {
+                            "type": "string",
+                            "description": "Skater id (e.g. skater:jane_smith)"
+                        },
+            Tool {
+                name: "skater_appearances".into(),
+                title: Some("Skater Appearances".into()),
+                description: Some(
+                    "Every shoot and competition a skater is connected to, oldest first, via shot_in/competed_in or their family's gallery edges, with dates and gallery statuses"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "skater_id": {
+                            "type": "string",
+                            "description": "Skater record id (e.g. skater:smith_jane or smith_jane)"
+                        },
+            Tool {
+                name: "set_skater_gallery_status".into(),
+                title: Some("Set Skater Gallery Status".into()),
+                description: Some(
+                    "Set one skater's own gallery status (and optional URL) for a shoot on the shot_in edge, creating the edge if needed; for families where each skater gets a separate gallery"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "skater_id": {
+                            "type": "string",
+                            "description": "Skater record id (e.g. skater:smith_jane or smith_jane)"
+                        },
+                        "shoot_name": {
+                            "type": "string",
+                            "description": "Shoot slug or name (partial match)"
+                        },
+                        "gallery_status": {
+                            "type": "string",
+                            "enum": GALLERY_STATUSES,
+                            "description": "New status for this skater's gallery"
+                        },
+                        "gallery_url": {
+                            "type": "string",
+                            "description": "Link to this skater's gallery (kept as-is when omitted)"
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "If true, resolve targets and report the change without writing"
+                        }
+                    },
+                    "required": ["skater_id", "shoot_name", "gallery_status"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "get_skater_gallery".into(),
+                title: Some("Get Skater Gallery".into()),
+                description: Some(
+                    "A skater's own galleries (shot_in edges) with status and URL, oldest shoot first; optionally for one shoot"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "skater_id": {
+                            "type": "string",
+                            "description": "Skater record id (e.g. skater:smith_jane or smith_jane)"
+                        },
+                        "shoot_name": {
+                            "type": "string",
+                            "description": "Only this shoot (slug or partial name)"
+                        }
+                    },
+                    "required": ["skater_id"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+                        "name": {
+                            "type": "string",
+                            "description": "Skater's first and last name; use skater_id if several skaters share it"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+                        "last_name": {
+                            "type": "string",
+                            "description": "Family last name"
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "If true, resolve both records and report the link without writing"
+                        }
+                    },
+                    "required": ["skater_id", "last_name"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "create_skaters_bulk".into(),
+                title: Some("Create Skaters (Bulk)".into()),
+                description: Some(
+                    "Create several skaters for a family in one transaction, each linked with belongs_to; missing last names default to the family's"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "family_last_name": {
+                            "type": "string",
+                            "description": "Family last name"
+                        },
+                        "skaters": {
+                            "type": "array",
+                            "description": "Skaters to create",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "first_name": { "type": "string" },
+                                    "last_name": {
+                                        "type": "string",
+                                        "description": "Defaults to the family's last name"
+                                    }
+                                },
+                                "required": ["first_name"]
+                            }
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "If true, resolve targets and report the change without writing"
+                        }
+                    },
+                    "required": ["family_last_name", "skaters"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "get_family".into(),
+                title: Some("Get Family".into()),
+                description: Some(
+                    "Get complete family record: members, linked events, and lifetime revenue. Accepts \"Last\" or \"First Last\" (first name picks among same-surname families)".into(),
+                ),
+                input_schema: last_name_schema.clone(),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "mark_gallery_sent".into(),
+                title: Some("Mark Gallery Sent".into()),
+                description: Some("Mark gallery as sent for a family at a competition".into()),
+                input_schema: mark_gallery_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "list_pending_galleries".into(),
+                title: Some("List Pending Galleries".into()),
+                description: Some(
+                    "List all families with pending galleries for a competition".into(),
+                ),
+                input_schema: pending_competition_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "competition_status".into(),
+                title: Some("Competition Status".into()),
+                description: Some(
+                    "Get status overview, revenue, and thank-you counts for a competition".into(),
+                ),
+                input_schema: competition_schema.clone(),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "create_competition".into(),
+                title: Some("Create Competition".into()),
+                description: Some("Create a competition with optional date and location".into()),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "name": {
+                            "type": "string",
+                            "description": "Competition name"
+                        },
+                        "date": {
+                            "type": "string",
+                            "description": "Competition date (YYYY-MM-DD, optional)"
+                        },
+                        "location": {
+                            "type": "string",
+                            "description": "Rink or city (optional)"
+                        },
+                        "notes": {
+                            "type": "string",
+                            "description": "Additional notes (optional)"
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "If true, show what would be created without writing"
+                        }
+                    },
+                    "required": ["name"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "get_competition".into(),
+                title: Some("Get Competition".into()),
+                description: Some(
+                    "Get a competition's date, location, notes, and family/event counts".into(),
+                ),
+                input_schema: competition_schema.clone(),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "list_competitions".into(),
+                title: Some("List Competitions".into()),
+                description: Some("List competitions with date and location, most recent first".into()),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "include_archived": {
+                            "type": "boolean",
+                            "description": "Also list events archived by archive_season"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "empty_events".into(),
+                title: Some("Empty Events".into()),
+                description: Some(
+                    "Shoots and competitions with no linked families, with their dates (cleanup candidates)"
+                        .into(),
+                ),
+                input_schema: empty_schema.clone(),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "detect_slot_conflicts".into(),
+                title: Some("Detect Slot Conflicts".into()),
+                description: Some(
+                    "Find time slots where skaters from more than one tracked family compete (with split-ice side)"
+                        .into(),
+                ),
+                input_schema: competition_schema.clone(),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "create_shoot".into(),
+                title: Some("Create Shoot".into()),
+                description: Some("Create a new shoot (portrait, commercial, event, etc.)".into()),
                 input_schema: create_shoot_schema,
                 icons: None,
                 annotations: None,
@@ -326,19 +1354,587 @@ impl ServerHandler for Router {
                 meta: None,
             },
             Tool {
-                name: "mark_shoot_sent".into(),
-                title: Some("Mark Shoot Sent".into()),
-                description: Some("Mark shoot gallery as sent for a family".into()),
-                input_schema: mark_shoot_schema,
+                name: "mark_shoot_sent".into(),
+                title: Some("Mark Shoot Sent".into()),
+                description: Some("Mark shoot gallery as sent for a family".into()),
+                input_schema: mark_shoot_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "find_shoots".into(),
+                title: Some("Find Shoots".into()),
+                description: Some(
+                    "Find shoots by location (partial match) and/or date range, ordered by date; filters combine with AND"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "location": {
+                            "type": "string",
+                            "description": "Location text to search for (case-insensitive, optional)"
+                        },
+                        "start_date": {
+                            "type": "string",
+                            "description": "Only shoots on/after this date (YYYY-MM-DD, optional)"
+                        },
+                        "end_date": {
+                            "type": "string",
+                            "description": "Only shoots on/before this date (YYYY-MM-DD, optional)"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum shoots to return (default PHOTO_DEFAULT_LIMIT)"
+                        },
+                        "include_archived": {
+                            "type": "boolean",
+                            "description": "Also list events archived by archive_season"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "list_shoots".into(),
+                title: Some("List Shoots".into()),
+                description: Some("List shoots, newest first (with optional tag filter)".into()),
+                input_schema: list_shoots_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "create_family".into(),
+                title: Some("Create Family".into()),
+                description: Some(
+                    "Create a family/client, or update the email and notes if it already exists; was_created tells which, and previous holds the replaced values on update"
+                        .into(),
+                ),
+                input_schema: create_family_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "link_family_shoot".into(),
+                title: Some("Link Family to Shoot".into()),
+                description: Some("Connect a family to a shoot (creates family_shoot relationship)".into()),
+                input_schema: link_family_shoot_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "add_note".into(),
+                title: Some("Add Note".into()),
+                description: Some("Append a dated entry to a family's notes timeline".into()),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "last_name": {
+                            "type": "string",
+                            "description": "Family last name"
+                        },
+                        "text": {
+                            "type": "string",
+                            "description": "Note text"
+                        },
+                        "author": {
+                            "type": "string",
+                            "description": "Who wrote the note (optional)"
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "If true, resolve the family and shoot and report what would be linked (or every candidate when a name is ambiguous) without writing"
+                        }
+                    },
+                    "required": ["last_name", "text"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "list_notes".into(),
+                title: Some("List Notes".into()),
+                description: Some(
+                    "A family's notes timeline, oldest first; the legacy notes field is shown as the first entry"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "last_name": {
+                            "type": "string",
+                            "description": "Family last name"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Most recent entries to return (default PHOTO_DEFAULT_LIMIT)"
+                        }
+                    },
+                    "required": ["last_name"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "book_client".into(),
+                title: Some("Book Client".into()),
+                description: Some(
+                    "Create (or update) a family, create a shoot, and link them in one transaction; returns the family and shoot ids"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "last_name": {
+                            "type": "string",
+                            "description": "Family last name"
+                        },
+                        "delivery_email": {
+                            "type": "string",
+                            "description": "Email for gallery delivery"
+                        },
+                        "family_notes": {
+                            "type": "string",
+                            "description": "Notes for the family record (optional)"
+                        },
+                        "shoot_name": {
+                            "type": "string",
+                            "description": "Name of the shoot to create"
+                        },
+                        "shoot_type": {
+                            "type": "string",
+                            "description": "Type of shoot (portrait, commercial, event, etc.)"
+                        },
+                        "date": {
+                            "type": "string",
+                            "description": "Shoot date (YYYY-MM-DD, optional; defaults to now)"
+                        },
+                        "location": {
+                            "type": "string",
+                            "description": "Shoot location (optional)"
+                        },
+                        "shoot_notes": {
+                            "type": "string",
+                            "description": "Notes for the shoot (optional)"
+                        },
+                        "gallery_status": {
+                            "type": "string",
+                            "enum": GALLERY_STATUSES,
+                            "description": "Initial gallery status for the link (default pending)"
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "If true, resolve targets and report the change without writing"
+                        }
+                    },
+                    "required": ["last_name", "delivery_email", "shoot_name", "shoot_type"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "record_purchase".into(),
+                title: Some("Record Purchase".into()),
+                description: Some(
+                    "Record a purchase amount for a family at a shoot; marks the gallery purchased unless set_status is false or status_override is given, and returns the resulting gallery_status"
+                        .into(),
+                ),
+                input_schema: record_purchase_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "duplicate_shoot".into(),
+                title: Some("Duplicate Shoot".into()),
+                description: Some(
+                    "Create a new shoot copying type, location, and notes from an existing one (family links are not copied)"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "source_shoot_name": {
+                            "type": "string",
+                            "description": "Existing shoot to copy from"
+                        },
+                        "name": {
+                            "type": "string",
+                            "description": "Name for the new shoot"
+                        },
+                        "date": {
+                            "type": "string",
+                            "description": "Shoot date for the new shoot (YYYY-MM-DD, defaults to now)"
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "If true, resolve targets and report the change without writing"
+                        }
+                    },
+                    "required": ["source_shoot_name", "name"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "resend_gallery".into(),
+                title: Some("Resend Gallery".into()),
+                description: Some(
+                    "Revert a sent gallery for re-delivery: status back to pending (or to_status), sent_date cleared, reason logged on the edge"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "at": at_property.clone(),
+                        "last_name": {
+                            "type": "string",
+                            "description": "Family last name"
+                        },
+                        "shoot_name": {
+                            "type": "string",
+                            "description": "Shoot name (use this or competition_name)"
+                        },
+                        "competition_name": {
+                            "type": "string",
+                            "description": "Competition name (use this or shoot_name)"
+                        },
+                        "reason": {
+                            "type": "string",
+                            "description": "Why the gallery must be re-delivered (e.g. 'link expired')"
+                        },
+                        "to_status": {
+                            "type": "string",
+                            "enum": GALLERY_STATUSES.iter().filter(|s| **s != "sent").collect::<Vec<_>>(),
+                            "description": "Status to revert to (default PHOTO_RESEND_STATUS, normally 'pending')"
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "If true, show the change without writing"
+                        }
+                    },
+                    "required": ["last_name", "reason"]
+                })),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
             Tool {
-                name: "list_shoots".into(),
-                title: Some("List Shoots".into()),
-                description: Some("List all shoots".into()),
+                name: "bulk_transition".into(),
+                title: Some("Bulk Status Transition".into()),
+                description: Some(
+                    "Move all families at a shoot or competition from one gallery status to another"
+                        .into(),
+                ),
+                input_schema: bulk_transition_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "move_purchase".into(),
+                title: Some("Move Purchase".into()),
+                description: Some(
+                    "Move a recorded purchase (amount and date) from one shoot to another for a family. Shoots only; competition purchases can't be moved. Refuses when the destination already has a purchase unless force is true"
+                        .into(),
+                ),
+                input_schema: move_purchase_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "list_pending_shoot_galleries".into(),
+                title: Some("List Pending Shoot Galleries".into()),
+                description: Some("List all families with pending galleries for a shoot".into()),
+                input_schema: pending_shoot_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "shoot_status".into(),
+                title: Some("Shoot Status".into()),
+                description: Some("Get status overview and revenue for a shoot".into()),
+                input_schema: shoot_name_schema.clone(),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "get_shoot".into(),
+                title: Some("Get Shoot".into()),
+                description: Some(
+                    "Get details about a specific shoot (optionally with linked families)".into(),
+                ),
+                input_schema: get_shoot_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "list_families".into(),
+                title: Some("List Families".into()),
+                description: Some("List all families/clients (with optional search)".into()),
+                input_schema: list_families_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "list_incomplete_families".into(),
+                title: Some("List Incomplete Families".into()),
+                description: Some(
+                    "Families with neither last_name nor name (shown elsewhere as \"Unknown\"), with their ids and linked skater/shoot/competition counts so they can be fixed"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "limit": {
+                            "type": "integer",
+                            "description": "Max families to return (default PHOTO_DEFAULT_LIMIT)"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "add_tag".into(),
+                title: Some("Add Tag".into()),
+                description: Some("Add a tag/label to a family or shoot".into()),
+                input_schema: tag_schema.clone(),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "remove_tag".into(),
+                title: Some("Remove Tag".into()),
+                description: Some("Remove a tag/label from a family or shoot".into()),
+                input_schema: tag_schema,
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "referral_tree".into(),
+                title: Some("Referral Tree".into()),
+                description: Some(
+                    "Families a family referred (set with create_family referred_by), nested through their own referrals, with each family's revenue and the downstream revenue total"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "last_name": {
+                            "type": "string",
+                            "description": "Family last name"
+                        },
+                        "depth": {
+                            "type": "integer",
+                            "description": "Referral levels to follow (1-5, default 5)"
+                        }
+                    },
+                    "required": ["last_name"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "tag_summary".into(),
+                title: Some("Tag Summary".into()),
+                description: Some(
+                    "Per-tag family counts and revenue from tagged families (optional date range)"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "start_date": {
+                            "type": "string",
+                            "description": "Only count purchases on/after this date (YYYY-MM-DD, optional)"
+                        },
+                        "end_date": {
+                            "type": "string",
+                            "description": "Only count purchases on/before this date (YYYY-MM-DD, optional)"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "attention_list".into(),
+                title: Some("Attention List".into()),
+                description: Some(
+                    "Families ranked by a weighted needs-attention score (stale pending galleries, sent-not-purchased age, missing email, no recent contact) with reasons"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "stale_days": {
+                            "type": "integer",
+                            "description": "Pending galleries linked at least this many days ago count as stale (default 14)"
+                        },
+                        "contact_days": {
+                            "type": "integer",
+                            "description": "Flag families with no send/purchase/thank-you in this many days (default 60)"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Max families returned (default 25)"
+                        },
+                        "weights": {
+                            "type": "object",
+                            "description": "Override weights for this call (defaults from PHOTO_ATTENTION_WEIGHTS)",
+                            "properties": {
+                                "stale_pending": { "type": "number" },
+                                "sent_not_purchased": { "type": "number" },
+                                "missing_email": { "type": "number" },
+                                "no_recent_contact": { "type": "number" }
+                            }
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "delivery_queue".into(),
+                title: Some("Delivery Queue".into()),
+                description: Some(
+                    "Shoots and competitions with pending galleries, ranked by urgency (oldest pending age in days + pending count), with event date and pending counts"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "statuses": statuses_property.clone(),
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum events to return (default 25)"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "delivery_checklist".into(),
+                title: Some("Delivery Checklist".into()),
+                description: Some(
+                    "Pre-send check for one shoot or competition: each linked family's delivery email, gallery url, and gallery status, split into ready and not_ready (missing email or gallery url)"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "shoot_name": {
+                            "type": "string",
+                            "description": "Shoot slug or name (partial match); give this or competition_name. Several matching shoots return candidates instead of a checklist"
+                        },
+                        "competition_name": {
+                            "type": "string",
+                            "description": "Competition name (partial match); give this or shoot_name. Several matching competitions return candidates instead of a checklist"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "sent_not_purchased".into(),
+                title: Some("Sent Not Purchased".into()),
+                description: Some(
+                    "Families whose gallery was sent but not purchased (shoots and competitions), longest-waiting first"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "days": {
+                            "type": "integer",
+                            "description": "Only include galleries sent at least this many days ago (optional)"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "outstanding_balances".into(),
+                title: Some("Outstanding Balances".into()),
+                description: Some(
+                    "Sent shoot galleries with no recorded purchase on shoots that have package pricing: expected amount per gallery, summed per family and overall, largest balance first"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "package": {
+                            "type": "string",
+                            "description": "Package to price by when the edge has none (default: the shoot's cheapest package)"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "reconcile_revenue".into(),
+                title: Some("Reconcile Revenue".into()),
+                description: Some(
+                    "Check revenue consistency: edge totals vs per-event status totals, purchased edges without amounts, and amounts on non-purchased edges"
+                        .into(),
+                ),
                 input_schema: empty_schema.clone(),
                 icons: None,
                 annotations: None,
@@ -346,70 +1942,143 @@ impl ServerHandler for Router {
                 meta: None,
             },
             Tool {
-                name: "create_family".into(),
-                title: Some("Create Family".into()),
-                description: Some("Create a new family/client in the database".into()),
-                input_schema: create_family_schema,
+                name: "revenue_trend".into(),
+                title: Some("Revenue Trend".into()),
+                description: Some(
+                    "Monthly revenue (shoots + competitions) by purchase_date: one entry per calendar month from start_date to end_date, with zero-revenue months included"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "start_date": {
+                            "type": "string",
+                            "description": "First day of the range (YYYY-MM-DD)"
+                        },
+                        "end_date": {
+                            "type": "string",
+                            "description": "Last day of the range, inclusive (YYYY-MM-DD)"
+                        }
+                    },
+                    "required": ["start_date", "end_date"]
+                })),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
             Tool {
-                name: "link_family_shoot".into(),
-                title: Some("Link Family to Shoot".into()),
-                description: Some("Connect a family to a shoot (creates family_shoot relationship)".into()),
-                input_schema: link_family_shoot_schema,
+                name: "purchase_latency".into(),
+                title: Some("Purchase Latency".into()),
+                description: Some(
+                    "Days from gallery delivery (sent_date) to purchase (purchase_date) on purchased edges: median and average overall and for shoots vs competitions; edges missing either date are excluded"
+                        .into(),
+                ),
+                input_schema: empty_schema.clone(),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
             Tool {
-                name: "record_purchase".into(),
-                title: Some("Record Purchase".into()),
-                description: Some("Record a purchase amount for a family at a shoot".into()),
-                input_schema: record_purchase_schema,
+                name: "distinct_statuses".into(),
+                title: Some("Distinct Statuses".into()),
+                description: Some(
+                    "Every gallery_status value present on family_shoot/family_competition with counts; values outside the allowed set are listed under unknown_statuses"
+                        .into(),
+                ),
+                input_schema: empty_schema.clone(),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
             Tool {
-                name: "list_pending_shoot_galleries".into(),
-                title: Some("List Pending Shoot Galleries".into()),
-                description: Some("List all families with pending galleries for a shoot".into()),
-                input_schema: shoot_name_schema.clone(),
+                name: "pipeline_status".into(),
+                title: Some("Pipeline Status".into()),
+                description: Some(
+                    "Global gallery funnel: count of every gallery_status across family_shoot and family_competition combined (zeros included), with pending/delivered/purchased totals and conversion rate"
+                        .into(),
+                ),
+                input_schema: empty_schema.clone(),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
             Tool {
-                name: "shoot_status".into(),
-                title: Some("Shoot Status".into()),
-                description: Some("Get status overview and revenue for a shoot".into()),
-                input_schema: shoot_name_schema.clone(),
+                name: "export_sales".into(),
+                title: Some("Export Sales".into()),
+                description: Some(
+                    "CSV of every recorded purchase (date,family,event,event_type,amount,email) sorted by date, with a TOTAL row"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "start_date": {
+                            "type": "string",
+                            "description": "Only include purchases on/after this date (YYYY-MM-DD, optional)"
+                        },
+            Tool {
+                name: "export_status_report".into(),
+                title: Some("Export Status Report".into()),
+                description: Some(
+                    "CSV of every family on every shoot and competition with gallery status and purchase amount, grouped by event with per-event subtotals and a grand total"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "active_only": {
+                            "type": "boolean",
+                            "description": "Only include events that still have a gallery in a pending status"
+                        },
+                        "statuses": statuses_property
+                    }
+                })),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
-            Tool {
-                name: "get_shoot".into(),
-                title: Some("Get Shoot".into()),
-                description: Some("Get details about a specific shoot".into()),
-                input_schema: shoot_name_schema.clone(),
+                        "end_date": {
+                            "type": "string",
+                            "description": "Only include purchases on/before this date (YYYY-MM-DD, optional)"
+                        },
+                        "stream": {
+                            "type": "boolean",
+                            "description": "Return a path to the streaming CSV route instead of inline CSV (automatic above PHOTO_EXPORT_MAX_INLINE_ROWS)"
+                        }
+                    }
+                })),
                 icons: None,
                 annotations: None,
                 output_schema: None,
                 meta: None,
             },
             Tool {
-                name: "list_families".into(),
-                title: Some("List Families".into()),
-                description: Some("List all families/clients (with optional search)".into()),
-                input_schema: list_families_schema,
+                name: "export_family".into(),
+                title: Some("Export Family".into()),
+                description: Some(
+                    "Complete record for one family as a single JSON document: contact details, skaters, competition entries, and every shoot/competition edge (statuses, dates, purchases)"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "last_name": {
+                            "type": "string",
+                            "description": "Family last name"
+                        },
+                        "format": {
+                            "type": "string",
+                            "enum": ["structured", "text"],
+                            "description": "Return structured content (default) or pretty-printed JSON text"
+                        }
+                    },
+                    "required": ["last_name"]
+                })),
                 icons: None,
                 annotations: None,
                 output_schema: None,
@@ -439,6 +2108,59 @@ impl ServerHandler for Router {
                 output_schema: None,
                 meta: None,
             },
+            Tool {
+                name: "find_family_by_sp_gallery".into(),
+                title: Some("Find Family by ShootProof Gallery".into()),
+                description: Some(
+                    "Look up the family holding a ShootProof gallery id (for orders that reference only the gallery id); reports every candidate if several families share it"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "gallery_id": {
+                            "type": "integer",
+                            "description": "ShootProof gallery id"
+                        }
+                    },
+                    "required": ["gallery_id"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "reassign_gallery".into(),
+                title: Some("Reassign Gallery".into()),
+                description: Some("Move a ShootProof gallery id/url from whichever family holds it to another family".into()),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "gallery_id": {
+                            "type": "integer",
+                            "description": "ShootProof gallery id"
+                        },
+                        "last_name": {
+                            "type": "string",
+                            "description": "Family last name that should own the gallery"
+                        },
+                        "url": {
+                            "type": "string",
+                            "description": "Gallery URL (optional; defaults to the current holder's URL)"
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "If true, show the reassignment without writing"
+                        }
+                    },
+                    "required": ["gallery_id", "last_name"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
             Tool {
                 name: "sync_shootproof_orders".into(),
                 title: Some("Sync ShootProof Orders".into()),
@@ -462,196 +2184,405 @@ impl ServerHandler for Router {
                 output_schema: None,
                 meta: None,
             },
+            Tool {
+                name: "export_shootproof".into(),
+                title: Some("Export ShootProof".into()),
+                description: Some(
+                    "Export families and recorded purchases as ShootProof-style JSON (galleries/orders arrays) that sync_shootproof_galleries and sync_shootproof_orders can read back; includes shootproof_gallery_id/shootproof_url where known"
+                        .into(),
+                ),
+                input_schema: empty_schema.clone(),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
+            Tool {
+                name: "export_function_specs".into(),
+                title: Some("Export Function Specs".into()),
+                description: Some(
+                    "Export the enabled tools as OpenAI-style function definitions (name, description, parameters) for non-MCP agent frameworks"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "style": {
+                            "type": "string",
+                            "enum": ["functions", "tools"],
+                            "description": "functions: bare definitions for the legacy `functions` array (default); tools: each wrapped as {type: function, function: ...} for the `tools` array"
+                        }
+                    }
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            },
         ];
 
-        Ok(ListToolsResult {
-            tools,
-            ..Default::default()
-        })
+        tools.push(Tool {
+                name: "reload_config".into(),
+                title: Some("Reload Config".into()),
+                description: Some(
+                    "Admin: re-read configuration and apply hot-reloadable settings (bearer token, limits, pending statuses) without a restart; the DB connection is kept"
+                        .into(),
+                ),
+                input_schema: empty_schema.clone(),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            });
+
+        tools.push(Tool {
+                name: "raw_query".into(),
+                title: Some("Raw Query".into()),
+                description: Some(
+                    "Admin: run a read-only SurrealQL query (SELECT, INFO, RETURN only; anything that writes is refused) and return the rows as JSON"
+                        .into(),
+                ),
+                input_schema: schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "query": {
+                            "type": "string",
+                            "description": "SurrealQL to run; one or more SELECT/INFO/RETURN statements"
+                        }
+                    },
+                    "required": ["query"]
+                })),
+                icons: None,
+                annotations: None,
+                output_schema: None,
+                meta: None,
+            });
+
+        tools
     }
 
-    async fn call_tool(
+    /// Route a tool call to its handler.
+    /// `ct` is cancelled when the client cancels the request or disconnects.
+    async fn dispatch(
         &self,
         request: CallToolRequestParam,
-        _context: RequestContext<rmcp::service::RoleServer>,
+        ct: &CancellationToken,
     ) -> std::result::Result<CallToolResult, McpError> {
         match request.name.as_ref() {
-            "health" => self.0.handle_health(request).await.map_err(|e| McpError {
-                code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                message: e.to_string().into(),
-                data: None,
-            }),
-            "status" => self.0.handle_status(request).await.map_err(|e| McpError {
-                code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                message: e.to_string().into(),
-                data: None,
-            }),
-            "get_contact" => self
+            "health" => self.0.handle_health(request).await.map_err(tool_error),
+            "status" => self.0.handle_status(request).await.map_err(tool_error),
+            "dashboard" => self.0.handle_dashboard(request).await.map_err(tool_error),
+            "weekly_summary" => self
                 .0
-                .handle_get_contact(request)
+                .handle_weekly_summary(request)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
-            "find_skater" => self
+                .map_err(tool_error),
+            "recent_activity" => self
                 .0
-                .handle_find_skater(request)
+                .handle_recent_activity(request)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
-            "get_family" => self
+                .map_err(tool_error),
+            "get_contact" => self.0.handle_get_contact(request).await.map_err(tool_error),
+            "get_contacts_bulk" => self
                 .0
-                .handle_get_family(request)
+                .handle_get_contacts_bulk(request)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
-            "mark_gallery_sent" => {
-                self.0
-                    .handle_mark_gallery_sent(request)
-                    .await
-                    .map_err(|e| McpError {
-                        code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                        message: e.to_string().into(),
-                        data: None,
-                    })
-            }
+                .map_err(tool_error),
+            "add_delivery_email" => self
+                .0
+                .handle_add_delivery_email(request)
+                .await
+                .map_err(tool_error),
+            "remove_delivery_email" => self
+                .0
+                .handle_remove_delivery_email(request)
+                .await
+                .map_err(tool_error),
+            "find_skater" => self.0.handle_find_skater(request).await.map_err(tool_error),
+            "merge_skaters" => self
+                .0
+                .handle_merge_skaters(request)
+                .await
+                .map_err(tool_error),
+            "validate_dataset" => self
+                .0
+                .handle_validate_dataset(request)
+                .await
+                .map_err(tool_error),
+            "find_dangling_edges" => self
+                .0
+                .handle_find_dangling_edges(request)
+                .await
+                .map_err(tool_error),
+            "prune_dangling_edges" => self
+                .0
+                .handle_prune_dangling_edges(request)
+                .await
+                .map_err(tool_error),
+            "backfill_edge_timestamps" => self
+                .0
+                .handle_backfill_edge_timestamps(request)
+                .await
+                .map_err(tool_error),
+            "archive_season" => self
+                .0
+                .handle_archive_season(request)
+                .await
+                .map_err(tool_error),
+            "orphan_skaters" => self
+                .0
+                .handle_orphan_skaters(request)
+                .await
+                .map_err(tool_error),
+            "suggest_skater_families" => self
+                .0
+                .handle_suggest_skater_families(request)
+                .await
+                .map_err(tool_error),
+            "create_skaters_bulk" => self
+                .0
+                .handle_create_skaters_bulk(request)
+                .await
+                .map_err(tool_error),
+            "link_skater_family" => self
+                .0
+                .handle_link_skater_family(request)
+                .await
+                .map_err(tool_error),
+            "skater_appearances" => self
+                .0
+                .handle_skater_appearances(request)
+                .await
+                .map_err(tool_error),
+            "set_skater_gallery_status" => self
+                .0
+                .handle_set_skater_gallery_status(request)
+                .await
+                .map_err(tool_error),
+            "get_skater_gallery" => self
+                .0
+                .handle_get_skater_gallery(request)
+                .await
+                .map_err(tool_error),
+            "get_family" => self.0.handle_get_family(request).await.map_err(tool_error),
+            "mark_gallery_sent" => self
+                .0
+                .handle_mark_gallery_sent(request)
+                .await
+                .map_err(tool_error),
             "list_pending_galleries" => self
                 .0
                 .handle_list_pending_galleries(request)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
+                .map_err(tool_error),
             "competition_status" => self
                 .0
                 .handle_competition_status(request)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
+                .map_err(tool_error),
+            "create_competition" => self
+                .0
+                .handle_create_competition(request)
+                .await
+                .map_err(tool_error),
+            "get_competition" => self
+                .0
+                .handle_get_competition(request)
+                .await
+                .map_err(tool_error),
+            "list_competitions" => self
+                .0
+                .handle_list_competitions(request)
+                .await
+                .map_err(tool_error),
+            "empty_events" => self
+                .0
+                .handle_empty_events(request)
+                .await
+                .map_err(tool_error),
             "create_shoot" => self
                 .0
                 .handle_create_shoot(request)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
-            "mark_shoot_sent" => {
-                self.0
-                    .handle_mark_shoot_sent(request)
-                    .await
-                    .map_err(|e| McpError {
-                        code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                        message: e.to_string().into(),
-                        data: None,
-                    })
-            }
-            "list_shoots" => self
+                .map_err(tool_error),
+            "mark_shoot_sent" => self
                 .0
-                .handle_list_shoots(request)
+                .handle_mark_shoot_sent(request)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
+                .map_err(tool_error),
+            "find_shoots" => self.0.handle_find_shoots(request).await.map_err(tool_error),
+            "list_shoots" => self.0.handle_list_shoots(request).await.map_err(tool_error),
             "create_family" => self
                 .0
                 .handle_create_family(request)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
-            "link_family_shoot" => {
-                self.0
-                    .handle_link_family_shoot(request)
-                    .await
-                    .map_err(|e| McpError {
-                        code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                        message: e.to_string().into(),
-                        data: None,
-                    })
-            }
-            "record_purchase" => {
-                self.0
-                    .handle_record_purchase(request)
-                    .await
-                    .map_err(|e| McpError {
-                        code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                        message: e.to_string().into(),
-                        data: None,
-                    })
-            }
+                .map_err(tool_error),
+            "add_note" => self.0.handle_add_note(request).await.map_err(tool_error),
+            "list_notes" => self.0.handle_list_notes(request).await.map_err(tool_error),
+            "book_client" => self.0.handle_book_client(request).await.map_err(tool_error),
+            "link_family_shoot" => self
+                .0
+                .handle_link_family_shoot(request)
+                .await
+                .map_err(tool_error),
+            "duplicate_shoot" => self
+                .0
+                .handle_duplicate_shoot(request)
+                .await
+                .map_err(tool_error),
+            "resend_gallery" => self
+                .0
+                .handle_resend_gallery(request)
+                .await
+                .map_err(tool_error),
+            "bulk_transition" => self
+                .0
+                .handle_bulk_transition(request)
+                .await
+                .map_err(tool_error),
+            "move_purchase" => self
+                .0
+                .handle_move_purchase(request)
+                .await
+                .map_err(tool_error),
+            "record_purchase" => self
+                .0
+                .handle_record_purchase(request)
+                .await
+                .map_err(tool_error),
             "list_pending_shoot_galleries" => self
                 .0
                 .handle_list_pending_shoot_galleries(request)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
+                .map_err(tool_error),
             "shoot_status" => self
                 .0
                 .handle_shoot_status(request)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
-            "get_shoot" => self
-                .0
-                .handle_get_shoot(request)
-                .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
+                .map_err(tool_error),
+            "get_shoot" => self.0.handle_get_shoot(request).await.map_err(tool_error),
             "list_families" => self
                 .0
                 .handle_list_families(request)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
+                .map_err(tool_error),
+            "list_incomplete_families" => self
+                .0
+                .handle_list_incomplete_families(request)
+                .await
+                .map_err(tool_error),
+            "add_tag" => self.0.handle_add_tag(request).await.map_err(tool_error),
+            "remove_tag" => self.0.handle_remove_tag(request).await.map_err(tool_error),
+            "detect_slot_conflicts" => self
+                .0
+                .handle_detect_slot_conflicts(request)
+                .await
+                .map_err(tool_error),
+            "tag_summary" => self.0.handle_tag_summary(request).await.map_err(tool_error),
+            "referral_tree" => self
+                .0
+                .handle_referral_tree(request)
+                .await
+                .map_err(tool_error),
+            "attention_list" => self
+                .0
+                .handle_attention_list(request)
+                .await
+                .map_err(tool_error),
+            "delivery_queue" => self
+                .0
+                .handle_delivery_queue(request)
+                .await
+                .map_err(tool_error),
+            "delivery_checklist" => self
+                .0
+                .handle_delivery_checklist(request)
+                .await
+                .map_err(tool_error),
+            "sent_not_purchased" => self
+                .0
+                .handle_sent_not_purchased(request)
+                .await
+                .map_err(tool_error),
+            "outstanding_balances" => self
+                .0
+                .handle_outstanding_balances(request)
+                .await
+                .map_err(tool_error),
+            "reconcile_revenue" => self
+                .0
+                .handle_reconcile_revenue(request)
+                .await
+                .map_err(tool_error),
+            "revenue_trend" => self
+                .0
+                .handle_revenue_trend(request)
+                .await
+                .map_err(tool_error),
+            "purchase_latency" => self
+                .0
+                .handle_purchase_latency(request)
+                .await
+                .map_err(tool_error),
+            "distinct_statuses" => self
+                .0
+                .handle_distinct_statuses(request)
+                .await
+                .map_err(tool_error),
+            "pipeline_status" => self
+                .0
+                .handle_pipeline_status(request)
+                .await
+                .map_err(tool_error),
+            "export_sales" => self
+                .0
+                .handle_export_sales(request)
+                .await
+                .map_err(tool_error),
+            "export_status_report" => self
+                .0
+                .handle_export_status_report(request)
+                .await
+                .map_err(tool_error),
+            "export_family" => self
+                .0
+                .handle_export_family(request)
+                .await
+                .map_err(tool_error),
             "sync_shootproof_galleries" => self
                 .0
-                .handle_sync_shootproof_galleries(request)
+                .handle_sync_shootproof_galleries(request, ct)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
+                .map_err(tool_error),
+            "find_family_by_sp_gallery" => self
+                .0
+                .handle_find_family_by_sp_gallery(request)
+                .await
+                .map_err(tool_error),
+            "reassign_gallery" => self
+                .0
+                .handle_reassign_gallery(request)
+                .await
+                .map_err(tool_error),
             "sync_shootproof_orders" => self
                 .0
-                .handle_sync_shootproof_orders(request)
+                .handle_sync_shootproof_orders(request, ct)
                 .await
-                .map_err(|e| McpError {
-                    code: rmcp::model::ErrorCode::INTERNAL_ERROR,
-                    message: e.to_string().into(),
-                    data: None,
-                }),
+                .map_err(tool_error),
+            "export_shootproof" => self
+                .0
+                .handle_export_shootproof(request)
+                .await
+                .map_err(tool_error),
+            "raw_query" => self.0.handle_raw_query(request).await.map_err(tool_error),
+            "reload_config" => self
+                .0
+                .handle_reload_config(request)
+                .await
+                .map_err(tool_error),
+            "export_function_specs" => self
+                .handle_export_function_specs(request)
+                .map_err(tool_error),
             _ => Err(McpError {
                 code: rmcp::model::ErrorCode::METHOD_NOT_FOUND,
                 message: format!("Unknown tool: {}", request.name).into(),
@@ -660,3 +2591,57 @@ impl ServerHandler for Router {
         }
     }
 }
+
+/// One tool as an OpenAI function definition. Parameter-less schemas get an empty
+/// `properties` object, which function-calling APIs expect.
+fn function_spec(tool: &Tool) -> serde_json::Value {
+    let mut parameters = tool.input_schema.as_ref().clone();
+    parameters
+        .entry("properties")
+        .or_insert_with(|| serde_json::json!({}));
+    serde_json::json!({
+        "name": tool.name,
+        "description": tool.description.as_deref().unwrap_or_default(),
+        "parameters": parameters,
+    })
+}
+
+/// Maps handler errors to MCP errors: parameter problems become `INVALID_PARAMS`
+/// (with the missing/invalid list in `data`), everything else `INTERNAL_ERROR`.
+fn tool_error(e: anyhow::Error) -> McpError {
+    match e.downcast_ref::<InvalidParams>() {
+        Some(invalid) => McpError {
+            code: rmcp::model::ErrorCode::INVALID_PARAMS,
+            message: invalid.to_string().into(),
+            data: Some(invalid.to_json()),
+        },
+        None => McpError {
+            code: rmcp::model::ErrorCode::INTERNAL_ERROR,
+            message: e.to_string().into(),
+            data: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Router, function_spec};
+
+    #[test]
+    fn function_spec_maps_input_schema_to_parameters() {
+        let tools = Router::tool_definitions();
+        let status = tools.iter().find(|t| t.name == "status").unwrap();
+        let spec = function_spec(status);
+        assert_eq!(spec["name"], "status");
+        assert_eq!(spec["parameters"]["type"], "object");
+        assert_eq!(spec["parameters"]["properties"], serde_json::json!({}));
+
+        let get_family = tools.iter().find(|t| t.name == "get_family").unwrap();
+        let spec = function_spec(get_family);
+        assert_eq!(
+            spec["parameters"]["required"],
+            serde_json::json!(["last_name"])
+        );
+        assert!(!spec["description"].as_str().unwrap().is_empty());
+    }
+}
@@ -1,30 +1,97 @@
-use crate::config::Config;
-use crate::db::{connect_db, healthcheck};
+use crate::args::Args;
+use crate::config::{AttentionWeights, Config, FamilyIdStrategy};
+use crate::db::{ConnectionStats, connect_db, probe, verify_selection, write_check};
+use crate::photography::GALLERY_STATUSES;
+use crate::photography::utils::{
+    DUPLICATE_SURNAME_THRESHOLD, calendar_months, check_import_size, competition_to_id,
+    family_display_name, family_slug, fold_name, format_record_id, median, merge_delivery_emails,
+    new_family_key, normalize_surname, shoot_slug, skater_key, surname_similarity, unique_slug,
+    validate_email, validate_purchase_amount,
+};
 use anyhow::Result;
-use rmcp::model::{CallToolRequestParam, CallToolResult};
+use arc_swap::ArcSwap;
+use rmcp::model::{CallToolRequestParam, CallToolResult, Content};
+use std::sync::Arc;
 use surrealdb::{Surreal, engine::remote::ws::Client};
+use tokio_util::sync::CancellationToken;
 
 #[derive(Clone)]
 pub struct PhotoMindServer {
     pub db: Surreal<Client>,
-    pub cfg: Config,
+    config: Arc<ArcSwap<Config>>,
+    /// Reconnects and the last connection error, updated by `db::monitor_connection`.
+    pub connection: Arc<ConnectionStats>,
 }
 
 impl PhotoMindServer {
     pub async fn new(cfg: Config) -> Result<Self> {
         tracing::info!(db_url = %cfg.db_url, ns = %cfg.db_namespace, db = %cfg.db_name, "connecting db");
         let db = connect_db(&cfg).await?;
-        Ok(Self { db, cfg })
+        verify_selection(&db, &cfg).await?;
+        Ok(Self {
+            db,
+            config: Arc::new(ArcSwap::from_pointee(cfg)),
+            connection: Arc::new(ConnectionStats::default()),
+        })
     }
 
-    /// Lightweight health tool: returns DB connectivity + config surface.
-    pub async fn handle_health(&self, _req: CallToolRequestParam) -> Result<CallToolResult> {
-        let db_ok = healthcheck(&self.db).await.unwrap_or(false);
-        let body = serde_json::json!({
+    /// Current configuration snapshot; reloads swap it without touching in-flight calls.
+    pub fn cfg(&self) -> Arc<Config> {
+        self.config.load_full()
+    }
+
+    /// Shared handle for components that read config outside tool calls (HTTP auth).
+    pub fn config_handle(&self) -> Arc<ArcSwap<Config>> {
+        self.config.clone()
+    }
+
+    /// Re-run config loading and swap in the hot-reloadable fields, keeping the DB
+    /// connection. Used by SIGHUP and the `reload_config` tool.
+    /// Returns (changed fields, restart-only fields that were ignored).
+    pub fn reload_config(&self) -> Result<(Vec<&'static str>, Vec<&'static str>)> {
+        let fresh = Config::reload()?;
+        let (next, changed, needs_restart) = self.cfg().apply_reload(&fresh);
+        self.config.store(Arc::new(next));
+
+        tracing::info!(changed = ?changed, "config reloaded");
+        if !needs_restart.is_empty() {
+            tracing::warn!(fields = ?needs_restart, "changed settings need a restart to take effect");
+        }
+        Ok((changed, needs_restart))
+    }
+
+    /// Admin tool: reload configuration without restarting (see `reload_config`).
+    pub async fn handle_reload_config(&self, _req: CallToolRequestParam) -> Result<CallToolResult> {
+        let (changed, needs_restart) = self.reload_config()?;
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "changed": changed,
+            "needs_restart": needs_restart,
+        })))
+    }
+
+    /// Lightweight health tool: returns DB connectivity + config surface, plus reconnect
+    /// counts and the last connection error. With `check_write: true`, also proves the DB
+    /// accepts writes.
+    pub async fn handle_health(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let db_ok = probe(&self.db, &self.connection).await;
+        let mut body = serde_json::json!({
             "db": db_ok,
-            "namespace": self.cfg.db_namespace,
-            "database": self.cfg.db_name,
+            "namespace": self.cfg().db_namespace,
+            "database": self.cfg().db_name,
+            "connection": self.connection.to_json(),
         });
+
+        if bool_arg(&req, "check_write") {
+            match write_check(&self.db).await {
+                Ok(()) => body["write_ok"] = serde_json::json!(true),
+                Err(e) => {
+                    body["write_ok"] = serde_json::json!(false);
+                    body["write_error"] = serde_json::json!(e.to_string());
+                }
+            }
+        }
+
         Ok(CallToolResult::structured(body))
     }
 
@@ -35,18 +102,8 @@ impl PhotoMindServer {
             count: i64,
         }
 
-        let tables = [
-            ("family", "family"),
-            ("skater", "skater"),
-            ("competition", "competition"),
-            ("event", "event"),
-            ("family_competition", "family_competition"),
-            ("shoot", "shoot"),
-            ("family_shoot", "family_shoot"),
-        ];
-
         let mut counts = serde_json::Map::new();
-        for (key, table) in tables {
+        for (key, table) in self.status_tables() {
             let query = format!("SELECT count() FROM {} GROUP ALL;", table);
             let count = self
                 .db
@@ -57,7 +114,7 @@ impl PhotoMindServer {
                 .and_then(|vec| vec.into_iter().next())
                 .map(|r| r.count)
                 .unwrap_or(0);
-            counts.insert(key.to_string(), serde_json::json!(count));
+            counts.insert(key, serde_json::json!(count));
         }
 
         Ok(CallToolResult::structured(serde_json::Value::Object(
@@ -65,28 +122,423 @@ impl PhotoMindServer {
         )))
     }
 
-    /// Find skaters by partial name match (first or last name)
-    pub async fn handle_find_skater(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
-        let search_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: name"))?;
+    /// One-call overview: table counts, revenue, pending totals, and the stalest galleries
+    pub async fn handle_dashboard(&self, _req: CallToolRequestParam) -> Result<CallToolResult> {
+        use std::future::IntoFuture;
+
+        #[derive(serde::Deserialize)]
+        struct CountResult {
+            count: i64,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct RevenueResult {
+            total: Option<f64>,
+            this_month: Option<f64>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct StaleGallery {
+            family: Option<String>,
+            event: Option<String>,
+            gallery_status: String,
+            created_at: Option<String>,
+            days_pending: Option<i64>,
+        }
+
+        let tables = self.status_tables();
+        let counts_query: String = tables
+            .iter()
+            .map(|(_, table)| format!("SELECT count() FROM {} GROUP ALL;", table))
+            .collect();
+
+        let revenue_query = r#"
+            SELECT math::sum(purchase_amount ?? 0) AS total,
+                math::sum(IF purchase_date >= time::group(time::now(), 'month') { purchase_amount ?? 0 } ELSE { 0 }) AS this_month
+            FROM family_shoot WHERE purchase_amount IS NOT NONE GROUP ALL;
+            SELECT math::sum(purchase_amount ?? 0) AS total,
+                math::sum(IF purchase_date >= time::group(time::now(), 'month') { purchase_amount ?? 0 } ELSE { 0 }) AS this_month
+            FROM family_competition WHERE purchase_amount IS NOT NONE GROUP ALL;
+        "#;
+
+        let pending_query = r#"
+            SELECT count() FROM family_shoot WHERE gallery_status IN $statuses GROUP ALL;
+            SELECT count() FROM family_competition WHERE gallery_status IN $statuses GROUP ALL;
+        "#;
+
+        // Oldest pending edges first; 'stale' means longest since the gallery was linked
+        let stale_query = r#"
+            SELECT in.last_name AS family, out.name AS event, gallery_status, created_at,
+                duration::days(time::now() - created_at) AS days_pending
+            FROM family_shoot WHERE gallery_status IN $statuses
+            ORDER BY created_at ASC LIMIT 5;
+            SELECT in.last_name AS family, out.name AS event, gallery_status, created_at,
+                duration::days(time::now() - created_at) AS days_pending
+            FROM family_competition WHERE gallery_status IN $statuses
+            ORDER BY created_at ASC LIMIT 5;
+        "#;
+
+        let statuses = self.cfg().pending_statuses.clone();
+        let (counts_res, revenue_res, pending_res, stale_res) = tokio::join!(
+            self.db.query(counts_query).into_future(),
+            self.db.query(revenue_query).into_future(),
+            self.db
+                .query(pending_query)
+                .bind(("statuses", statuses.clone()))
+                .into_future(),
+            self.db
+                .query(stale_query)
+                .bind(("statuses", statuses.clone()))
+                .into_future(),
+        );
+
+        let mut counts_res = counts_res?;
+        let mut counts = serde_json::Map::new();
+        for (idx, (key, _)) in tables.into_iter().enumerate() {
+            let count = counts_res
+                .take::<Vec<CountResult>>(idx)?
+                .first()
+                .map(|r| r.count)
+                .unwrap_or(0);
+            counts.insert(key, serde_json::json!(count));
+        }
+
+        let mut revenue_res = revenue_res?;
+        let (mut lifetime_revenue, mut month_revenue) = (0.0, 0.0);
+        for idx in 0..2 {
+            if let Some(row) = revenue_res.take::<Vec<RevenueResult>>(idx)?.first() {
+                lifetime_revenue += row.total.unwrap_or(0.0);
+                month_revenue += row.this_month.unwrap_or(0.0);
+            }
+        }
+
+        let mut pending_res = pending_res?;
+        let mut pending = [0i64; 2];
+        for (idx, slot) in pending.iter_mut().enumerate() {
+            *slot = pending_res
+                .take::<Vec<CountResult>>(idx)?
+                .first()
+                .map(|r| r.count)
+                .unwrap_or(0);
+        }
+
+        let mut stale_res = stale_res?;
+        let mut stale: Vec<(&str, StaleGallery)> = Vec::new();
+        for (idx, event_type) in ["shoot", "competition"].into_iter().enumerate() {
+            let rows: Vec<StaleGallery> = stale_res.take(idx)?;
+            stale.extend(rows.into_iter().map(|r| (event_type, r)));
+        }
+        stale.sort_by(|a, b| b.1.days_pending.cmp(&a.1.days_pending));
+        stale.truncate(5);
+
+        let stale_list: Vec<_> = stale
+            .iter()
+            .map(|(event_type, g)| {
+                serde_json::json!({
+                    "family": self.family_display_name(g.family.as_deref(), None),
+                    "event": g.event,
+                    "event_type": event_type,
+                    "gallery_status": g.gallery_status,
+                    "linked_at": g.created_at,
+                    "days_pending": g.days_pending,
+                })
+            })
+            .collect();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "counts": counts,
+            "revenue": {
+                "lifetime": lifetime_revenue,
+                "this_month": month_revenue,
+            },
+            "pending": {
+                "statuses": statuses,
+                "shoot_galleries": pending[0],
+                "competition_galleries": pending[1],
+                "total": pending[0] + pending[1],
+            },
+            "stalest_galleries": stale_list,
+        })))
+    }
 
+    /// Monday snapshot: events this week (Mon-Sun, UTC), galleries sent and purchases
+    /// recorded in the last 7 days, and families created this week
+    pub async fn handle_weekly_summary(
+        &self,
+        _req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        // Boundaries come from the database clock; time::wday is 1 for Monday
         let query = r#"
-            SELECT * FROM skater
-            WHERE string::lowercase(first_name ?? '') CONTAINS string::lowercase($search)
-            OR string::lowercase(last_name ?? '') CONTAINS string::lowercase($search)
-            ORDER BY last_name, first_name
-            LIMIT 50;
+            LET $today = time::floor(time::now(), 1d);
+            LET $week_start = $today - duration::from::days(time::wday($today) - 1);
+            LET $week_end = $week_start + 1w;
+            LET $since = time::now() - 7d;
+            RETURN {
+                week_start: <string> $week_start,
+                week_end: <string> $week_end,
+                since: <string> $since
+            };
+            SELECT <string> id AS id, name, shoot_date AS date, location FROM shoot
+            WHERE shoot_date >= $week_start AND shoot_date < $week_end
+            ORDER BY date;
+            SELECT <string> id AS id, name, date ?? start_date AS date, location FROM competition
+            WHERE (date ?? start_date) < $week_end AND (end_date ?? date ?? start_date) >= $week_start
+            ORDER BY date;
+            SELECT in.last_name AS family, out.name AS event, 'shoot' AS event_type, sent_date AS date
+            FROM family_shoot WHERE sent_date >= $since;
+            SELECT in.last_name AS family, out.name AS event, 'competition' AS event_type, sent_date AS date
+            FROM family_competition WHERE sent_date >= $since;
+            SELECT in.last_name AS family, out.name AS event, 'shoot' AS event_type,
+                purchase_date AS date, purchase_amount AS amount
+            FROM family_shoot WHERE purchase_date >= $since AND purchase_amount IS NOT NONE;
+            SELECT in.last_name AS family, out.name AS event, 'competition' AS event_type,
+                purchase_date AS date, purchase_amount AS amount
+            FROM family_competition WHERE purchase_date >= $since AND purchase_amount IS NOT NONE;
+            SELECT <string> id AS id, name, last_name, delivery_email, created_at
+            FROM type::table($family_table) WHERE created_at >= $week_start
+            ORDER BY created_at;
         "#;
 
+        #[derive(serde::Deserialize)]
+        struct Bounds {
+            week_start: String,
+            week_end: String,
+            since: String,
+        }
+
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct EventRow {
+            id: String,
+            name: Option<String>,
+            date: Option<String>,
+            location: Option<String>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct EdgeRow {
+            family: Option<String>,
+            event: Option<String>,
+            event_type: String,
+            date: Option<String>,
+            amount: Option<f64>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct NewFamily {
+            id: String,
+            name: Option<String>,
+            last_name: Option<String>,
+            delivery_email: Option<String>,
+            created_at: Option<String>,
+        }
+
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .await?;
+        let bounds: Option<Bounds> = result.take(4)?;
+        let shoots: Vec<EventRow> = result.take(5)?;
+        let competitions: Vec<EventRow> = result.take(6)?;
+        let mut sent: Vec<EdgeRow> = result.take(7)?;
+        sent.extend(result.take::<Vec<EdgeRow>>(8)?);
+        let mut purchases: Vec<EdgeRow> = result.take(9)?;
+        purchases.extend(result.take::<Vec<EdgeRow>>(10)?);
+        let new_families: Vec<NewFamily> = result.take(11)?;
+
+        sent.sort_by(|a, b| a.date.cmp(&b.date));
+        purchases.sort_by(|a, b| a.date.cmp(&b.date));
+        let purchase_total: f64 = purchases.iter().filter_map(|p| p.amount).sum();
+
+        let edge_json = |rows: &[EdgeRow]| -> Vec<serde_json::Value> {
+            rows.iter()
+                .map(|r| {
+                    let mut row = serde_json::json!({
+                        "family": self.family_display_name(r.family.as_deref(), None),
+                        "event": r.event,
+                        "event_type": r.event_type,
+                        "date": r.date,
+                    });
+                    if let Some(amount) = r.amount {
+                        row["amount"] = serde_json::json!(amount);
+                    }
+                    row
+                })
+                .collect()
+        };
+
+        let families: Vec<_> = new_families
+            .iter()
+            .map(|f| {
+                serde_json::json!({
+                    "id": f.id,
+                    "name": self.family_display_name(f.last_name.as_deref(), f.name.as_deref()),
+                    "email": f.delivery_email,
+                    "created_at": f.created_at,
+                })
+            })
+            .collect();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "week_start": bounds.as_ref().map(|b| &b.week_start),
+            "week_end": bounds.as_ref().map(|b| &b.week_end),
+            "since": bounds.as_ref().map(|b| &b.since),
+            "events_this_week": {
+                "shoots": shoots,
+                "competitions": competitions,
+            },
+            "galleries_sent": {
+                "count": sent.len(),
+                "items": edge_json(&sent),
+            },
+            "purchases": {
+                "count": purchases.len(),
+                "total": purchase_total,
+                "items": edge_json(&purchases),
+            },
+            "new_families": {
+                "count": families.len(),
+                "items": families,
+            },
+        })))
+    }
+
+    /// Newest timestamped events across records and edges (creations, links, sends,
+    /// purchases, thank-yous, notes), merged and sorted newest first.
+    pub async fn handle_recent_activity(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let since = args.optional_str("since");
+        let limit = limit_arg_or(&mut args, 50).min(self.cfg().max_detail_items);
+        args.finish()?;
+
+        // Each source returns at most `limit` rows in the same shape; the merge keeps the newest
+        let mut statements = vec![
+            "SELECT 'family_created' AS kind, created_at AS at, <string> id AS entity, \
+             <string> id AS family_id, last_name AS family \
+             FROM type::table($family_table) WHERE created_at >= $since"
+                .to_string(),
+            "SELECT 'shoot_created' AS kind, created_at AS at, <string> id AS entity, \
+             <string> id AS event_id, name AS event, 'shoot' AS event_type \
+             FROM shoot WHERE created_at >= $since"
+                .to_string(),
+            "SELECT 'skater_linked' AS kind, created_at AS at, <string> id AS entity, \
+             <string> out AS family_id, out.last_name AS family, \
+             string::concat(in.first_name ?? '', ' ', in.last_name ?? '') AS skater \
+             FROM belongs_to WHERE created_at >= $since"
+                .to_string(),
+            "SELECT 'note_added' AS kind, created_at AS at, <string> id AS entity, \
+             <string> family AS family_id, family.last_name AS family, text \
+             FROM note WHERE created_at >= $since"
+                .to_string(),
+        ];
+        for (edge_table, event_type) in SALE_EDGES {
+            for (field, kind) in [
+                ("created_at", "linked"),
+                ("sent_date", "gallery_sent"),
+                ("purchase_date", "purchased"),
+                ("ty_sent_date", "thank_you_sent"),
+            ] {
+                statements.push(format!(
+                    "SELECT '{kind}' AS kind, {field} AS at, <string> id AS entity, \
+                     <string> in AS family_id, in.last_name AS family, \
+                     <string> out AS event_id, out.name AS event, '{event_type}' AS event_type, \
+                     purchase_amount AS amount \
+                     FROM {edge_table} WHERE {field} >= $since"
+                ));
+            }
+        }
+        let query = std::iter::once(
+            "LET $since = IF $since_raw { type::datetime($since_raw) } ELSE { d'1970-01-01T00:00:00Z' };"
+                .to_string(),
+        )
+        .chain(
+            statements
+                .iter()
+                .map(|s| format!("{} ORDER BY at DESC LIMIT $limit;", s)),
+        )
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct Activity {
+            kind: String,
+            at: String,
+            entity: String,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            family_id: Option<String>,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            family: Option<String>,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            event_id: Option<String>,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            event: Option<String>,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            event_type: Option<String>,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            skater: Option<String>,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            text: Option<String>,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            amount: Option<f64>,
+        }
+
         let mut result = self
             .db
             .query(query)
-            .bind(("search", search_name.clone()))
+            .bind(("since_raw", since.clone()))
+            .bind(("limit", limit))
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .await?;
+        let mut events: Vec<Activity> = Vec::new();
+        for index in 1..=statements.len() {
+            events.extend(result.take::<Vec<Activity>>(index)?);
+        }
+
+        // Only purchases carry an amount; the edge column is shared by every edge event
+        for event in &mut events {
+            if event.kind != "purchased" {
+                event.amount = None;
+            }
+        }
+        events.sort_by(|a, b| b.at.cmp(&a.at));
+        events.truncate(limit);
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "since": since,
+            "limit": limit,
+            "count": events.len(),
+            "events": events,
+        })))
+    }
+
+    /// Find skaters by partial name match (first or last name)
+    pub async fn handle_find_skater(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let search_name = args.required_str("name");
+        let cursor = cursor_arg(&mut args, "skater");
+        let limit = self.limit_arg(&mut args);
+        args.finish()?;
+
+        // Cursor paging walks skaters in id order so inserts between pages can't shift them
+        let cursor_mode = cursor.is_some() || bool_arg(&req, "use_cursor");
+        let query = match (&cursor, cursor_mode) {
+            (Some(_), _) => {
+                "SELECT id, first_name, last_name FROM skater WHERE id > type::thing($cursor) ORDER BY id;"
+            }
+            (None, true) => "SELECT id, first_name, last_name FROM skater ORDER BY id;",
+            // Accent folding isn't available in SurrealQL, so match in Rust
+            (None, false) => {
+                "SELECT id, first_name, last_name FROM skater ORDER BY last_name, first_name;"
+            }
+        };
+
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("cursor", cursor.clone().unwrap_or_default()))
             .await?;
 
         #[derive(serde::Deserialize, serde::Serialize)]
@@ -96,16 +548,27 @@ impl PhotoMindServer {
             last_name: String,
         }
 
-        let skaters: Vec<Skater> = result.take(0)?;
+        let needle = fold_name(&search_name);
+        let skaters: Vec<Skater> = result
+            .take::<Vec<Skater>>(0)?
+            .into_iter()
+            .filter(|s| {
+                fold_name(&s.first_name).contains(&needle)
+                    || fold_name(&s.last_name).contains(&needle)
+            })
+            .take(limit)
+            .collect();
+        let next_cursor = next_cursor(cursor_mode, limit, skaters.iter().map(|s| &s.id));
 
         if skaters.is_empty() {
             return Ok(CallToolResult::structured(serde_json::json!({
                 "found": false,
-                "message": format!("No skaters found matching: {}", search_name)
+                "message": format!("No skaters found matching: {}", search_name),
+                "next_cursor": null,
             })));
         }
 
-        let results: Vec<_> = skaters
+        let mut results: Vec<_> = skaters
             .iter()
             .map(|s| {
                 serde_json::json!({
@@ -117,1060 +580,6808 @@ impl PhotoMindServer {
             })
             .collect();
 
+        // Opt-in: one extra round trip for family and event participation
+        if bool_arg(&req, "include_context") {
+            let context_query = r#"
+                SELECT
+                    id,
+                    ->belongs_to.out AS family_ids,
+                    ->belongs_to.out.last_name AS family_names,
+                    array::distinct(->competed_in.out.competition.name) AS competitions,
+                    array::distinct(->shot_in.out.name) AS shoots
+                FROM $skaters;
+            "#;
+
+            #[derive(serde::Deserialize)]
+            struct SkaterContext {
+                id: surrealdb::sql::Thing,
+                #[serde(default)]
+                family_ids: Vec<surrealdb::sql::Thing>,
+                #[serde(default)]
+                family_names: Vec<Option<String>>,
+                #[serde(default)]
+                competitions: Vec<Option<String>>,
+                #[serde(default)]
+                shoots: Vec<Option<String>>,
+            }
+
+            let ids: Vec<surrealdb::sql::Thing> = skaters.iter().map(|s| s.id.clone()).collect();
+            let mut context_result = self.db.query(context_query).bind(("skaters", ids)).await?;
+            let contexts: Vec<SkaterContext> = context_result.take(0)?;
+
+            for (skater, entry) in skaters.iter().zip(results.iter_mut()) {
+                let Some(ctx) = contexts.iter().find(|c| c.id == skater.id) else {
+                    continue;
+                };
+                let families: Vec<_> = ctx
+                    .family_ids
+                    .iter()
+                    .zip(ctx.family_names.iter())
+                    .map(
+                        |(id, name)| serde_json::json!({ "id": id.to_string(), "last_name": name }),
+                    )
+                    .collect();
+                entry["families"] = serde_json::json!(families);
+                entry["competitions"] =
+                    serde_json::json!(ctx.competitions.iter().flatten().collect::<Vec<_>>());
+                entry["shoots"] =
+                    serde_json::json!(ctx.shoots.iter().flatten().collect::<Vec<_>>());
+            }
+        }
+
         Ok(CallToolResult::structured(serde_json::json!({
             "found": true,
             "count": skaters.len(),
             "skaters": results,
+            "next_cursor": next_cursor,
         })))
     }
 
-    /// Get complete family record including all family members
-    pub async fn handle_get_family(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
-        let last_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("last_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: last_name"))?;
-
-        // Use ID-based lookup like CLI does (family:lastname_lowercase)
-        let family_id = format!("family:{}", last_name.to_lowercase().replace(' ', "_"));
-
-        let family_query = "SELECT * FROM type::thing($family_id);";
+    /// Merge a duplicate skater into another: repoint its edges, then delete it
+    pub async fn handle_merge_skaters(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let source_str = skater_record_id(&args.required_str("source_id"));
+        let target_str = skater_record_id(&args.required_str("target_id"));
+        args.finish()?;
+
+        if source_str == target_str {
+            return Err(anyhow::anyhow!(
+                "source_id and target_id are the same skater"
+            ));
+        }
 
-        let mut family_result = self
+        let lookup_query = r#"
+            SELECT id, first_name, last_name FROM type::thing($source_id);
+            SELECT id, first_name, last_name FROM type::thing($target_id);
+        "#;
+        let mut lookup = self
             .db
-            .query(family_query)
-            .bind(("family_id", family_id.clone()))
+            .query(lookup_query)
+            .bind(("source_id", source_str.clone()))
+            .bind(("target_id", target_str.clone()))
             .await?;
 
         #[derive(serde::Deserialize)]
-        struct FamilyRecord {
+        struct SkaterRecord {
             id: surrealdb::sql::Thing,
-            name: Option<String>,
-            last_name: Option<String>,
-            delivery_email: Option<String>,
+            first_name: String,
+            last_name: String,
         }
 
-        let families: Vec<FamilyRecord> = family_result.take(0)?;
+        let sources: Vec<SkaterRecord> = lookup.take(0)?;
+        let targets: Vec<SkaterRecord> = lookup.take(1)?;
 
-        if families.is_empty() {
+        let (Some(source), Some(target)) = (sources.into_iter().next(), targets.into_iter().next())
+        else {
             return Ok(CallToolResult::structured(serde_json::json!({
-                "found": false,
-                "message": format!("No family found with last name: {} (ID: {})", last_name, family_id)
+                "success": false,
+                "message": format!("Skater not found: {} or {}", source_str, target_str)
             })));
+        };
+
+        if source.id.tb != "skater" || target.id.tb != "skater" {
+            return Err(anyhow::anyhow!("Both ids must be skater records"));
         }
 
-        let family = &families[0];
-        let display_name = family
-            .last_name
-            .clone()
-            .or_else(|| family.name.clone())
-            .unwrap_or_else(|| last_name.clone());
+        // Edge tables out of skater, with the fields copied when repointing.
+        // competed_in is included so deleting the source doesn't drop competition history.
+        const SKATER_EDGES: &[(&str, &str)] = &[
+            ("belongs_to", "created_at = $e.created_at ?? time::now()"),
+            (
+                "shot_in",
+                "gallery_status = $e.gallery_status ?? 'pending', gallery_url = $e.gallery_url, \
+                 notes = $e.notes, created_at = $e.created_at ?? time::now()",
+            ),
+            (
+                "competed_in",
+                "skate_order = $e.skate_order, request_status = $e.request_status ?? 'unrequested', \
+                 gallery_status = $e.gallery_status ?? 'pending', gallery_url = $e.gallery_url, \
+                 gallery_sent_at = $e.gallery_sent_at, purchase_amount = $e.purchase_amount, \
+                 purchase_date = $e.purchase_date, notes = $e.notes, \
+                 created_at = $e.created_at ?? time::now()",
+            ),
+        ];
 
-        // Get all skaters belonging to this family
-        let skaters_query = r#"
-            SELECT in.id as id, in.first_name as first_name, in.last_name as last_name
-            FROM belongs_to
-            WHERE out = $family_id
-        "#;
+        // Count what will move vs. what the target already has (duplicates are just dropped)
+        let mut counts = serde_json::Map::new();
+        for (table, _) in SKATER_EDGES {
+            let outs_query = format!(
+                "SELECT VALUE out FROM {table} WHERE in = $source; \
+                 SELECT VALUE out FROM {table} WHERE in = $target;"
+            );
+            let mut outs_result = self
+                .db
+                .query(outs_query)
+                .bind(("source", source.id.clone()))
+                .bind(("target", target.id.clone()))
+                .await?;
+            let source_outs: Vec<surrealdb::sql::Thing> = outs_result.take(0)?;
+            let target_outs: Vec<surrealdb::sql::Thing> = outs_result.take(1)?;
+
+            let duplicates = source_outs
+                .iter()
+                .filter(|o| target_outs.contains(o))
+                .count();
+            counts.insert(
+                table.to_string(),
+                serde_json::json!({
+                    "repointed": source_outs.len() - duplicates,
+                    "duplicates_removed": duplicates,
+                }),
+            );
+        }
 
-        let mut skaters_result = self
-            .db
-            .query(skaters_query)
-            .bind(("family_id", family.id.clone()))
-            .await?;
+        let source_name = format!("{} {}", source.first_name, source.last_name);
+        let target_name = format!("{} {}", target.first_name, target.last_name);
 
-        #[derive(serde::Deserialize)]
-        struct SkaterInfo {
-            id: surrealdb::sql::Thing,
-            first_name: String,
-            last_name: String,
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!(
+                    "Would merge {} ({}) into {} ({})",
+                    source_name, source.id, target_name, target.id
+                ),
+                serde_json::json!({
+                    "source_id": source.id.to_string(),
+                    "target_id": target.id.to_string(),
+                    "edges": counts,
+                    "delete": source.id.to_string(),
+                }),
+            ));
         }
 
-        let skaters: Vec<SkaterInfo> = skaters_result.take(0).unwrap_or_default();
+        let mut merge_query = String::from("BEGIN TRANSACTION;\n");
+        for (table, fields) in SKATER_EDGES {
+            merge_query.push_str(&format!(
+                "FOR $e IN (SELECT * FROM {table} WHERE in = $source) {{
+                    IF array::len((SELECT id FROM {table} WHERE in = $target AND out = $e.out)) = 0 {{
+                        LET $out = $e.out;
+                        RELATE $target->{table}->$out SET {fields};
+                    }};
+                    DELETE $e.id;
+                }};\n"
+            ));
+        }
+        merge_query.push_str("DELETE $source;\nCOMMIT TRANSACTION;");
 
-        let skater_list: Vec<_> = skaters
-            .iter()
-            .map(|s| {
+        self.db
+            .query(merge_query)
+            .bind(("source", source.id.clone()))
+            .bind(("target", target.id.clone()))
+            .await?
+            .check()?;
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "message": format!("Merged {} into {}", source_name, target_name),
+            "deleted_id": source.id.to_string(),
+            "target_id": target.id.to_string(),
+            "edges": counts,
+        })))
+    }
+
+    /// Edges whose `in` or `out` record no longer exists, per edge table (deletes made
+    /// outside this server leave these behind and they skew counts).
+    async fn dangling_edges(&self) -> Result<Vec<(&'static str, Vec<DanglingEdge>)>> {
+        let mut tables = Vec::new();
+        for edge_table in DANGLING_EDGE_TABLES {
+            let query = format!(
+                "SELECT id, <string> in AS in_id, <string> out AS out_id, \
+                 in.id IS NONE AS missing_in, out.id IS NONE AS missing_out \
+                 FROM {edge_table} WHERE in.id IS NONE OR out.id IS NONE;"
+            );
+            let edges: Vec<DanglingEdge> = self.db.query(query).await?.take(0)?;
+            tables.push((edge_table, edges));
+        }
+        Ok(tables)
+    }
+
+    /// List edges pointing at missing records, with counts by edge type
+    pub async fn handle_find_dangling_edges(
+        &self,
+        _req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let tables = self.dangling_edges().await?;
+
+        let mut counts = serde_json::Map::new();
+        let mut edges = Vec::new();
+        for (edge_table, found) in &tables {
+            counts.insert(edge_table.to_string(), serde_json::json!(found.len()));
+            edges.extend(found.iter().map(|e| {
                 serde_json::json!({
-                    "id": s.id.to_string(),
-                    "name": format!("{} {}", s.first_name, s.last_name),
-                    "first_name": s.first_name,
-                    "last_name": s.last_name,
+                    "edge_table": edge_table,
+                    "id": e.id.to_string(),
+                    "in": e.in_id,
+                    "out": e.out_id,
+                    "missing_in": e.missing_in,
+                    "missing_out": e.missing_out,
                 })
-            })
-            .collect();
+            }));
+        }
+        let total = edges.len();
+        let (edges, omitted) = cap_details(edges, self.cfg().max_detail_items);
 
         Ok(CallToolResult::structured(serde_json::json!({
-            "found": true,
-            "family": {
-                "id": family.id.to_string(),
-                "name": display_name,
-                "email": family.delivery_email,
-            },
-            "skaters": skater_list,
-            "skater_count": skaters.len(),
+            "total": total,
+            "by_edge_type": counts,
+            "edges": edges,
+            "truncated": omitted > 0,
+            "omitted_count": omitted,
         })))
     }
 
-    /// Mark gallery as sent for a family at a competition
-    pub async fn handle_mark_gallery_sent(
+    /// Delete edges pointing at missing records. Requires `confirm: true`; without it the
+    /// counts are reported and nothing is deleted.
+    pub async fn handle_prune_dangling_edges(
         &self,
         req: CallToolRequestParam,
     ) -> Result<CallToolResult> {
-        let last_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("last_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: last_name"))?;
-
-        let competition_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("competition_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: competition_name"))?;
-
-        // Use ID-based lookup for family (family:lastname_lowercase)
-        let family_id_str = format!("family:{}", last_name.to_lowercase().replace(' ', "_"));
-        let family_query = "SELECT VALUE id FROM type::thing($family_id);";
-        let mut family_result = self
-            .db
-            .query(family_query)
-            .bind(("family_id", family_id_str.clone()))
-            .await?;
-        let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
+        let tables = self.dangling_edges().await?;
+        let total: usize = tables.iter().map(|(_, found)| found.len()).sum();
+        let mut counts = serde_json::Map::new();
+        for (edge_table, found) in &tables {
+            counts.insert(edge_table.to_string(), serde_json::json!(found.len()));
+        }
 
-        if family_ids.is_empty() {
+        if !bool_arg(&req, "confirm") {
             return Ok(CallToolResult::structured(serde_json::json!({
                 "success": false,
-                "message": format!("No family found with last name: {} (ID: {})", last_name, family_id_str)
+                "deleted": 0,
+                "message": format!("Found {} dangling edge(s); pass confirm: true to delete them", total),
+                "by_edge_type": counts,
             })));
         }
 
-        // Find competition
-        let comp_query = "SELECT VALUE id FROM competition WHERE string::lowercase(name ?? '') CONTAINS string::lowercase($comp);";
-        let mut comp_result = self
-            .db
-            .query(comp_query)
-            .bind(("comp", competition_name.clone()))
-            .await?;
-        let comp_ids: Vec<surrealdb::sql::Thing> = comp_result.take(0)?;
+        // Delete exactly the edges found, so anything repaired meanwhile is left alone
+        let ids: Vec<surrealdb::sql::Thing> = tables
+            .into_iter()
+            .flat_map(|(_, found)| found.into_iter().map(|e| e.id))
+            .collect();
+        self.db
+            .query("DELETE $ids;")
+            .bind(("ids", ids))
+            .await?
+            .check()?;
+        tracing::info!(deleted = total, "pruned dangling edges");
 
-        if comp_ids.is_empty() {
-            return Ok(CallToolResult::structured(serde_json::json!({
-                "success": false,
-                "message": format!("No competition found matching: {}", competition_name)
-            })));
-        }
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "deleted": total,
+            "by_edge_type": counts,
+        })))
+    }
 
-        // Check if edge exists first
-        let check_query = r#"
-            SELECT id FROM family_competition
-            WHERE in = $family_id AND out = $comp_id
-            LIMIT 1
-        "#;
-        let mut check_result = self
+    /// Mark every shoot and competition dated in a range (`year`, or `start_date`/`end_date`)
+    /// as `archived`, and with `include_edges` their family edges too. List tools hide
+    /// archived events. Requires `confirm: true`; without it only counts are reported.
+    pub async fn handle_archive_season(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let year = args.optional_value("year").and_then(|v| v.as_i64());
+        let mut start_date = args.optional_str("start_date");
+        let mut end_date = args.optional_str("end_date");
+        match year {
+            Some(_) if start_date.is_some() || end_date.is_some() => {
+                args.invalid("year", "give either year or start_date/end_date, not both");
+            }
+            Some(year) if !(1900..=9999).contains(&year) => {
+                args.invalid("year", "must be a four-digit year");
+            }
+            Some(year) => {
+                start_date = Some(format!("{:04}-01-01", year));
+                end_date = Some(format!("{:04}-12-31", year));
+            }
+            None if start_date.is_none() || end_date.is_none() => {
+                args.invalid("start_date", "give year, or both start_date and end_date");
+            }
+            None => {}
+        }
+        args.finish()?;
+        let include_edges = bool_arg(&req, "include_edges");
+        let confirm = bool_arg(&req, "confirm");
+
+        let mut shoot_conditions = vec!["archived != true".to_string()];
+        push_date_range(&mut shoot_conditions, "shoot_date", &start_date, &end_date);
+        let mut competition_conditions = vec!["archived != true".to_string()];
+        push_date_range(
+            &mut competition_conditions,
+            "(date ?? start_date)",
+            &start_date,
+            &end_date,
+        );
+        let query = format!(
+            "SELECT VALUE id FROM shoot WHERE {}; \
+             SELECT VALUE id FROM competition WHERE {};",
+            shoot_conditions.join(" AND "),
+            competition_conditions.join(" AND ")
+        );
+        let mut result = self
             .db
-            .query(check_query)
-            .bind(("family_id", family_ids[0].clone()))
-            .bind(("comp_id", comp_ids[0].clone()))
+            .query(query)
+            .bind(("start_date", start_date.clone()))
+            .bind(("end_date", end_date.clone()))
             .await?;
+        let shoot_ids: Vec<surrealdb::sql::Thing> = result.take(0)?;
+        let competition_ids: Vec<surrealdb::sql::Thing> = result.take(1)?;
 
         #[derive(serde::Deserialize)]
-        struct EdgeCheck {
-            #[allow(dead_code)]
-            id: surrealdb::sql::Thing,
+        struct EdgeCount {
+            count: i64,
+        }
+        let mut edge_count = 0;
+        if include_edges {
+            let mut result = self
+                .db
+                .query(
+                    "SELECT count() FROM family_shoot WHERE out IN $shoots AND archived != true GROUP ALL; \
+                     SELECT count() FROM family_competition WHERE out IN $competitions AND archived != true GROUP ALL;",
+                )
+                .bind(("shoots", shoot_ids.clone()))
+                .bind(("competitions", competition_ids.clone()))
+                .await?;
+            for index in 0..2 {
+                let counts: Option<EdgeCount> = result.take(index)?;
+                edge_count += counts.map_or(0, |c| c.count);
+            }
         }
-        let edges: Vec<EdgeCheck> = check_result.take(0)?;
 
-        if edges.is_empty() {
+        let counts = serde_json::json!({
+            "shoots": shoot_ids.len(),
+            "competitions": competition_ids.len(),
+            "edges": if include_edges { Some(edge_count) } else { None },
+        });
+        if !confirm {
             return Ok(CallToolResult::structured(serde_json::json!({
                 "success": false,
-                "message": format!("No family_competition edge exists for {} at {}. Family may not be linked to this competition.", last_name, competition_name),
-                "family_id": family_ids[0].to_string(),
-                "competition_id": comp_ids[0].to_string(),
+                "archived": counts,
+                "start_date": start_date,
+                "end_date": end_date,
+                "message": format!(
+                    "Found {} shoot(s) and {} competition(s) to archive; pass confirm: true to archive them",
+                    shoot_ids.len(),
+                    competition_ids.len()
+                ),
             })));
         }
 
-        // Update family_competition edge
+        // Edges are matched through the events found above, so the two stay in step
         let update_query = r#"
-            UPDATE family_competition
-            SET gallery_status = 'sent', sent_date = time::now()
-            WHERE in = $family_id AND out = $comp_id
+            BEGIN TRANSACTION;
+            UPDATE $events SET archived = true, archived_at = time::now() RETURN NONE;
+            IF $include_edges {
+                UPDATE family_shoot SET archived = true WHERE out IN $shoots AND archived != true RETURN NONE;
+                UPDATE family_competition SET archived = true WHERE out IN $competitions AND archived != true RETURN NONE;
+            };
+            COMMIT TRANSACTION;
         "#;
-
+        let events: Vec<surrealdb::sql::Thing> = shoot_ids
+            .iter()
+            .chain(competition_ids.iter())
+            .cloned()
+            .collect();
         self.db
             .query(update_query)
-            .bind(("family_id", family_ids[0].clone()))
-            .bind(("comp_id", comp_ids[0].clone()))
-            .await?;
+            .bind(("events", events))
+            .bind(("shoots", shoot_ids.clone()))
+            .bind(("competitions", competition_ids.clone()))
+            .bind(("include_edges", include_edges))
+            .await?
+            .check()?;
+        tracing::info!(
+            shoots = shoot_ids.len(),
+            competitions = competition_ids.len(),
+            edges = edge_count,
+            "archived season"
+        );
 
         Ok(CallToolResult::structured(serde_json::json!({
             "success": true,
-            "message": format!("Marked gallery as sent for {} at {}", last_name, competition_name),
-            "family_id": family_ids[0].to_string(),
-            "competition_id": comp_ids[0].to_string(),
+            "archived": counts,
+            "start_date": start_date,
+            "end_date": end_date,
+            "message": format!(
+                "Archived {} shoot(s) and {} competition(s)",
+                shoot_ids.len(),
+                competition_ids.len()
+            ),
         })))
     }
 
-    /// List all families with pending galleries for a competition
-    pub async fn handle_list_pending_galleries(
+    /// Fill in `created_at` on edges that predate the field (or came from a sync without it).
+    /// The best-effort value is the earliest known milestone on the edge, then `default_date`;
+    /// edges with neither are left alone. Requires `confirm: true`; without it only counts
+    /// are reported.
+    pub async fn handle_backfill_edge_timestamps(
         &self,
         req: CallToolRequestParam,
     ) -> Result<CallToolResult> {
-        let competition_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("competition_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: competition_name"))?;
+        let mut args = Args::new(&req);
+        let default_date = args.optional_str("default_date");
+        args.finish()?;
+        let confirm = bool_arg(&req, "confirm");
 
-        let query = r#"
-            SELECT in.last_name as family, in.delivery_email as email, gallery_status
-            FROM family_competition
-            WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($comp)
-            AND gallery_status IN ['pending', 'culling', 'processing']
-            ORDER BY in.last_name
-        "#;
+        #[derive(serde::Deserialize)]
+        struct BackfillCounts {
+            missing: i64,
+            fillable: i64,
+        }
+
+        let mut by_edge_type = serde_json::Map::new();
+        let (mut missing_total, mut updated_total) = (0, 0);
+        for edge_table in DANGLING_EDGE_TABLES {
+            let fallback = match edge_table {
+                // Skater links carry no milestones; the skater's own created_at is the best guess
+                "belongs_to" => "in.created_at",
+                _ => "sent_date ?? purchase_date ?? ty_sent_date",
+            };
+            let value = format!(
+                "({fallback} ?? (IF $default_date {{ type::datetime($default_date) }} ELSE {{ NONE }}))"
+            );
+            let counts_query = format!(
+                "SELECT count() AS missing, count({value} IS NOT NONE) AS fillable \
+                 FROM {edge_table} WHERE created_at IS NONE GROUP ALL;"
+            );
+            let counts: Option<BackfillCounts> = self
+                .db
+                .query(counts_query)
+                .bind(("default_date", default_date.clone()))
+                .await?
+                .take(0)?;
+            let (missing, fillable) = counts.map_or((0, 0), |c| (c.missing, c.fillable));
+
+            let mut updated = 0;
+            if confirm && fillable > 0 {
+                let update_query = format!(
+                    "UPDATE {edge_table} SET created_at = {value} \
+                     WHERE created_at IS NONE AND {value} IS NOT NONE RETURN NONE;"
+                );
+                self.db
+                    .query(update_query)
+                    .bind(("default_date", default_date.clone()))
+                    .await?
+                    .check()?;
+                updated = fillable;
+            }
+            missing_total += missing;
+            updated_total += updated;
+            by_edge_type.insert(
+                edge_table.to_string(),
+                serde_json::json!({
+                    "missing": missing,
+                    "fillable": fillable,
+                    "updated": updated,
+                    "skipped": missing - updated,
+                }),
+            );
+        }
+
+        if !confirm {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "updated": 0,
+                "missing": missing_total,
+                "message": format!(
+                    "Found {} edge(s) without created_at; pass confirm: true to backfill them",
+                    missing_total
+                ),
+                "by_edge_type": by_edge_type,
+            })));
+        }
+        tracing::info!(updated = updated_total, "backfilled edge created_at");
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "updated": updated_total,
+            "missing": missing_total,
+            "skipped": missing_total - updated_total,
+            "default_date": default_date,
+            "by_edge_type": by_edge_type,
+        })))
+    }
+
+    /// Count and up to `VALIDATION_SAMPLES` ids of the rows in `from` matching `condition`.
+    /// `from` may use `$family_table`; `$statuses` is bound to `GALLERY_STATUSES`.
+    async fn validation_issue(
+        &self,
+        from: &str,
+        condition: &str,
+    ) -> Result<(i64, Vec<serde_json::Value>)> {
+        #[derive(serde::Deserialize)]
+        struct CountRow {
+            count: i64,
+        }
 
+        let query = format!(
+            "SELECT count() FROM {from} WHERE {condition} GROUP ALL; \
+             SELECT VALUE <string> id FROM {from} WHERE {condition} LIMIT $sample;"
+        );
         let mut result = self
             .db
             .query(query)
-            .bind(("comp", competition_name.clone()))
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .bind(("statuses", GALLERY_STATUSES.to_vec()))
+            .bind(("sample", VALIDATION_SAMPLES as i64))
             .await?;
+        let count = result
+            .take::<Vec<CountRow>>(0)?
+            .first()
+            .map_or(0, |r| r.count);
+        let ids: Vec<String> = result.take(1)?;
+        Ok((
+            count,
+            ids.into_iter().map(serde_json::Value::from).collect(),
+        ))
+    }
 
-        let families: Vec<crate::photography::models::PendingFamily> =
-            result.take(0).unwrap_or_default();
+    /// Every integrity check in one report: a count and sample ids per issue category, plus
+    /// the tool that digs into or fixes each one. Read-only.
+    pub async fn handle_validate_dataset(
+        &self,
+        _req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut checks = Vec::new();
+        let mut push = |check: &str, tool: &str, count: i64, samples: Vec<serde_json::Value>| {
+            checks.push(serde_json::json!({
+                "check": check,
+                "count": count,
+                "samples": samples,
+                "see": tool,
+            }));
+        };
+
+        // Status edges outside GALLERY_STATUSES (missing counts too)
+        let mut invalid = (0, Vec::new());
+        for edge_table in [
+            "family_shoot",
+            "family_competition",
+            "shot_in",
+            "competed_in",
+        ] {
+            let (count, ids) = self
+                .validation_issue(edge_table, "gallery_status NOT IN $statuses")
+                .await?;
+            invalid.0 += count;
+            invalid.1.extend(ids);
+        }
+        invalid.1.truncate(VALIDATION_SAMPLES);
+        push(
+            "invalid_statuses",
+            "distinct_statuses",
+            invalid.0,
+            invalid.1,
+        );
+
+        let dangling = self.dangling_edges().await?;
+        let dangling_count = dangling.iter().map(|(_, edges)| edges.len() as i64).sum();
+        let dangling_ids = dangling
+            .iter()
+            .flat_map(|(_, edges)| edges.iter().map(|e| e.id.to_string().into()))
+            .take(VALIDATION_SAMPLES)
+            .collect();
+        push(
+            "dangling_edges",
+            "find_dangling_edges",
+            dangling_count,
+            dangling_ids,
+        );
+
+        let (count, ids) = self
+            .validation_issue(
+                "type::table($family_table)",
+                "string::trim(delivery_email ?? '') = '' AND array::len(delivery_emails ?? []) = 0",
+            )
+            .await?;
+        push("families_without_email", "add_delivery_email", count, ids);
+
+        let (count, ids) = self
+            .validation_issue("skater", "count(->belongs_to) = 0")
+            .await?;
+        push("orphan_skaters", "suggest_skater_families", count, ids);
+
+        #[derive(serde::Deserialize)]
+        struct FamilyKey {
+            id: String,
+            last_name: Option<String>,
+            delivery_email: Option<String>,
+        }
+        let families: Vec<FamilyKey> = self
+            .db
+            .query("SELECT <string> id AS id, last_name, delivery_email FROM type::table($family_table);")
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .await?
+            .take(0)?;
+        let keys: Vec<(&str, &str, &str)> = families
+            .iter()
+            .map(|f| {
+                (
+                    f.id.as_str(),
+                    f.last_name.as_deref().unwrap_or(""),
+                    f.delivery_email.as_deref().unwrap_or(""),
+                )
+            })
+            .collect();
+        let pairs = duplicate_family_pairs(&keys);
+        let pair_samples = pairs
+            .iter()
+            .take(VALIDATION_SAMPLES)
+            .map(|(a, b, reason)| serde_json::json!({ "ids": [a, b], "reason": reason }))
+            .collect();
+        push(
+            "duplicate_families",
+            "create_family",
+            pairs.len() as i64,
+            pair_samples,
+        );
+
+        let mut untimed = (0, Vec::new());
+        for edge_table in DANGLING_EDGE_TABLES {
+            let (count, ids) = self
+                .validation_issue(edge_table, "created_at IS NONE")
+                .await?;
+            untimed.0 += count;
+            untimed.1.extend(ids);
+        }
+        untimed.1.truncate(VALIDATION_SAMPLES);
+        push(
+            "edges_missing_timestamps",
+            "backfill_edge_timestamps",
+            untimed.0,
+            untimed.1,
+        );
+
+        let total: i64 = checks.iter().filter_map(|c| c["count"].as_i64()).sum();
+        let failing: Vec<&str> = checks
+            .iter()
+            .filter(|c| c["count"].as_i64().unwrap_or(0) > 0)
+            .filter_map(|c| c["check"].as_str())
+            .collect();
 
         Ok(CallToolResult::structured(serde_json::json!({
-            "competition": competition_name,
-            "pending_count": families.len(),
-            "families": families,
+            "clean": total == 0,
+            "message": if total == 0 {
+                "No integrity issues found".to_string()
+            } else {
+                format!("{} issue(s) in {} check(s): {}", total, failing.len(), failing.join(", "))
+            },
+            "total_issues": total,
+            "checks": checks,
         })))
     }
 
-    /// Get status overview for a competition
-    pub async fn handle_competition_status(
-        &self,
-        req: CallToolRequestParam,
-    ) -> Result<CallToolResult> {
-        let competition_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("competition_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: competition_name"))?;
+    /// Skaters with no `belongs_to` family edge (invisible in family views)
+    pub async fn handle_orphan_skaters(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let limit = self.limit_arg(&mut args);
+        args.finish()?;
 
-        // Get counts by gallery_status
-        let status_query = r#"
-            SELECT gallery_status ?? 'unknown' as gallery_status, count() as count
-            FROM family_competition
-            WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($comp)
-            GROUP BY gallery_status
+        let query = r#"
+            SELECT count() FROM skater WHERE count(->belongs_to) = 0 GROUP ALL;
+            SELECT id, first_name, last_name,
+                array::distinct(->competed_in.out.competition.name) AS competitions
+            FROM skater WHERE count(->belongs_to) = 0
+            ORDER BY last_name, first_name LIMIT $limit;
         "#;
 
-        let mut status_result = self
-            .db
-            .query(status_query)
-            .bind(("comp", competition_name.clone()))
-            .await?;
+        let mut result = self.db.query(query).bind(("limit", limit as i64)).await?;
 
         #[derive(serde::Deserialize)]
-        struct StatusCount {
-            gallery_status: Option<String>,
+        struct CountResult {
             count: i64,
         }
 
-        let status_counts: Vec<StatusCount> = status_result.take(0).unwrap_or_default();
+        #[derive(serde::Deserialize)]
+        struct OrphanSkater {
+            id: surrealdb::sql::Thing,
+            first_name: String,
+            last_name: String,
+            #[serde(default)]
+            competitions: Vec<Option<String>>,
+        }
 
-        let mut counts = serde_json::Map::new();
-        let mut total = 0i64;
+        let total = result
+            .take::<Vec<CountResult>>(0)?
+            .first()
+            .map(|r| r.count)
+            .unwrap_or(0);
+        let orphans: Vec<OrphanSkater> = result.take(1)?;
 
-        for sc in status_counts {
-            let status = sc.gallery_status.unwrap_or_else(|| "unknown".to_string());
-            counts.insert(status, serde_json::json!(sc.count));
-            total += sc.count;
-        }
+        let skaters: Vec<_> = orphans
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "id": s.id.to_string(),
+                    "name": format!("{} {}", s.first_name, s.last_name),
+                    "first_name": s.first_name,
+                    "last_name": s.last_name,
+                    "competitions": s.competitions.iter().flatten().collect::<Vec<_>>(),
+                })
+            })
+            .collect();
 
         Ok(CallToolResult::structured(serde_json::json!({
-            "competition": competition_name,
-            "total_families": total,
-            "status_breakdown": counts,
+            "total": total,
+            "count": skaters.len(),
+            "has_more": (skaters.len() as i64) < total,
+            "skaters": skaters,
         })))
     }
 
-    /// Create a new shoot
-    pub async fn handle_create_shoot(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
-        let name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: name"))?;
-
-        let shoot_type = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("shoot_type"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: shoot_type"))?;
-
-        let location = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("location"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
-
-        let notes = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("notes"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
+    /// For each orphan skater, propose the family sharing its last name; `apply: true`
+    /// links the unambiguous exact matches with `belongs_to` edges
+    pub async fn handle_suggest_skater_families(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let query = r#"
+            SELECT <string> id AS id, first_name, last_name FROM skater
+            WHERE count(->belongs_to) = 0 ORDER BY last_name, first_name;
+            SELECT <string> id AS id, name, last_name FROM type::table($family_table);
+        "#;
 
-        // Optional date parameter (format: YYYY-MM-DD or YYYYMMDD)
-        let shoot_date = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("date"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
+        #[derive(serde::Deserialize)]
+        struct Orphan {
+            id: String,
+            first_name: Option<String>,
+            last_name: Option<String>,
+        }
 
-        // Build query based on whether shoot_date is provided
-        let create_query = if shoot_date.is_some() {
-            r#"
-                CREATE shoot CONTENT {
-                    name: $name,
-                    shoot_type: $shoot_type,
-                    shoot_date: type::datetime($shoot_date),
-                    location: $location,
-                    notes: $notes
-                }
-            "#
-        } else {
-            r#"
-                CREATE shoot CONTENT {
-                    name: $name,
-                    shoot_type: $shoot_type,
-                    shoot_date: time::now(),
-                    location: $location,
-                    notes: $notes
-                }
-            "#
-        };
+        #[derive(serde::Deserialize)]
+        struct Candidate {
+            id: String,
+            name: Option<String>,
+            last_name: Option<String>,
+        }
 
         let mut result = self
             .db
-            .query(create_query)
-            .bind(("name", name.clone()))
-            .bind(("shoot_type", shoot_type.clone()))
-            .bind(("shoot_date", shoot_date.unwrap_or_default()))
-            .bind(("location", location))
-            .bind(("notes", notes))
+            .query(query)
+            .bind(("family_table", self.cfg().family_table.clone()))
             .await?;
+        let orphans: Vec<Orphan> = result.take(0)?;
+        let families: Vec<Candidate> = result.take(1)?;
+
+        let mut suggestions = Vec::new();
+        let mut links = Vec::new();
+        let mut no_match = 0;
+        for orphan in &orphans {
+            let Some(surname) = orphan.last_name.as_deref().filter(|s| !s.trim().is_empty()) else {
+                no_match += 1;
+                continue;
+            };
+            let mut scored: Vec<(&Candidate, f64)> = families
+                .iter()
+                .filter_map(|f| {
+                    let score = surname_similarity(f.last_name.as_deref()?, surname);
+                    (score >= DUPLICATE_SURNAME_THRESHOLD).then_some((f, score))
+                })
+                .collect();
+            scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+            // Exact (accent/case-folded) matches beat fuzzy ones; only a lone exact match is high
+            let exact = scored.iter().filter(|(_, score)| *score >= 1.0).count();
+            let confidence = match (exact, scored.len()) {
+                (_, 0) => {
+                    no_match += 1;
+                    continue;
+                }
+                (1, _) => "high",
+                (0, 1) => "medium",
+                _ => "ambiguous",
+            };
+            if exact == 1 {
+                scored.truncate(1);
+            }
 
-        let shoots: Vec<crate::photography::models::Shoot> = result.take(0)?;
+            let skater_name = format!("{} {}", orphan.first_name.as_deref().unwrap_or(""), surname);
+            if confidence == "high" {
+                links.push(serde_json::json!({ "skater": orphan.id, "family": scored[0].0.id }));
+            }
+            suggestions.push(serde_json::json!({
+                "skater_id": orphan.id,
+                "skater_name": skater_name.trim(),
+                "confidence": confidence,
+                "candidates": scored
+                    .iter()
+                    .map(|(f, score)| serde_json::json!({
+                        "family_id": f.id,
+                        "name": self.family_display_name(f.last_name.as_deref(), f.name.as_deref()),
+                        "similarity": (score * 100.0).round() / 100.0,
+                    }))
+                    .collect::<Vec<_>>(),
+            }));
+        }
 
-        if let Some(shoot) = shoots.first() {
-            Ok(CallToolResult::structured(serde_json::json!({
-                "success": true,
-                "shoot_id": shoot.id.to_string(),
-                "name": shoot.name,
-                "shoot_type": shoot.shoot_type,
-            })))
+        let apply = bool_arg(&req, "apply");
+        let linked = if apply && !links.is_empty() {
+            let link_query = r#"
+                BEGIN TRANSACTION;
+                FOR $link IN $links {
+                    LET $skater = type::thing($link.skater);
+                    LET $family = type::thing($link.family);
+                    IF array::len((SELECT id FROM belongs_to WHERE in = $skater)) = 0 {
+                        RELATE $skater->belongs_to->$family SET created_at = time::now();
+                    };
+                };
+                COMMIT TRANSACTION;
+            "#;
+            self.db
+                .query(link_query)
+                .bind(("links", links.clone()))
+                .await?
+                .check()?;
+            links.len()
         } else {
-            Ok(CallToolResult::structured(serde_json::json!({
-                "success": false,
-                "message": "Failed to create shoot"
-            })))
-        }
+            0
+        };
+
+        let suggestion_count = suggestions.len();
+        let (suggestions, omitted) = cap_details(suggestions, self.cfg().max_detail_items);
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "orphans": orphans.len(),
+            "suggested": suggestion_count,
+            "high_confidence": links.len(),
+            "no_match": no_match,
+            "applied": apply,
+            "linked": linked,
+            "suggestions": suggestions,
+            "truncated": omitted > 0,
+            "omitted_count": omitted,
+        })))
     }
 
-    /// Mark shoot gallery as sent for a family
-    pub async fn handle_mark_shoot_sent(
+    /// Attach a skater to a family with a `belongs_to` edge
+    pub async fn handle_link_skater_family(
         &self,
         req: CallToolRequestParam,
     ) -> Result<CallToolResult> {
-        let last_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("last_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: last_name"))?;
+        let mut args = Args::new(&req);
+        let skater_id = skater_record_id(&args.required_str("skater_id"));
+        let last_name = args.required_str("last_name");
+        args.finish()?;
 
-        let shoot_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("shoot_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: shoot_name"))?;
-
-        // Use ID-based lookup for family (family:lastname_lowercase)
-        let family_id_str = format!("family:{}", last_name.to_lowercase().replace(' ', "_"));
-        let family_query = "SELECT VALUE id FROM type::thing($family_id);";
-        let mut family_result = self
+        let mut skater_result = self
             .db
-            .query(family_query)
-            .bind(("family_id", family_id_str.clone()))
+            .query("SELECT id, first_name, last_name FROM type::thing($skater_id);")
+            .bind(("skater_id", skater_id.clone()))
             .await?;
-        let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
 
-        if family_ids.is_empty() {
+        #[derive(serde::Deserialize)]
+        struct SkaterRow {
+            id: surrealdb::sql::Thing,
+            first_name: String,
+            last_name: String,
+        }
+
+        let skaters: Vec<SkaterRow> = skater_result.take(0)?;
+        let Some(skater) = skaters.into_iter().next() else {
             return Ok(CallToolResult::structured(serde_json::json!({
                 "success": false,
-                "message": format!("No family found with last name: {} (ID: {})", last_name, family_id_str)
+                "message": format!("Skater not found: {}", skater_id)
             })));
-        }
-
-        // Find shoot
-        let shoot_query = "SELECT VALUE id FROM shoot WHERE string::lowercase(name ?? '') CONTAINS string::lowercase($shoot);";
-        let mut shoot_result = self
-            .db
-            .query(shoot_query)
-            .bind(("shoot", shoot_name.clone()))
-            .await?;
-        let shoot_ids: Vec<surrealdb::sql::Thing> = shoot_result.take(0)?;
+        };
 
-        if shoot_ids.is_empty() {
+        let mut family_result = self.query_family("VALUE id", &last_name).await?;
+        let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
+        let Some(family_id) = family_ids.into_iter().next() else {
             return Ok(CallToolResult::structured(serde_json::json!({
                 "success": false,
-                "message": format!("No shoot found matching: {}", shoot_name)
+                "message": format!("Family not found: {}", self.family_id_label(&last_name))
             })));
-        }
+        };
 
-        // Check if edge exists first
-        let check_query = r#"
-            SELECT id FROM family_shoot
-            WHERE in = $family_id AND out = $shoot_id
-            LIMIT 1
-        "#;
-        let mut check_result = self
+        let mut existing_result = self
             .db
-            .query(check_query)
-            .bind(("family_id", family_ids[0].clone()))
-            .bind(("shoot_id", shoot_ids[0].clone()))
+            .query(
+                "SELECT VALUE out FROM belongs_to WHERE in = $skater; \
+                 SELECT VALUE id FROM belongs_to WHERE in = $skater AND out = $family;",
+            )
+            .bind(("skater", skater.id.clone()))
+            .bind(("family", family_id.clone()))
             .await?;
+        let current_families: Vec<surrealdb::sql::Thing> = existing_result.take(0)?;
+        let existing: Vec<surrealdb::sql::Thing> = existing_result.take(1)?;
+        let skater_name = format!("{} {}", skater.first_name, skater.last_name);
 
-        #[derive(serde::Deserialize)]
-        struct EdgeCheck {
-            #[allow(dead_code)]
-            id: surrealdb::sql::Thing,
-        }
-        let edges: Vec<EdgeCheck> = check_result.take(0)?;
-
-        if edges.is_empty() {
+        if !existing.is_empty() {
             return Ok(CallToolResult::structured(serde_json::json!({
-                "success": false,
-                "message": format!("No family_shoot edge exists for {} at {}. Family may not be linked to this shoot.", last_name, shoot_name),
-                "family_id": family_ids[0].to_string(),
-                "shoot_id": shoot_ids[0].to_string(),
+                "success": true,
+                "changed": false,
+                "message": format!("{} already belongs to {}", skater_name, family_id),
             })));
         }
 
-        // Update family_shoot edge
-        let update_query = r#"
-            UPDATE family_shoot
-            SET gallery_status = 'sent', sent_date = time::now()
-            WHERE in = $family_id AND out = $shoot_id
-        "#;
+        // Siblings can be linked to more than one household, so other links are reported, not removed
+        let other_families: Vec<String> = current_families.iter().map(|f| f.to_string()).collect();
+
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!("Would link {} to {}", skater_name, family_id),
+                serde_json::json!({
+                    "relate": "belongs_to",
+                    "skater_id": skater.id.to_string(),
+                    "family_id": family_id.to_string(),
+                    "other_families": other_families,
+                }),
+            ));
+        }
 
         self.db
-            .query(update_query)
-            .bind(("family_id", family_ids[0].clone()))
-            .bind(("shoot_id", shoot_ids[0].clone()))
-            .await?;
+            .query("RELATE $skater->belongs_to->$family SET created_at = time::now();")
+            .bind(("skater", skater.id.clone()))
+            .bind(("family", family_id.clone()))
+            .await?
+            .check()?;
 
         Ok(CallToolResult::structured(serde_json::json!({
             "success": true,
-            "message": format!("Marked shoot gallery as sent for {} at {}", last_name, shoot_name),
-            "family_id": family_ids[0].to_string(),
-            "shoot_id": shoot_ids[0].to_string(),
-        })))
-    }
-
-    /// List all shoots
-    pub async fn handle_list_shoots(&self, _req: CallToolRequestParam) -> Result<CallToolResult> {
-        let query = "SELECT * FROM shoot ORDER BY shoot_date DESC, name;";
-
-        let mut result = self.db.query(query).await?;
-        let shoots: Vec<crate::photography::models::Shoot> = result.take(0)?;
-
-        let shoot_list: Vec<_> = shoots
-            .iter()
-            .map(|s| {
-                serde_json::json!({
-                    "id": s.id.to_string(),
-                    "name": s.name,
-                    "shoot_type": s.shoot_type,
-                    "shoot_date": s.shoot_date,
-                    "location": s.location,
-                })
-            })
-            .collect();
-
-        Ok(CallToolResult::structured(serde_json::json!({
-            "count": shoots.len(),
-            "shoots": shoot_list,
+            "changed": true,
+            "message": format!("Linked {} to {}", skater_name, family_id),
+            "skater_id": skater.id.to_string(),
+            "family_id": family_id.to_string(),
+            "other_families": other_families,
         })))
     }
 
-    /// List all families with pending galleries for a shoot
-    pub async fn handle_list_pending_shoot_galleries(
+    /// Every shoot and competition a skater is connected to, oldest first: directly
+    /// (`shot_in`, `competed_in`) or through their family's `family_shoot`/`family_competition`.
+    pub async fn handle_skater_appearances(
         &self,
         req: CallToolRequestParam,
     ) -> Result<CallToolResult> {
-        let shoot_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("shoot_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: shoot_name"))?;
+        let mut args = Args::new(&req);
+        let skater_id = args
+            .optional_str("skater_id")
+            .map(|id| skater_record_id(&id));
+        let name = args.optional_str("name");
+        if skater_id.is_some() == name.is_some() {
+            args.invalid("skater_id", "provide exactly one of skater_id or name");
+        }
+        let name_parts = name.as_deref().map(split_first_last);
+        if matches!(name_parts, Some(None)) {
+            args.invalid("name", "must be a first and last name");
+        }
+        args.finish()?;
+
+        #[derive(serde::Deserialize)]
+        struct SkaterRow {
+            id: surrealdb::sql::Thing,
+            first_name: String,
+            last_name: String,
+        }
+
+        let skaters: Vec<SkaterRow> = match (&skater_id, name_parts.flatten()) {
+            (Some(id), _) => self
+                .db
+                .query("SELECT id, first_name, last_name FROM type::thing($skater_id);")
+                .bind(("skater_id", id.clone()))
+                .await?
+                .take(0)?,
+            (None, Some((first, last))) => self
+                .db
+                .query(
+                    "SELECT id, first_name, last_name FROM skater \
+                     WHERE string::lowercase(first_name) = string::lowercase($first) \
+                     AND string::lowercase(last_name) = string::lowercase($last) ORDER BY id;",
+                )
+                .bind(("first", first.to_string()))
+                .bind(("last", last.to_string()))
+                .await?
+                .take(0)?,
+            (None, None) => unreachable!("validated above"),
+        };
+
+        let skater = match skaters.as_slice() {
+            [] => {
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "found": false,
+                    "message": format!(
+                        "Skater not found: {}",
+                        skater_id.or(name).unwrap_or_default()
+                    ),
+                })));
+            }
+            [skater] => skater,
+            _ => {
+                let candidates: Vec<_> = skaters
+                    .iter()
+                    .map(|s| {
+                        serde_json::json!({
+                            "id": s.id.to_string(),
+                            "name": format!("{} {}", s.first_name, s.last_name),
+                        })
+                    })
+                    .collect();
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "found": false,
+                    "ambiguous": true,
+                    "message": format!(
+                        "{} skaters are named {}; pass skater_id",
+                        skaters.len(),
+                        name.unwrap_or_default()
+                    ),
+                    "candidates": candidates,
+                })));
+            }
+        };
 
         let query = r#"
-            SELECT in.last_name as family, in.delivery_email as email, gallery_status
-            FROM family_shoot
-            WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($shoot)
-            AND gallery_status IN ['pending', 'culling', 'processing']
-            ORDER BY in.last_name
+            SELECT * FROM shot_in WHERE in = $skater;
+            SELECT <string> out.competition AS event_id, out.competition.name AS event,
+                out.competition.date ?? out.competition.start_date AS date,
+                out.event_number AS event_number,
+                gallery_status, gallery_url
+            FROM competed_in WHERE in = $skater;
+            SELECT <string> out AS event_id, out.name AS event, out.shoot_date AS date,
+                gallery_status, in.last_name AS family
+            FROM family_shoot WHERE in IN (SELECT VALUE out FROM belongs_to WHERE in = $skater);
+            SELECT <string> out AS event_id, out.name AS event,
+                out.date ?? out.start_date AS date, gallery_status, in.last_name AS family
+            FROM family_competition WHERE in IN (SELECT VALUE out FROM belongs_to WHERE in = $skater);
         "#;
-
         let mut result = self
             .db
             .query(query)
-            .bind(("shoot", shoot_name.clone()))
+            .bind(("skater", skater.id.clone()))
             .await?;
 
-        let families: Vec<crate::photography::models::PendingFamily> =
-            result.take(0).unwrap_or_default();
+        #[derive(serde::Deserialize)]
+        struct EventEdge {
+            event_id: String,
+            event: Option<String>,
+            date: Option<String>,
+            gallery_status: Option<String>,
+            #[serde(default)]
+            gallery_url: Option<String>,
+            #[serde(default)]
+            event_number: Option<i64>,
+            #[serde(default)]
+            family: Option<String>,
+        }
 
-        Ok(CallToolResult::structured(serde_json::json!({
-            "shoot": shoot_name,
-            "pending_count": families.len(),
-            "families": families,
-        })))
-    }
+        #[derive(serde::Serialize)]
+        struct Appearance {
+            event_type: &'static str,
+            event_id: String,
+            event: Option<String>,
+            date: Option<String>,
+            via: Vec<&'static str>,
+            skater_gallery_status: Option<String>,
+            skater_gallery_url: Option<String>,
+            event_numbers: Vec<i64>,
+            family: Option<String>,
+            family_gallery_status: Option<String>,
+        }
 
-    /// Get status overview for a shoot
-    pub async fn handle_shoot_status(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
-        let shoot_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("shoot_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: shoot_name"))?;
+        let shot_in: Vec<crate::photography::models::ShotIn> = result.take(0)?;
+        let competed_in: Vec<EventEdge> = result.take(1)?;
+        let family_shoots: Vec<EventEdge> = result.take(2)?;
+        let family_competitions: Vec<EventEdge> = result.take(3)?;
 
-        // Get counts by gallery_status
-        let status_query = r#"
-            SELECT gallery_status, count() as count
-            FROM family_shoot
-            WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($shoot)
-            GROUP BY gallery_status
-        "#;
+        #[derive(serde::Deserialize)]
+        struct ShootInfo {
+            id: surrealdb::sql::Thing,
+            name: Option<String>,
+            shoot_date: Option<String>,
+        }
 
-        let mut status_result = self
+        let shoot_ids: Vec<surrealdb::sql::Thing> = shot_in.iter().map(|e| e.out.clone()).collect();
+        let shoots: Vec<ShootInfo> = self
             .db
-            .query(status_query)
-            .bind(("shoot", shoot_name.clone()))
-            .await?;
+            .query("SELECT id, name, shoot_date FROM $shoots;")
+            .bind(("shoots", shoot_ids))
+            .await?
+            .take(0)?;
+
+        // One entry per event, whichever edges reach it
+        fn slot(
+            appearances: &mut Vec<Appearance>,
+            event_type: &'static str,
+            event_id: String,
+            event: Option<String>,
+            date: Option<String>,
+        ) -> &mut Appearance {
+            let i = match appearances.iter().position(|a| a.event_id == event_id) {
+                Some(i) => i,
+                None => {
+                    appearances.push(Appearance {
+                        event_type,
+                        event_id,
+                        event,
+                        date,
+                        via: Vec::new(),
+                        skater_gallery_status: None,
+                        skater_gallery_url: None,
+                        event_numbers: Vec::new(),
+                        family: None,
+                        family_gallery_status: None,
+                    });
+                    appearances.len() - 1
+                }
+            };
+            &mut appearances[i]
+        }
 
-        #[derive(serde::Deserialize)]
-        struct StatusCount {
-            gallery_status: String,
-            count: i64,
+        let mut appearances: Vec<Appearance> = Vec::new();
+        for edge in shot_in {
+            let shoot = shoots.iter().find(|s| s.id == edge.out);
+            let appearance = slot(
+                &mut appearances,
+                "shoot",
+                edge.out.to_string(),
+                shoot.and_then(|s| s.name.clone()),
+                shoot.and_then(|s| s.shoot_date.clone()),
+            );
+            appearance.via.push("shot_in");
+            appearance.skater_gallery_status = Some(edge.gallery_status);
+            appearance.skater_gallery_url = edge.gallery_url;
+        }
+        for edge in competed_in {
+            let appearance = slot(
+                &mut appearances,
+                "competition",
+                edge.event_id,
+                edge.event,
+                edge.date,
+            );
+            if !appearance.via.contains(&"competed_in") {
+                appearance.via.push("competed_in");
+            }
+            appearance.skater_gallery_status = appearance
+                .skater_gallery_status
+                .take()
+                .or(edge.gallery_status);
+            appearance.skater_gallery_url =
+                appearance.skater_gallery_url.take().or(edge.gallery_url);
+            appearance.event_numbers.extend(edge.event_number);
+        }
+        for (event_type, edges) in [
+            ("shoot", family_shoots),
+            ("competition", family_competitions),
+        ] {
+            for edge in edges {
+                let appearance = slot(
+                    &mut appearances,
+                    event_type,
+                    edge.event_id,
+                    edge.event,
+                    edge.date,
+                );
+                if !appearance.via.contains(&"family") {
+                    appearance.via.push("family");
+                }
+                appearance.family_gallery_status = appearance
+                    .family_gallery_status
+                    .take()
+                    .or(edge.gallery_status);
+                appearance.family = appearance.family.take().or(edge.family);
+            }
         }
 
-        let status_counts: Vec<StatusCount> = status_result.take(0)?;
+        // Chronological; undated events last
+        appearances.sort_by(|a, b| {
+            (a.date.is_none(), &a.date, &a.event).cmp(&(b.date.is_none(), &b.date, &b.event))
+        });
+        let shoot_count = appearances
+            .iter()
+            .filter(|a| a.event_type == "shoot")
+            .count();
 
-        let mut counts = serde_json::Map::new();
-        let mut total = 0i64;
+        Ok(CallToolResult::structured(serde_json::json!({
+            "found": true,
+            "skater": {
+                "id": skater.id.to_string(),
+                "name": format!("{} {}", skater.first_name, skater.last_name),
+            },
+            "count": appearances.len(),
+            "shoot_count": shoot_count,
+            "competition_count": appearances.len() - shoot_count,
+            "appearances": appearances,
+        })))
+    }
 
-        for sc in status_counts {
-            counts.insert(sc.gallery_status, serde_json::json!(sc.count));
-            total += sc.count;
+    /// Skater-level gallery state for a shoot (`shot_in`), for families where each skater
+    /// gets their own gallery. Creates the `shot_in` edge if the skater isn't linked yet.
+    pub async fn handle_set_skater_gallery_status(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let skater_id = skater_record_id(&args.required_str("skater_id"));
+        let shoot_name = args.required_str("shoot_name");
+        let status = args.required_str("gallery_status").to_lowercase();
+        if !status.is_empty() && !GALLERY_STATUSES.contains(&status.as_str()) {
+            args.invalid(
+                "gallery_status",
+                &format!("must be one of: {}", GALLERY_STATUSES.join(", ")),
+            );
         }
-
-        // Also get total revenue if any purchases
-        let revenue_query = r#"
-            SELECT math::sum(purchase_amount) as total_revenue
-            FROM family_shoot
-            WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($shoot)
-            AND purchase_amount IS NOT NONE
-        "#;
-
-        let mut revenue_result = self
+        let gallery_url = args.optional_str("gallery_url");
+        args.finish()?;
+
+        let query = format!(
+            "SELECT VALUE id FROM type::thing($skater_id); \
+             SELECT VALUE id FROM shoot WHERE {};",
+            shoot_match("shoot")
+        );
+        let mut result = self
             .db
-            .query(revenue_query)
+            .query(query)
+            .bind(("skater_id", skater_id.clone()))
             .bind(("shoot", shoot_name.clone()))
             .await?;
+        let skater_ids: Vec<surrealdb::sql::Thing> = result.take(0)?;
+        let shoot_ids: Vec<surrealdb::sql::Thing> = result.take(1)?;
+        let Some(skater) = skater_ids.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "skater_not_found",
+                "message": format!("Skater not found: {}", skater_id),
+            })));
+        };
+        let Some(shoot) = shoot_ids.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "shoot_not_found",
+                "message": format!("No shoot found matching: {}", shoot_name),
+            })));
+        };
 
-        #[derive(serde::Deserialize)]
-        struct Revenue {
-            total_revenue: Option<f64>,
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!(
+                    "Would set {}'s gallery at {} to {}",
+                    skater, shoot_name, status
+                ),
+                serde_json::json!({
+                    "edge": "shot_in",
+                    "skater_id": skater.to_string(),
+                    "shoot_id": shoot.to_string(),
+                    "set": { "gallery_status": status, "gallery_url": gallery_url },
+                }),
+            ));
         }
 
-        let revenue: Vec<Revenue> = revenue_result.take(0).unwrap_or_default();
-        let total_revenue = revenue.first().and_then(|r| r.total_revenue).unwrap_or(0.0);
+        // Update the existing edge, or create it when the skater isn't linked to the shoot
+        let update_query = r#"
+            LET $existing = SELECT VALUE id FROM shot_in WHERE in = $skater AND out = $shoot LIMIT 1;
+            IF array::len($existing) > 0 {
+                UPDATE $existing SET gallery_status = $status, gallery_url = $url ?? gallery_url;
+            } ELSE {
+                RELATE $skater->shot_in->$shoot
+                SET gallery_status = $status, gallery_url = $url, created_at = time::now();
+            };
+            RETURN array::len($existing) = 0;
+            SELECT * FROM shot_in WHERE in = $skater AND out = $shoot LIMIT 1;
+        "#;
+        let mut result = self
+            .db
+            .query(update_query)
+            .bind(("skater", skater.clone()))
+            .bind(("shoot", shoot.clone()))
+            .bind(("status", status))
+            .bind(("url", gallery_url))
+            .await?;
+        let created_edge: Option<bool> = result.take(2)?;
+        let edges: Vec<crate::photography::models::ShotIn> = result.take(3)?;
+        let Some(edge) = edges.into_iter().next() else {
+            return Err(anyhow::anyhow!(
+                "shot_in edge missing after update for {}",
+                skater
+            ));
+        };
 
         Ok(CallToolResult::structured(serde_json::json!({
-            "shoot": shoot_name,
-            "total_families": total,
-            "status_breakdown": counts,
-            "total_revenue": total_revenue,
+            "success": true,
+            "created_edge": created_edge.unwrap_or(false),
+            "edge": shot_in_json(&edge, Some(&shoot_name)),
         })))
     }
 
-    /// Get details about a specific shoot
-    pub async fn handle_get_shoot(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
-        let shoot_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("shoot_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: shoot_name"))?;
-
-        let query = r#"
-            SELECT * FROM shoot
-            WHERE string::lowercase(name ?? '') CONTAINS string::lowercase($shoot)
-            LIMIT 1
-        "#;
-
+    /// A skater's own galleries (`shot_in` edges), optionally for one shoot.
+    pub async fn handle_get_skater_gallery(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let skater_id = skater_record_id(&args.required_str("skater_id"));
+        let shoot_name = args.optional_str("shoot_name");
+        args.finish()?;
+
+        let shoot_filter = match &shoot_name {
+            Some(_) => format!(
+                " AND out IN (SELECT VALUE id FROM shoot WHERE {})",
+                shoot_match("shoot")
+            ),
+            None => String::new(),
+        };
+        let query = format!(
+            "SELECT VALUE id FROM type::thing($skater_id); \
+             SELECT * FROM shot_in WHERE in = type::thing($skater_id){shoot_filter};"
+        );
         let mut result = self
             .db
             .query(query)
-            .bind(("shoot", shoot_name.clone()))
+            .bind(("skater_id", skater_id.clone()))
+            .bind(("shoot", shoot_name.clone().unwrap_or_default()))
             .await?;
 
-        let shoots: Vec<crate::photography::models::Shoot> = result.take(0)?;
-
-        if shoots.is_empty() {
+        let skater_ids: Vec<surrealdb::sql::Thing> = result.take(0)?;
+        if skater_ids.is_empty() {
             return Ok(CallToolResult::structured(serde_json::json!({
                 "found": false,
-                "message": format!("No shoot found matching: {}", shoot_name)
+                "reason": "skater_not_found",
+                "message": format!("Skater not found: {}", skater_id),
             })));
         }
-
-        let shoot = &shoots[0];
-
-        // Get linked families count
-        let family_query = r#"
-            SELECT count() as count FROM family_shoot WHERE out = $shoot_id GROUP ALL
-        "#;
-
-        let mut family_result = self
-            .db
-            .query(family_query)
-            .bind(("shoot_id", shoot.id.clone()))
-            .await?;
+        let edges: Vec<crate::photography::models::ShotIn> = result.take(1)?;
 
         #[derive(serde::Deserialize)]
-        struct Count {
-            count: i64,
+        struct ShootInfo {
+            id: surrealdb::sql::Thing,
+            name: Option<String>,
+            shoot_date: Option<String>,
         }
+        let shoot_ids: Vec<surrealdb::sql::Thing> = edges.iter().map(|e| e.out.clone()).collect();
+        let shoots: Vec<ShootInfo> = self
+            .db
+            .query("SELECT id, name, shoot_date FROM $shoots;")
+            .bind(("shoots", shoot_ids))
+            .await?
+            .take(0)?;
 
-        let counts: Vec<Count> = family_result.take(0).unwrap_or_default();
-        let family_count = counts.first().map(|c| c.count).unwrap_or(0);
+        // Oldest shoot first
+        let mut galleries: Vec<_> = edges
+            .iter()
+            .map(|edge| {
+                let shoot = shoots.iter().find(|s| s.id == edge.out);
+                let date = shoot.and_then(|s| s.shoot_date.clone());
+                let mut gallery = shot_in_json(edge, shoot.and_then(|s| s.name.as_deref()));
+                gallery["shoot_date"] = serde_json::json!(date);
+                (date, gallery)
+            })
+            .collect();
+        galleries.sort_by(|a, b| (a.0.is_none(), &a.0).cmp(&(b.0.is_none(), &b.0)));
+        let galleries: Vec<_> = galleries.into_iter().map(|(_, gallery)| gallery).collect();
 
         Ok(CallToolResult::structured(serde_json::json!({
-            "found": true,
-            "shoot": {
-                "id": shoot.id.to_string(),
-                "name": shoot.name,
-                "shoot_type": shoot.shoot_type,
-                "shoot_date": shoot.shoot_date,
-                "location": shoot.location,
-                "notes": shoot.notes,
-            },
-            "family_count": family_count,
+            "found": !galleries.is_empty(),
+            "skater_id": skater_id,
+            "count": galleries.len(),
+            "galleries": galleries,
+            "message": galleries.is_empty().then(|| match &shoot_name {
+                Some(shoot) => format!("{} has no skater gallery at {}", skater_id, shoot),
+                None => format!("{} has no skater galleries", skater_id),
+            }),
         })))
     }
 
-    /// List all families (with optional search)
-    pub async fn handle_list_families(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
-        let search = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("search"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
-
-        let query = if search.is_some() {
-            r#"
-                SELECT id, name, last_name, delivery_email FROM family
-                WHERE string::lowercase(last_name ?? '') CONTAINS string::lowercase($search)
-                   OR string::lowercase(name ?? '') CONTAINS string::lowercase($search)
-                ORDER BY last_name
-                LIMIT 50
-            "#
-        } else {
-            r#"
-                SELECT id, name, last_name, delivery_email FROM family
-                ORDER BY last_name
-                LIMIT 100
-            "#
+    /// Create several skaters for one family in a single transaction, each linked by
+    /// `belongs_to`. Missing last names default to the family's. Existing skaters (same
+    /// import-style key) are updated and linked rather than duplicated.
+    pub async fn handle_create_skaters_bulk(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let family_last_name = args.required_str("family_last_name");
+        let items = match args.optional_value("skaters") {
+            Some(serde_json::Value::Array(items)) if !items.is_empty() => items.clone(),
+            Some(_) => {
+                args.invalid(
+                    "skaters",
+                    "must be a non-empty array of {first_name, last_name?}",
+                );
+                Vec::new()
+            }
+            None => {
+                args.missing("skaters");
+                Vec::new()
+            }
         };
-
-        let mut result = self
-            .db
-            .query(query)
-            .bind(("search", search.clone().unwrap_or_default()))
-            .await?;
+        let mut requested: Vec<(String, Option<String>)> = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            let first_name = item
+                .get("first_name")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty());
+            let last_name = item
+                .get("last_name")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string);
+            match first_name {
+                Some(first_name) => requested.push((first_name.to_string(), last_name)),
+                None => args.invalid("skaters", &format!("item {} is missing first_name", i)),
+            }
+        }
+        args.finish()?;
 
         #[derive(serde::Deserialize)]
         struct FamilyRow {
             id: surrealdb::sql::Thing,
-            name: Option<String>,
             last_name: Option<String>,
-            delivery_email: Option<String>,
         }
 
-        let families: Vec<FamilyRow> = result.take(0)?;
+        let mut family_result = self
+            .query_family("id, last_name", &family_last_name)
+            .await?;
+        let families: Vec<FamilyRow> = family_result.take(0)?;
+        let Some(family) = families.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": format!("Family not found: {}", self.family_id_label(&family_last_name))
+            })));
+        };
+        let default_last = family
+            .last_name
+            .clone()
+            .unwrap_or_else(|| family_last_name.trim().to_string());
+
+        // One entry per key, so a repeated name in the request doesn't double-insert
+        let mut skaters: Vec<serde_json::Value> = Vec::new();
+        let mut keys: Vec<String> = Vec::new();
+        for (first_name, last_name) in requested {
+            let last_name = last_name.unwrap_or_else(|| default_last.clone());
+            let key = skater_key(&first_name, &last_name);
+            if keys.contains(&key) {
+                continue;
+            }
+            keys.push(key.clone());
+            skaters.push(serde_json::json!({
+                "id": key,
+                "first_name": first_name,
+                "last_name": last_name,
+            }));
+        }
 
-        let family_list: Vec<_> = families
+        let existing_ids: Vec<surrealdb::sql::Thing> = keys
             .iter()
-            .map(|f| {
-                let display_name = f
-                    .last_name
-                    .clone()
-                    .or_else(|| f.name.clone())
-                    .unwrap_or_else(|| "Unknown".to_string());
-                serde_json::json!({
-                    "id": f.id.to_string(),
-                    "name": display_name,
-                    "email": f.delivery_email,
-                })
-            })
+            .map(|k| surrealdb::sql::Thing::from(("skater", k.as_str())))
+            .collect();
+        let mut existing_result = self
+            .db
+            .query("SELECT VALUE meta::id(id) FROM $ids;")
+            .bind(("ids", existing_ids))
+            .await?;
+        let existing: Vec<String> = existing_result.take(0)?;
+
+        let skater_ids: Vec<String> = keys.iter().map(|k| format!("skater:{}", k)).collect();
+        let created: Vec<&String> = keys
+            .iter()
+            .zip(&skater_ids)
+            .filter(|(k, _)| !existing.contains(k))
+            .map(|(_, id)| id)
+            .collect();
+        let already_existed: Vec<&String> = keys
+            .iter()
+            .zip(&skater_ids)
+            .filter(|(k, _)| existing.contains(k))
+            .map(|(_, id)| id)
             .collect();
 
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!("Would create {} skater(s) for {}", created.len(), family.id),
+                serde_json::json!({
+                    "family_id": family.id.to_string(),
+                    "skaters": skaters,
+                    "create": created,
+                    "update_existing": already_existed,
+                    "relate": "belongs_to",
+                }),
+            ));
+        }
+
+        let create_query = r#"
+            BEGIN TRANSACTION;
+            FOR $s IN $skaters {
+                INSERT INTO skater (id, first_name, last_name, created_at)
+                VALUES ($s.id, $s.first_name, $s.last_name, time::now())
+                ON DUPLICATE KEY UPDATE first_name = $s.first_name, last_name = $s.last_name;
+                LET $skater = type::thing('skater', $s.id);
+                IF array::len((SELECT id FROM belongs_to WHERE in = $skater AND out = $family)) = 0 {
+                    RELATE $skater->belongs_to->$family SET created_at = time::now();
+                };
+            };
+            COMMIT TRANSACTION;
+        "#;
+
+        self.db
+            .query(create_query)
+            .bind(("skaters", skaters))
+            .bind(("family", family.id.clone()))
+            .await?
+            .check()?;
+
         Ok(CallToolResult::structured(serde_json::json!({
-            "count": families.len(),
-            "search": search,
-            "families": family_list,
+            "success": true,
+            "family_id": family.id.to_string(),
+            "skater_ids": skater_ids,
+            "created": created,
+            "already_existed": already_existed,
         })))
     }
 
-    /// Create a new family
-    pub async fn handle_create_family(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
-        let last_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("last_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: last_name"))?;
+    /// Get complete family record including all family members
+    pub async fn handle_get_family(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        args.finish()?;
+
+        let (family, matched_by) = match self.resolve_family(&last_name).await? {
+            FamilyResolution::Found(family, matched_by) => (family, matched_by),
+            FamilyResolution::Ambiguous(candidates) => {
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "found": false,
+                    "ambiguous": true,
+                    "message": format!("{} families match '{}'; use one of the candidate names", candidates.len(), last_name),
+                    "candidates": self.family_candidates(&candidates),
+                })));
+            }
+            FamilyResolution::NotFound => {
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "found": false,
+                    "message": format!("No family found with last name: {} (ID: {})", last_name, self.family_id_label(&last_name))
+                })));
+            }
+        };
+        let display_name =
+            self.family_display_name(family.last_name.as_deref(), family.name.as_deref());
 
-        let email = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("delivery_email"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: delivery_email"))?;
+        // Get all skaters belonging to this family
+        let skaters_query = r#"
+            SELECT in.id as id, in.first_name as first_name, in.last_name as last_name
+            FROM belongs_to
+            WHERE out = $family_id
+        "#;
 
-        let notes = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("notes"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
+        let mut skaters_result = self
+            .db
+            .query(skaters_query)
+            .bind(("family_id", family.id.clone()))
+            .await?;
 
-        // Generate ID and name from last name (matching CLI format)
-        let family_id = last_name.to_lowercase().replace(' ', "_");
-        let family_name = format!("Family {}", last_name);
+        #[derive(serde::Deserialize)]
+        struct SkaterInfo {
+            id: surrealdb::sql::Thing,
+            first_name: String,
+            last_name: String,
+        }
 
-        // Match CLI format: includes name, first_name, last_name for compatibility
-        let create_query = r#"
-            INSERT INTO family (id, name, first_name, last_name, delivery_email, notes, created_at)
-            VALUES (type::thing('family', $family_id), $name, 'Family', $last_name, $email, $notes, time::now())
-            ON DUPLICATE KEY UPDATE delivery_email = $email, notes = $notes
+        let skaters: Vec<SkaterInfo> = skaters_result.take(0).unwrap_or_default();
+
+        let skater_list: Vec<_> = skaters
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "id": s.id.to_string(),
+                    "name": format!("{} {}", s.first_name, s.last_name),
+                    "first_name": s.first_name,
+                    "last_name": s.last_name,
+                })
+            })
+            .collect();
+
+        // Engagement summary: linked events, lifetime revenue, most recent event date
+        let summary_query = r#"
+            SELECT count() as count, math::sum(purchase_amount ?? 0) as revenue
+            FROM family_shoot WHERE in = $family_id GROUP ALL;
+            SELECT count() as count, math::sum(purchase_amount ?? 0) as revenue
+            FROM family_competition WHERE in = $family_id GROUP ALL;
+            RETURN array::max(array::concat(
+                (SELECT VALUE out.shoot_date FROM family_shoot
+                    WHERE in = $family_id AND out.shoot_date IS NOT NONE),
+                (SELECT VALUE out.start_date FROM family_competition
+                    WHERE in = $family_id AND out.start_date IS NOT NONE)
+            ));
         "#;
 
-        let result = self
+        let mut summary_result = self
             .db
-            .query(create_query)
-            .bind(("family_id", family_id.clone()))
-            .bind(("name", family_name.clone()))
-            .bind(("last_name", last_name.clone()))
-            .bind(("email", email.clone()))
-            .bind(("notes", notes))
+            .query(summary_query)
+            .bind(("family_id", family.id.clone()))
             .await?;
 
-        // Check query result
-        result.check()?;
+        #[derive(serde::Deserialize)]
+        struct EdgeSummary {
+            count: i64,
+            revenue: Option<f64>,
+        }
+
+        let shoot_summary: Vec<EdgeSummary> = summary_result.take(0).unwrap_or_default();
+        let comp_summary: Vec<EdgeSummary> = summary_result.take(1).unwrap_or_default();
+        let last_event_date: Option<String> = summary_result.take(2).unwrap_or_default();
+
+        let shoot_count = shoot_summary.first().map(|s| s.count).unwrap_or(0);
+        let competition_count = comp_summary.first().map(|s| s.count).unwrap_or(0);
+        let lifetime_revenue = shoot_summary.first().and_then(|s| s.revenue).unwrap_or(0.0)
+            + comp_summary.first().and_then(|s| s.revenue).unwrap_or(0.0);
 
         Ok(CallToolResult::structured(serde_json::json!({
-            "success": true,
-            "family_id": format!("family:{}", family_id),
-            "name": family_name,
-            "last_name": last_name,
-            "email": email,
+            "found": true,
+            "matched_by": matched_by,
+            "family": {
+                "id": family.id.to_string(),
+                "name": display_name,
+                "email": family.delivery_email,
+                "tags": family.tags.clone().unwrap_or_default(),
+            },
+            "skaters": skater_list,
+            "skater_count": skaters.len(),
+            "summary": {
+                "shoot_count": shoot_count,
+                "competition_count": competition_count,
+                "lifetime_revenue": lifetime_revenue,
+                "last_event_date": last_event_date,
+            },
         })))
     }
 
-    /// Link a family to a shoot (creates family_shoot edge)
-    pub async fn handle_link_family_shoot(
+    /// Mark gallery as sent for a family at a competition
+    pub async fn handle_mark_gallery_sent(
         &self,
         req: CallToolRequestParam,
     ) -> Result<CallToolResult> {
-        let last_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("last_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: last_name"))?;
-
-        let shoot_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("shoot_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: shoot_name"))?;
-
-        // Use ID-based lookup for family (family:lastname_lowercase)
-        let family_id_str = format!("family:{}", last_name.to_lowercase().replace(' ', "_"));
-        let family_query = "SELECT VALUE id FROM type::thing($family_id);";
-        let mut family_result = self
-            .db
-            .query(family_query)
-            .bind(("family_id", family_id_str.clone()))
-            .await?;
-        let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
-
-        if family_ids.is_empty() {
-            return Ok(CallToolResult::structured(serde_json::json!({
-                "success": false,
-                "message": format!("No family found with last name: {} (ID: {})", last_name, family_id_str)
-            })));
-        }
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        let competition_name = args.required_str("competition_name");
+        let at = self.clock_arg(&mut args);
+        args.finish()?;
+
+        let dry_run = bool_arg(&req, "dry_run");
+
+        // Exact id first, then "First Last" and partial-name matches (see resolve_family)
+        let family = match self.resolve_family(&last_name).await? {
+            FamilyResolution::Found(family, _) => family,
+            FamilyResolution::Ambiguous(candidates) => {
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "success": false,
+                    "reason": "family_ambiguous",
+                    "ambiguous": true,
+                    "message": format!("{} families match '{}'; use one of the candidate names", candidates.len(), last_name),
+                    "candidates": self.family_candidates(&candidates),
+                })));
+            }
+            FamilyResolution::NotFound => {
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "success": false,
+                    "reason": "family_not_found",
+                    "message": format!("No family found with last name: {} (ID: {})", last_name, self.family_id_label(&last_name))
+                })));
+            }
+        };
 
-        // Find shoot
-        let shoot_query = "SELECT VALUE id FROM shoot WHERE string::lowercase(name ?? '') CONTAINS string::lowercase($shoot);";
-        let mut shoot_result = self
+        // Find competition
+        let comp_query = "SELECT VALUE id FROM competition WHERE string::lowercase(name ?? '') CONTAINS string::lowercase($comp);";
+        let mut comp_result = self
             .db
-            .query(shoot_query)
-            .bind(("shoot", shoot_name.clone()))
+            .query(comp_query)
+            .bind(("comp", competition_name.clone()))
             .await?;
-        let shoot_ids: Vec<surrealdb::sql::Thing> = shoot_result.take(0)?;
+        let comp_ids: Vec<surrealdb::sql::Thing> = comp_result.take(0)?;
 
-        if shoot_ids.is_empty() {
+        if comp_ids.is_empty() {
             return Ok(CallToolResult::structured(serde_json::json!({
                 "success": false,
-                "message": format!("No shoot found matching: {}", shoot_name)
+                "reason": "competition_not_found",
+                "message": format!("No competition found matching: {}", competition_name)
             })));
         }
 
-        // Check if edge already exists
+        // Check if edge exists first
         let check_query = r#"
-            SELECT id FROM family_shoot
-            WHERE in = $family_id AND out = $shoot_id
+            SELECT id, gallery_status, sent_date FROM family_competition
+            WHERE in = $family_id AND out = $comp_id
             LIMIT 1
         "#;
         let mut check_result = self
             .db
             .query(check_query)
-            .bind(("family_id", family_ids[0].clone()))
-            .bind(("shoot_id", shoot_ids[0].clone()))
+            .bind(("family_id", family.id.clone()))
+            .bind(("comp_id", comp_ids[0].clone()))
             .await?;
 
         #[derive(serde::Deserialize)]
         struct EdgeCheck {
+            #[allow(dead_code)]
             id: surrealdb::sql::Thing,
+            gallery_status: Option<String>,
+            sent_date: Option<String>,
         }
-        let existing: Vec<EdgeCheck> = check_result.take(0)?;
+        let edges: Vec<EdgeCheck> = check_result.take(0)?;
 
-        if !existing.is_empty() {
+        if edges.is_empty() {
             return Ok(CallToolResult::structured(serde_json::json!({
                 "success": false,
-                "message": format!("{} is already linked to shoot {}", last_name, shoot_name),
-                "family_id": family_ids[0].to_string(),
-                "shoot_id": shoot_ids[0].to_string(),
-                "existing_edge_id": existing[0].id.to_string(),
+                "reason": "not_linked",
+                "message": format!("No family_competition edge exists for {} at {}. Family may not be linked to this competition.", last_name, competition_name),
+                "family_id": family.id.to_string(),
+                "competition_id": comp_ids[0].to_string(),
             })));
         }
 
-        // Create family_shoot edge using RELATE
-        let relate_query = r#"
-            RELATE $family_id->family_shoot->$shoot_id
-            SET gallery_status = 'pending', created_at = time::now()
-        "#;
+        // Already sent: keep the first sent_date so turnaround metrics stay accurate
+        if edges[0].gallery_status.as_deref() == Some("sent") {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": true,
+                "changed": false,
+                "message": format!("Gallery for {} at {} was already sent", last_name, competition_name),
+                "sent_date": edges[0].sent_date,
+                "family_id": family.id.to_string(),
+                "competition_id": comp_ids[0].to_string(),
+            })));
+        }
 
-        self.db
-            .query(relate_query)
-            .bind(("family_id", family_ids[0].clone()))
-            .bind(("shoot_id", shoot_ids[0].clone()))
-            .await?;
+        if dry_run {
+            return Ok(dry_run_result(
+                format!(
+                    "Would mark gallery as sent for {} at {}",
+                    last_name, competition_name
+                ),
+                serde_json::json!({
+                    "edge": "family_competition",
+                    "family_id": family.id.to_string(),
+                    "competition_id": comp_ids[0].to_string(),
+                    "set": { "gallery_status": "sent", "sent_date": at.as_deref().unwrap_or("now") },
+                }),
+            ));
+        }
+
+        // Update family_competition edge
+        let update_query = format!(
+            r#"
+            UPDATE family_competition
+            SET gallery_status = 'sent', sent_date = {NOW}
+            WHERE in = $family_id AND out = $comp_id AND gallery_status != 'sent'
+            RETURN VALUE id
+        "#
+        );
+
+        // A concurrent call may have sent it between the check and here; that one wins
+        let updated: Vec<surrealdb::sql::Thing> = self
+            .db
+            .query(update_query)
+            .bind(("family_id", family.id.clone()))
+            .bind(("comp_id", comp_ids[0].clone()))
+            .bind(("now", at))
+            .await?
+            .take(0)?;
 
         Ok(CallToolResult::structured(serde_json::json!({
             "success": true,
-            "message": format!("Linked {} to shoot {}", last_name, shoot_name),
-            "family_id": family_ids[0].to_string(),
-            "shoot_id": shoot_ids[0].to_string(),
+            "changed": !updated.is_empty(),
+            "message": format!("Marked gallery as sent for {} at {}", last_name, competition_name),
+            "family_id": family.id.to_string(),
+            "competition_id": comp_ids[0].to_string(),
         })))
     }
 
-    /// Record a purchase for a family at a shoot
-    pub async fn handle_record_purchase(
+    /// List all families with pending galleries for a competition
+    pub async fn handle_list_pending_galleries(
         &self,
         req: CallToolRequestParam,
     ) -> Result<CallToolResult> {
-        let last_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("last_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: last_name"))?;
-
-        let shoot_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("shoot_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: shoot_name"))?;
+        let mut args = Args::new(&req);
+        let competition_name = args.required_str("competition_name");
+        args.finish()?;
 
-        let amount = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("amount"))
-            .and_then(|v| v.as_f64())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: amount"))?;
+        let query = format!(
+            r#"
+            SELECT in.last_name as family, in.delivery_email as email,
+                {IN_DELIVERY_EMAILS} as emails, gallery_status
+            FROM family_competition
+            WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($comp)
+            AND gallery_status IN $statuses
+            ORDER BY in.last_name
+        "#
+        );
+
+        let statuses = self.pending_statuses(&req);
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("comp", competition_name.clone()))
+            .bind(("statuses", statuses.clone()))
+            .await?;
+
+        let families: Vec<crate::photography::models::PendingFamily> =
+            result.take(0).unwrap_or_default();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "competition": competition_name,
+            "statuses": statuses,
+            "pending_count": families.len(),
+            "families": families,
+        })))
+    }
+
+    /// Get status overview for a competition
+    pub async fn handle_competition_status(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let competition_name = args.required_str("competition_name");
+        args.finish()?;
+
+        // Get counts by gallery_status
+        let status_query = r#"
+            SELECT gallery_status ?? 'unknown' as gallery_status, count() as count
+            FROM family_competition
+            WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($comp)
+            GROUP BY gallery_status
+        "#;
+
+        let mut status_result = self
+            .db
+            .query(status_query)
+            .bind(("comp", competition_name.clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct StatusCount {
+            gallery_status: Option<String>,
+            count: i64,
+        }
+
+        let status_counts: Vec<StatusCount> = status_result.take(0).unwrap_or_default();
+
+        let mut counts = serde_json::Map::new();
+        let mut total = 0i64;
+
+        for sc in status_counts {
+            let status = sc.gallery_status.unwrap_or_else(|| "unknown".to_string());
+            counts.insert(status, serde_json::json!(sc.count));
+            total += sc.count;
+        }
+
+        // Revenue and thank-you tracking (parity with shoot_status)
+        let summary_query = r#"
+            SELECT
+                math::sum(purchase_amount ?? 0) as total_revenue,
+                count(ty_requested = true) as ty_requested,
+                count(ty_sent = true) as ty_sent,
+                count(ty_requested = true AND ty_sent != true) as ty_pending
+            FROM family_competition
+            WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($comp)
+            GROUP ALL
+        "#;
+
+        let mut summary_result = self
+            .db
+            .query(summary_query)
+            .bind(("comp", competition_name.clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct Summary {
+            total_revenue: Option<f64>,
+            ty_requested: Option<i64>,
+            ty_sent: Option<i64>,
+            ty_pending: Option<i64>,
+        }
+
+        let summary: Vec<Summary> = summary_result.take(0).unwrap_or_default();
+        let summary = summary.first();
+        let total_revenue = summary.and_then(|s| s.total_revenue).unwrap_or(0.0);
+        let ty_requested = summary.and_then(|s| s.ty_requested).unwrap_or(0);
+        let ty_sent = summary.and_then(|s| s.ty_sent).unwrap_or(0);
+        let ty_pending = summary.and_then(|s| s.ty_pending).unwrap_or(0);
+
+        let competition = self.find_competition(&competition_name).await?;
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "competition": competition_name,
+            "date": competition.as_ref().and_then(|c| c.event_date()),
+            "location": competition.as_ref().and_then(|c| c.event_location()),
+            "total_families": total,
+            "status_breakdown": counts,
+            "total_revenue": total_revenue,
+            "thank_you": {
+                "requested": ty_requested,
+                "sent": ty_sent,
+                "pending": ty_pending,
+            },
+        })))
+    }
+
+    /// Create a competition with optional date and location
+    pub async fn handle_create_competition(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let name = args.required_str("name");
+        // Optional date parameter (format: YYYY-MM-DD)
+        let date = args.optional_str("date");
+        let location = args.optional_str("location");
+        let notes = args.optional_str("notes");
+        args.finish()?;
+
+        // Same key the CLI roster import uses, so later imports land on this record
+        let comp_key = competition_to_id(&name);
+
+        let mut existing_result = self
+            .db
+            .query("SELECT * FROM type::thing('competition', $comp_key);")
+            .bind(("comp_key", comp_key.clone()))
+            .await?;
+        let existing: Vec<crate::photography::models::Competition> = existing_result.take(0)?;
+        if let Some(competition) = existing.first() {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": format!("Competition already exists: {}", competition.id),
+                "competition": competition_json(competition),
+            })));
+        }
+
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!("Would create competition {}", name),
+                serde_json::json!({
+                    "create": format!("competition:{}", comp_key),
+                    "content": {
+                        "name": name,
+                        "date": date,
+                        "location": location,
+                        "notes": notes,
+                    },
+                }),
+            ));
+        }
+
+        // venue/start_date are the legacy import columns; keep them filled for the CLI
+        let create_query = r#"
+            CREATE type::thing('competition', $comp_key) CONTENT {
+                name: $name,
+                date: IF $date { type::datetime($date) } ELSE { NONE },
+                location: $location,
+                notes: $notes,
+                venue: $location ?? '',
+                start_date: IF $date { type::datetime($date) } ELSE { NONE }
+            };
+        "#;
+
+        let mut result = self
+            .db
+            .query(create_query)
+            .bind(("comp_key", comp_key))
+            .bind(("name", name.clone()))
+            .bind(("date", date))
+            .bind(("location", location))
+            .bind(("notes", notes))
+            .await?;
+
+        let created: Vec<crate::photography::models::Competition> = result.take(0)?;
+        match created.first() {
+            Some(competition) => Ok(CallToolResult::structured(serde_json::json!({
+                "success": true,
+                "competition": competition_json(competition),
+            }))),
+            None => Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": "Failed to create competition"
+            }))),
+        }
+    }
+
+    /// Get a competition by (partial) name with its date, location, and link counts
+    pub async fn handle_get_competition(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let competition_name = args.required_str("competition_name");
+        args.finish()?;
+
+        let Some(competition) = self.find_competition(&competition_name).await? else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "found": false,
+                "message": format!("No competition found matching: {}", competition_name)
+            })));
+        };
+
+        let counts_query = r#"
+            SELECT count() FROM family_competition WHERE out = $comp GROUP ALL;
+            SELECT count() FROM event WHERE competition = $comp GROUP ALL;
+        "#;
+        let mut counts_result = self
+            .db
+            .query(counts_query)
+            .bind(("comp", competition.id.clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct CountResult {
+            count: i64,
+        }
+
+        let family_count = counts_result
+            .take::<Vec<CountResult>>(0)?
+            .first()
+            .map(|r| r.count)
+            .unwrap_or(0);
+        let event_count = counts_result
+            .take::<Vec<CountResult>>(1)?
+            .first()
+            .map(|r| r.count)
+            .unwrap_or(0);
+
+        let mut body = competition_json(&competition);
+        body["found"] = serde_json::json!(true);
+        body["family_count"] = serde_json::json!(family_count);
+        body["event_count"] = serde_json::json!(event_count);
+        self.add_outstanding(&mut body, "family_competition", &competition.id)
+            .await?;
+        Ok(CallToolResult::structured(body))
+    }
+
+    /// Attach the families on `event` still waiting on delivery (anything but sent/purchased)
+    /// as a capped `outstanding` array; `outstanding_count` stays the full total.
+    async fn add_outstanding(
+        &self,
+        body: &mut serde_json::Value,
+        edge_table: &str,
+        event: &surrealdb::sql::Thing,
+    ) -> Result<()> {
+        let query = format!(
+            r#"
+            SELECT in.last_name AS family, in.delivery_email AS email,
+                {IN_DELIVERY_EMAILS} AS emails, gallery_status
+            FROM {edge_table}
+            WHERE out = $event AND gallery_status NOT IN ['sent', 'purchased']
+            ORDER BY family
+        "#
+        );
+        let mut result = self.db.query(query).bind(("event", event.clone())).await?;
+        let families: Vec<crate::photography::models::PendingFamily> = result.take(0)?;
+
+        let outstanding_count = families.len();
+        let items = families.into_iter().map(|f| serde_json::json!(f)).collect();
+        let (items, omitted) = cap_details(items, self.cfg().max_detail_items);
+
+        body["outstanding_count"] = serde_json::json!(outstanding_count);
+        body["outstanding"] = serde_json::json!(items);
+        body["outstanding_truncated"] = serde_json::json!(omitted > 0);
+        Ok(())
+    }
+
+    /// List competitions, most recent first (undated legacy competitions last)
+    pub async fn handle_list_competitions(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        // Archived seasons (archive_season) stay hidden unless asked for
+        let query = r#"
+            SELECT *, date ?? start_date AS sort_date, (date ?? start_date) IS NONE AS undated
+            FROM competition
+            WHERE $include_archived OR archived != true
+            ORDER BY undated ASC, sort_date DESC, name;
+        "#;
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("include_archived", bool_arg(&req, "include_archived")))
+            .await?;
+        let competitions: Vec<crate::photography::models::Competition> = result.take(0)?;
+
+        let list: Vec<_> = competitions.iter().map(competition_json).collect();
+        Ok(CallToolResult::structured(serde_json::json!({
+            "count": list.len(),
+            "competitions": list,
+        })))
+    }
+
+    /// Shoots and competitions with no linked families (setup mistakes, abandoned test events)
+    pub async fn handle_empty_events(&self, _req: CallToolRequestParam) -> Result<CallToolResult> {
+        let query = r#"
+            SELECT id, name, shoot_type, shoot_date AS date FROM shoot
+            WHERE count(<-family_shoot) = 0 ORDER BY date DESC, name;
+            SELECT id, name, date ?? start_date AS date FROM competition
+            WHERE count(<-family_competition) = 0 ORDER BY date DESC, name;
+        "#;
+        let mut result = self.db.query(query).await?;
+
+        #[derive(serde::Deserialize)]
+        struct EmptyEvent {
+            id: surrealdb::sql::Thing,
+            name: String,
+            #[serde(default)]
+            shoot_type: Option<String>,
+            date: Option<String>,
+        }
+
+        let shoots: Vec<EmptyEvent> = result.take(0)?;
+        let competitions: Vec<EmptyEvent> = result.take(1)?;
+
+        let to_json = |events: &[EmptyEvent]| -> Vec<serde_json::Value> {
+            events
+                .iter()
+                .map(|e| {
+                    serde_json::json!({
+                        "id": e.id.to_string(),
+                        "name": e.name,
+                        "shoot_type": e.shoot_type,
+                        "date": e.date,
+                    })
+                })
+                .collect()
+        };
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "total": shoots.len() + competitions.len(),
+            "shoots": to_json(&shoots),
+            "competitions": to_json(&competitions),
+        })))
+    }
+
+    /// Find time slots where skaters from more than one tracked family compete
+    pub async fn handle_detect_slot_conflicts(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let competition_name = args.required_str("competition_name");
+        args.finish()?;
+
+        // Tracked families = families linked to this competition
+        let query = r#"
+            SELECT VALUE in FROM family_competition
+            WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($comp);
+            SELECT
+                in.first_name as first_name,
+                in.last_name as last_name,
+                array::first(in->belongs_to.out) as family,
+                out.event_number as event_number,
+                out.split_ice as split_ice,
+                out.time_slot as time_slot
+            FROM competed_in
+            WHERE string::lowercase(out.competition.name ?? '') CONTAINS string::lowercase($comp)
+            AND out.time_slot IS NOT NONE;
+        "#;
+
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("comp", competition_name.clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct SlotEntry {
+            first_name: String,
+            last_name: String,
+            family: Option<surrealdb::sql::Thing>,
+            event_number: Option<i64>,
+            split_ice: Option<String>,
+            time_slot: String,
+        }
+
+        let tracked: Vec<surrealdb::sql::Thing> = result.take(0)?;
+        let entries: Vec<SlotEntry> = result.take(1)?;
+
+        let mut slots: std::collections::BTreeMap<String, Vec<SlotEntry>> =
+            std::collections::BTreeMap::new();
+        for entry in entries {
+            if entry.family.as_ref().is_some_and(|f| tracked.contains(f)) {
+                slots
+                    .entry(entry.time_slot.clone())
+                    .or_default()
+                    .push(entry);
+            }
+        }
+
+        let conflicts: Vec<_> = slots
+            .into_iter()
+            .filter_map(|(time_slot, entries)| {
+                let mut families: Vec<String> = entries
+                    .iter()
+                    .filter_map(|e| e.family.as_ref().map(|f| f.to_string()))
+                    .collect();
+                families.sort();
+                families.dedup();
+                if families.len() < 2 {
+                    return None;
+                }
+
+                let skaters: Vec<_> = entries
+                    .iter()
+                    .map(|e| {
+                        serde_json::json!({
+                            "name": format!("{} {}", e.first_name, e.last_name),
+                            "family": e.family.as_ref().map(|f| f.to_string()),
+                            "event_number": e.event_number,
+                            "split_ice": e.split_ice,
+                        })
+                    })
+                    .collect();
+
+                Some(serde_json::json!({
+                    "time_slot": time_slot,
+                    "family_count": families.len(),
+                    "skaters": skaters,
+                }))
+            })
+            .collect();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "competition": competition_name,
+            "tracked_families": tracked.len(),
+            "conflict_count": conflicts.len(),
+            "conflicts": conflicts,
+        })))
+    }
+
+    /// Create a new shoot
+    pub async fn handle_create_shoot(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let name = args.required_str("name");
+        let shoot_type = args.required_str("shoot_type");
+        let location = args.optional_str("location");
+        let notes = args.optional_str("notes");
+
+        // Optional date parameter (format: YYYY-MM-DD or YYYYMMDD)
+        let shoot_date = args.optional_str("date");
+        let packages = match args.optional_value("packages").map(parse_packages) {
+            Some(Ok(packages)) => Some(packages),
+            Some(Err(reason)) => {
+                args.invalid("packages", &reason);
+                None
+            }
+            None => None,
+        };
+        // Stable id from an external system (calendar, etc.) so re-runs don't duplicate
+        let external_key = args.optional_str("external_key");
+        let explicit_slug = args.optional_str("slug").map(|s| shoot_slug(&s, None));
+        if explicit_slug.as_deref() == Some("") {
+            args.invalid("slug", "must contain at least one letter or digit");
+        }
+        args.finish()?;
+
+        if let Some(key) = &external_key {
+            let mut existing_result = self
+                .db
+                .query("SELECT * FROM shoot WHERE external_key = $external_key LIMIT 1;")
+                .bind(("external_key", key.clone()))
+                .await?;
+            let existing: Vec<crate::photography::models::Shoot> = existing_result.take(0)?;
+            if let Some(shoot) = existing.first() {
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "success": true,
+                    "created": false,
+                    "message": format!("Shoot with external_key {} already exists", key),
+                    "shoot_id": shoot.id.to_string(),
+                    "slug": shoot.slug,
+                    "name": shoot.name,
+                    "shoot_type": shoot.shoot_type,
+                    "external_key": key,
+                    "packages": shoot.packages.clone().unwrap_or_default(),
+                })));
+            }
+        }
+
+        // An explicit slug must be free; a derived one gets a numeric suffix instead
+        let slug = match explicit_slug {
+            Some(slug) => {
+                let taken: Vec<surrealdb::sql::Thing> = self
+                    .db
+                    .query("SELECT VALUE id FROM shoot WHERE slug = $slug LIMIT 1;")
+                    .bind(("slug", slug.clone()))
+                    .await?
+                    .take(0)?;
+                if let Some(existing) = taken.first() {
+                    return Ok(CallToolResult::structured(serde_json::json!({
+                        "success": false,
+                        "created": false,
+                        "message": format!("Shoot slug {} is already used by {}", slug, existing),
+                        "slug": slug,
+                        "existing_shoot_id": existing.to_string(),
+                    })));
+                }
+                slug
+            }
+            None => self.next_shoot_slug(&name, shoot_date.as_deref()).await?,
+        };
+
+        // Same-name shoots are allowed but make name lookups ambiguous, so flag them
+        let same_name: Vec<surrealdb::sql::Thing> = self
+            .db
+            .query("SELECT VALUE id FROM shoot WHERE string::lowercase(name ?? '') = string::lowercase($name);")
+            .bind(("name", name.clone()))
+            .await?
+            .take(0)?;
+        let warning = (!same_name.is_empty()).then(|| {
+            format!(
+                "{} other shoot(s) are named '{}'; use slug {} to refer to this one",
+                same_name.len(),
+                name,
+                slug
+            )
+        });
+        let same_name: Vec<String> = same_name.iter().map(|id| id.to_string()).collect();
+
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!("Would create shoot {}", name),
+                serde_json::json!({
+                    "create": "shoot",
+                    "content": {
+                        "name": name,
+                        "shoot_type": shoot_type,
+                        "shoot_date": shoot_date.clone().unwrap_or_else(|| "now".to_string()),
+                        "location": location,
+                        "notes": notes,
+                        "packages": packages,
+                        "external_key": external_key,
+                        "slug": slug,
+                    },
+                    "warning": warning,
+                    "same_name_shoots": same_name,
+                }),
+            ));
+        }
+
+        // Build query based on whether shoot_date is provided
+        let create_query = if shoot_date.is_some() {
+            r#"
+                CREATE shoot CONTENT {
+                    name: $name,
+                    shoot_type: $shoot_type,
+                    shoot_date: type::datetime($shoot_date),
+                    location: $location,
+                    notes: $notes,
+                    packages: $packages,
+                    external_key: $external_key,
+                    slug: $slug
+                }
+            "#
+        } else {
+            r#"
+                CREATE shoot CONTENT {
+                    name: $name,
+                    shoot_type: $shoot_type,
+                    shoot_date: time::now(),
+                    location: $location,
+                    notes: $notes,
+                    packages: $packages,
+                    external_key: $external_key,
+                    slug: $slug
+                }
+            "#
+        };
+
+        let mut result = self
+            .db
+            .query(create_query)
+            .bind(("name", name.clone()))
+            .bind(("shoot_type", shoot_type.clone()))
+            .bind(("shoot_date", shoot_date.unwrap_or_default()))
+            .bind(("location", location))
+            .bind(("notes", notes))
+            .bind(("packages", packages))
+            .bind(("external_key", external_key.clone()))
+            .bind(("slug", slug))
+            .await?;
+
+        let shoots: Vec<crate::photography::models::Shoot> = result.take(0)?;
+
+        if let Some(shoot) = shoots.first() {
+            Ok(CallToolResult::structured(serde_json::json!({
+                "success": true,
+                "created": true,
+                "external_key": external_key,
+                "shoot_id": shoot.id.to_string(),
+                "slug": shoot.slug,
+                "warning": warning,
+                "same_name_shoots": same_name,
+                "name": shoot.name,
+                "shoot_type": shoot.shoot_type,
+                "packages": shoot.packages.clone().unwrap_or_default(),
+            })))
+        } else {
+            Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": "Failed to create shoot"
+            })))
+        }
+    }
+
+    /// Create a new shoot from an existing one (type, location, notes; no family links)
+    pub async fn handle_duplicate_shoot(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let source_shoot_name = args.required_str("source_shoot_name");
+        let name = args.required_str("name");
+        let shoot_date = args.optional_str("date");
+        args.finish()?;
+
+        let source_query = format!(
+            "SELECT * FROM shoot WHERE {} LIMIT 1;",
+            shoot_match("shoot")
+        );
+
+        let mut source_result = self
+            .db
+            .query(source_query)
+            .bind(("shoot", source_shoot_name.clone()))
+            .await?;
+        let sources: Vec<crate::photography::models::Shoot> = source_result.take(0)?;
+
+        let Some(source) = sources.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": format!("No shoot found matching: {}", source_shoot_name)
+            })));
+        };
+
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!("Would create shoot {} from {}", name, source.name),
+                serde_json::json!({
+                    "create": "shoot",
+                    "source_shoot_id": source.id.to_string(),
+                    "content": {
+                        "name": name,
+                        "shoot_type": source.shoot_type,
+                        "shoot_date": shoot_date.clone().unwrap_or_else(|| "now".to_string()),
+                        "location": source.location,
+                        "notes": source.notes,
+                        "packages": source.packages,
+                    },
+                }),
+            ));
+        }
+
+        let slug = self.next_shoot_slug(&name, shoot_date.as_deref()).await?;
+        let create_query = r#"
+            CREATE shoot CONTENT {
+                name: $name,
+                shoot_type: $shoot_type,
+                shoot_date: IF $shoot_date { type::datetime($shoot_date) } ELSE { time::now() },
+                location: $location,
+                notes: $notes,
+                packages: $packages,
+                slug: $slug
+            }
+        "#;
+
+        let mut result = self
+            .db
+            .query(create_query)
+            .bind(("name", name.clone()))
+            .bind(("shoot_type", source.shoot_type.clone()))
+            .bind(("shoot_date", shoot_date.unwrap_or_default()))
+            .bind(("location", source.location.clone()))
+            .bind(("notes", source.notes.clone()))
+            .bind(("packages", source.packages.clone()))
+            .bind(("slug", slug))
+            .await?;
+
+        let shoots: Vec<crate::photography::models::Shoot> = result.take(0)?;
+
+        match shoots.first() {
+            Some(shoot) => Ok(CallToolResult::structured(serde_json::json!({
+                "success": true,
+                "shoot_id": shoot.id.to_string(),
+                "slug": shoot.slug,
+                "name": shoot.name,
+                "shoot_type": shoot.shoot_type,
+                "shoot_date": shoot.shoot_date,
+                "source_shoot_id": source.id.to_string(),
+            }))),
+            None => Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": "Failed to create shoot"
+            }))),
+        }
+    }
+
+    /// Mark shoot gallery as sent for a family
+    pub async fn handle_mark_shoot_sent(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        let shoot_name = args.required_str("shoot_name");
+        let at = self.clock_arg(&mut args);
+        args.finish()?;
+
+        let dry_run = bool_arg(&req, "dry_run");
+
+        // Family lookup honours PHOTO_FAMILY_TABLE / PHOTO_FAMILY_ID_STRATEGY
+        let family_id_str = self.family_id_label(&last_name);
+        let mut family_result = self.query_family("VALUE id", &last_name).await?;
+        let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
+
+        if family_ids.is_empty() {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "family_not_found",
+                "message": format!("No family found with last name: {} (ID: {})", last_name, family_id_str)
+            })));
+        }
+
+        // Find shoot
+        let shoot_query = format!("SELECT VALUE id FROM shoot WHERE {};", shoot_match("shoot"));
+        let mut shoot_result = self
+            .db
+            .query(shoot_query)
+            .bind(("shoot", shoot_name.clone()))
+            .await?;
+        let shoot_ids: Vec<surrealdb::sql::Thing> = shoot_result.take(0)?;
+
+        if shoot_ids.is_empty() {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "shoot_not_found",
+                "message": format!("No shoot found matching: {}", shoot_name)
+            })));
+        }
+
+        // Check if edge exists first
+        let check_query = r#"
+            SELECT id, gallery_status, sent_date FROM family_shoot
+            WHERE in = $family_id AND out = $shoot_id
+            LIMIT 1
+        "#;
+        let mut check_result = self
+            .db
+            .query(check_query)
+            .bind(("family_id", family_ids[0].clone()))
+            .bind(("shoot_id", shoot_ids[0].clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct EdgeCheck {
+            #[allow(dead_code)]
+            id: surrealdb::sql::Thing,
+            gallery_status: Option<String>,
+            sent_date: Option<String>,
+        }
+        let edges: Vec<EdgeCheck> = check_result.take(0)?;
+
+        if edges.is_empty() {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "not_linked",
+                "message": format!("No family_shoot edge exists for {} at {}. Family may not be linked to this shoot.", last_name, shoot_name),
+                "family_id": family_ids[0].to_string(),
+                "shoot_id": shoot_ids[0].to_string(),
+            })));
+        }
+
+        // Already sent: keep the first sent_date so turnaround metrics stay accurate
+        if edges[0].gallery_status.as_deref() == Some("sent") {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": true,
+                "changed": false,
+                "message": format!("Shoot gallery for {} at {} was already sent", last_name, shoot_name),
+                "sent_date": edges[0].sent_date,
+                "family_id": family_ids[0].to_string(),
+                "shoot_id": shoot_ids[0].to_string(),
+            })));
+        }
+
+        if dry_run {
+            return Ok(dry_run_result(
+                format!(
+                    "Would mark shoot gallery as sent for {} at {}",
+                    last_name, shoot_name
+                ),
+                serde_json::json!({
+                    "edge": "family_shoot",
+                    "family_id": family_ids[0].to_string(),
+                    "shoot_id": shoot_ids[0].to_string(),
+                    "set": { "gallery_status": "sent", "sent_date": at.as_deref().unwrap_or("now") },
+                }),
+            ));
+        }
+
+        // Update family_shoot edge
+        let update_query = format!(
+            r#"
+            UPDATE family_shoot
+            SET gallery_status = 'sent', sent_date = {NOW}
+            WHERE in = $family_id AND out = $shoot_id AND gallery_status != 'sent'
+            RETURN VALUE id
+        "#
+        );
+
+        // A concurrent call may have sent it between the check and here; that one wins
+        let updated: Vec<surrealdb::sql::Thing> = self
+            .db
+            .query(update_query)
+            .bind(("family_id", family_ids[0].clone()))
+            .bind(("shoot_id", shoot_ids[0].clone()))
+            .bind(("now", at))
+            .await?
+            .take(0)?;
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "changed": !updated.is_empty(),
+            "message": format!("Marked shoot gallery as sent for {} at {}", last_name, shoot_name),
+            "family_id": family_ids[0].to_string(),
+            "shoot_id": shoot_ids[0].to_string(),
+        })))
+    }
+
+    /// List all shoots (with optional tag filter)
+    pub async fn handle_list_shoots(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let tag = args.optional_str("tag").map(|t| normalize_tag(&t));
+        // Default stays newest first; name breaks ties in every order
+        let (sort_by, sort_dir, order) = sort_arg(&mut args, SHOOT_SORTS, ("date", "desc"));
+        let offset = offset_arg(&mut args);
+        let limit = self.limit_arg(&mut args);
+        args.finish()?;
+
+        // Archived seasons (archive_season) stay hidden unless asked for
+        let mut conditions = vec!["($include_archived OR archived != true)"];
+        if tag.is_some() {
+            conditions.push("(tags ?? []) CONTAINS $tag");
+        }
+        let where_clause = conditions.join(" AND ");
+        let query = format!(
+            "SELECT * FROM shoot WHERE {where_clause} ORDER BY {order}, name, id LIMIT $limit START $offset; \
+             SELECT count() FROM shoot WHERE {where_clause} GROUP ALL;"
+        );
+
+        #[derive(serde::Deserialize)]
+        struct CountRow {
+            count: i64,
+        }
+
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("tag", tag.clone().unwrap_or_default()))
+            .bind(("include_archived", bool_arg(&req, "include_archived")))
+            .bind(("limit", limit as i64))
+            .bind(("offset", offset as i64))
+            .await?;
+        let shoots: Vec<crate::photography::models::Shoot> = result.take(0)?;
+        let total_count = result
+            .take::<Vec<CountRow>>(1)?
+            .first()
+            .map_or(0, |r| r.count);
+
+        let shoot_list: Vec<_> = shoots
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "id": s.id.to_string(),
+                    "name": s.name,
+                    "shoot_type": s.shoot_type,
+                    "shoot_date": s.shoot_date,
+                    "location": s.location,
+                    "tags": s.tags.clone().unwrap_or_default(),
+                    "archived": s.archived.unwrap_or(false),
+                })
+            })
+            .collect();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "count": shoots.len(),
+            "limit": limit,
+            "offset": offset,
+            "total_count": total_count,
+            "has_more": ((offset + shoots.len()) as i64) < total_count,
+            "tag": tag,
+            "sort_by": sort_by,
+            "sort_dir": sort_dir,
+            "shoots": shoot_list,
+        })))
+    }
+
+    /// Find shoots by location (partial match) and/or date range, oldest first.
+    /// Filters combine with AND; with none given this lists every shoot up to the limit.
+    pub async fn handle_find_shoots(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let location = args.optional_str("location");
+        let start_date = args.optional_str("start_date");
+        let end_date = args.optional_str("end_date");
+        let limit = self.limit_arg(&mut args);
+        args.finish()?;
+
+        let mut conditions = Vec::new();
+        if location.is_some() {
+            conditions.push(
+                "string::lowercase(location ?? '') CONTAINS string::lowercase($location)"
+                    .to_string(),
+            );
+        }
+        push_date_range(&mut conditions, "shoot_date", &start_date, &end_date);
+        if !bool_arg(&req, "include_archived") {
+            conditions.push("archived != true".to_string());
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let query = format!(
+            "SELECT * FROM shoot {} ORDER BY shoot_date ASC, name LIMIT $limit;",
+            where_clause
+        );
+
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("location", location.clone().unwrap_or_default()))
+            .bind(("start_date", start_date.clone().unwrap_or_default()))
+            .bind(("end_date", end_date.clone().unwrap_or_default()))
+            .bind(("limit", limit as i64))
+            .await?;
+        let shoots: Vec<crate::photography::models::Shoot> = result.take(0)?;
+
+        let shoot_list: Vec<_> = shoots
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "id": s.id.to_string(),
+                    "name": s.name,
+                    "shoot_type": s.shoot_type,
+                    "shoot_date": s.shoot_date,
+                    "location": s.location,
+                })
+            })
+            .collect();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "count": shoots.len(),
+            "limit": limit,
+            "filters": {
+                "location": location,
+                "start_date": start_date,
+                "end_date": end_date,
+            },
+            "shoots": shoot_list,
+        })))
+    }
+
+    /// List all families with pending galleries for a shoot
+    pub async fn handle_list_pending_shoot_galleries(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let shoot_name = args.required_str("shoot_name");
+        args.finish()?;
+
+        let query = format!(
+            r#"
+            SELECT in.last_name as family, in.delivery_email as email,
+                {IN_DELIVERY_EMAILS} as emails, gallery_status
+            FROM family_shoot
+            WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($shoot)
+            AND gallery_status IN $statuses
+            ORDER BY in.last_name
+        "#
+        );
+
+        let statuses = self.pending_statuses(&req);
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("shoot", shoot_name.clone()))
+            .bind(("statuses", statuses.clone()))
+            .await?;
+
+        let families: Vec<crate::photography::models::PendingFamily> =
+            result.take(0).unwrap_or_default();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "shoot": shoot_name,
+            "statuses": statuses,
+            "pending_count": families.len(),
+            "families": families,
+        })))
+    }
+
+    /// Get status overview for a shoot
+    pub async fn handle_shoot_status(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let shoot_name = args.required_str("shoot_name");
+        args.finish()?;
+
+        // Get counts by gallery_status
+        let status_query = r#"
+            SELECT gallery_status ?? 'unknown' as gallery_status, count() as count
+            FROM family_shoot
+            WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($shoot)
+            GROUP BY gallery_status
+        "#;
+
+        let mut status_result = self
+            .db
+            .query(status_query)
+            .bind(("shoot", shoot_name.clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct StatusCount {
+            gallery_status: Option<String>,
+            count: i64,
+        }
+
+        let status_counts: Vec<StatusCount> = status_result.take(0).unwrap_or_default();
+
+        let mut counts = serde_json::Map::new();
+        let mut total = 0i64;
+
+        for sc in status_counts {
+            let status = sc.gallery_status.unwrap_or_else(|| "unknown".to_string());
+            counts.insert(status, serde_json::json!(sc.count));
+            total += sc.count;
+        }
+
+        // Also get total revenue if any purchases
+        let revenue_query = r#"
+            SELECT math::sum(purchase_amount) as total_revenue
+            FROM family_shoot
+            WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($shoot)
+            AND purchase_amount IS NOT NONE
+            GROUP ALL
+        "#;
+
+        let mut revenue_result = self
+            .db
+            .query(revenue_query)
+            .bind(("shoot", shoot_name.clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct Revenue {
+            total_revenue: Option<f64>,
+        }
+
+        let revenue: Vec<Revenue> = revenue_result.take(0).unwrap_or_default();
+        let total_revenue = revenue.first().and_then(|r| r.total_revenue).unwrap_or(0.0);
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "shoot": shoot_name,
+            "total_families": total,
+            "status_breakdown": counts,
+            "total_revenue": total_revenue,
+        })))
+    }
+
+    /// Get details about a specific shoot
+    pub async fn handle_get_shoot(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let shoot_name = args.required_str("shoot_name");
+        args.finish()?;
+
+        let query = format!(
+            "SELECT * FROM shoot WHERE {} LIMIT 1;",
+            shoot_match("shoot")
+        );
+
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("shoot", shoot_name.clone()))
+            .await?;
+
+        let shoots: Vec<crate::photography::models::Shoot> = result.take(0)?;
+
+        if shoots.is_empty() {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "found": false,
+                "message": format!("No shoot found matching: {}", shoot_name)
+            })));
+        }
+
+        let shoot = &shoots[0];
+
+        // Get linked families count
+        let family_query = r#"
+            SELECT count() as count FROM family_shoot WHERE out = $shoot_id GROUP ALL
+        "#;
+
+        let mut family_result = self
+            .db
+            .query(family_query)
+            .bind(("shoot_id", shoot.id.clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct Count {
+            count: i64,
+        }
+
+        let counts: Vec<Count> = family_result.take(0).unwrap_or_default();
+        let family_count = counts.first().map(|c| c.count).unwrap_or(0);
+
+        let mut response = serde_json::json!({
+            "found": true,
+            "shoot": {
+                "id": shoot.id.to_string(),
+                "slug": shoot.slug,
+                "name": shoot.name,
+                "shoot_type": shoot.shoot_type,
+                "shoot_date": shoot.shoot_date,
+                "location": shoot.location,
+                "notes": shoot.notes,
+                "tags": shoot.tags.clone().unwrap_or_default(),
+            },
+            "family_count": family_count,
+        });
+        self.add_outstanding(&mut response, "family_shoot", &shoot.id)
+            .await?;
+
+        // Linked families are opt-in to keep the default response light
+        if bool_arg(&req, "include_families") {
+            let families_query = format!(
+                r#"
+                SELECT in.last_name as family, in.delivery_email as email,
+                    {IN_DELIVERY_EMAILS} as emails, gallery_status
+                FROM family_shoot
+                WHERE out = $shoot_id
+                ORDER BY family
+                LIMIT $limit
+            "#
+            );
+
+            let mut families_result = self
+                .db
+                .query(families_query)
+                .bind(("shoot_id", shoot.id.clone()))
+                .bind(("limit", self.cfg().max_detail_items as i64))
+                .await?;
+
+            let families: Vec<crate::photography::models::PendingFamily> =
+                families_result.take(0).unwrap_or_default();
+
+            response["has_more"] = serde_json::json!((families.len() as i64) < family_count);
+            response["families"] = serde_json::json!(families);
+        }
+
+        Ok(CallToolResult::structured(response))
+    }
+
+    /// List all families (with optional search)
+    pub async fn handle_list_families(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let search = args.optional_str("search");
+        let tag = args.optional_str("tag").map(|t| normalize_tag(&t));
+        let at_competition = args.optional_str("at_competition");
+        let at_shoot = args.optional_str("at_shoot");
+        let cursor = cursor_arg(&mut args, &self.cfg().family_table);
+        let (sort_by, sort_dir, order) = sort_arg(&mut args, FAMILY_SORTS, ("last_name", "asc"));
+        let cursor_mode = cursor.is_some() || bool_arg(&req, "use_cursor");
+        if cursor_mode
+            && (args.optional_str("sort_by").is_some() || args.optional_str("sort_dir").is_some())
+        {
+            args.invalid("sort_by", "cursor paging always walks families in id order");
+        }
+        if cursor_mode && args.optional_value("offset").is_some() {
+            args.invalid("offset", "use either cursor or offset paging, not both");
+        }
+        let offset = offset_arg(&mut args);
+        let limit = self.limit_arg(&mut args);
+        args.finish()?;
+
+        let mut conditions = Vec::new();
+        if search.is_some() {
+            conditions.push(
+                "(string::lowercase(last_name ?? '') CONTAINS string::lowercase($search)
+                   OR string::lowercase(name ?? '') CONTAINS string::lowercase($search))",
+            );
+        }
+        if tag.is_some() {
+            conditions.push("(tags ?? []) CONTAINS $tag");
+        }
+        // Event filters join through the edge tables, matching event names like the other tools
+        if at_competition.is_some() {
+            conditions.push(
+                "id IN (SELECT VALUE in FROM family_competition
+                   WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($at_competition))",
+            );
+        }
+        if at_shoot.is_some() {
+            conditions.push(
+                "id IN (SELECT VALUE in FROM family_shoot
+                   WHERE string::lowercase(out.name ?? '') CONTAINS string::lowercase($at_shoot))",
+            );
+        }
+        // total_count covers every page, so it is taken before the cursor condition
+        let count_where = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        };
+        if cursor.is_some() {
+            conditions.push("id > type::thing($cursor)");
+        }
+
+        let mut query = String::from(
+            "SELECT id, name, last_name, delivery_email, tags FROM type::table($family_table)",
+        );
+        if !conditions.is_empty() {
+            query.push_str(" WHERE ");
+            query.push_str(&conditions.join(" AND "));
+        }
+        // Cursor paging walks families in id order so inserts between pages can't shift them
+        if cursor_mode {
+            query.push_str(" ORDER BY id LIMIT $limit;");
+        } else {
+            // id breaks ties so equal sort keys can't repeat or vanish between pages
+            query.push_str(&format!(
+                " ORDER BY {order}, id LIMIT $limit START $offset;"
+            ));
+        }
+        query.push_str(&format!(
+            " SELECT count() FROM type::table($family_table){} GROUP ALL;",
+            count_where
+        ));
+
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("limit", limit as i64))
+            .bind(("offset", offset as i64))
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .bind(("search", search.clone().unwrap_or_default()))
+            .bind(("tag", tag.clone().unwrap_or_default()))
+            .bind(("at_competition", at_competition.clone().unwrap_or_default()))
+            .bind(("at_shoot", at_shoot.clone().unwrap_or_default()))
+            .bind(("cursor", cursor.clone().unwrap_or_default()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct FamilyRow {
+            id: surrealdb::sql::Thing,
+            name: Option<String>,
+            last_name: Option<String>,
+            delivery_email: Option<String>,
+            tags: Option<Vec<String>>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct CountRow {
+            count: i64,
+        }
+
+        let families: Vec<FamilyRow> = result.take(0)?;
+        let total_count = result
+            .take::<Vec<CountRow>>(1)?
+            .first()
+            .map_or(0, |r| r.count);
+        let next_cursor = next_cursor(cursor_mode, limit, families.iter().map(|f| &f.id));
+        let has_more = if cursor_mode {
+            next_cursor.is_some()
+        } else {
+            ((offset + families.len()) as i64) < total_count
+        };
+
+        // Opt-in: show nameless records as gaps rather than the "Unknown" placeholder
+        let show_incomplete = bool_arg(&req, "show_incomplete");
+        let blank = |s: &Option<String>| s.as_deref().is_none_or(|s| s.trim().is_empty());
+        let mut family_list: Vec<_> = families
+            .iter()
+            .map(|f| {
+                if show_incomplete && blank(&f.last_name) && blank(&f.name) {
+                    return serde_json::json!({
+                        "id": f.id.to_string(),
+                        "name": null,
+                        "data_incomplete": true,
+                        "email": f.delivery_email,
+                        "tags": f.tags.clone().unwrap_or_default(),
+                    });
+                }
+                let display_name =
+                    self.family_display_name(f.last_name.as_deref(), f.name.as_deref());
+                serde_json::json!({
+                    "id": f.id.to_string(),
+                    "name": display_name,
+                    "email": f.delivery_email,
+                    "tags": f.tags.clone().unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        // Opt-in pipeline view: edge counts by status across shoots and competitions
+        let with_status_summary = bool_arg(&req, "with_status_summary");
+        if with_status_summary && !families.is_empty() {
+            let summary_query = r#"
+                SELECT in, gallery_status, count() AS count FROM family_shoot
+                WHERE in IN $ids GROUP BY in, gallery_status;
+                SELECT in, gallery_status, count() AS count FROM family_competition
+                WHERE in IN $ids GROUP BY in, gallery_status;
+            "#;
+
+            #[derive(serde::Deserialize)]
+            struct StatusCount {
+                #[serde(rename = "in")]
+                family: surrealdb::sql::Thing,
+                gallery_status: Option<String>,
+                count: i64,
+            }
+
+            let ids: Vec<surrealdb::sql::Thing> = families.iter().map(|f| f.id.clone()).collect();
+            let mut summary_result = self.db.query(summary_query).bind(("ids", ids)).await?;
+
+            let mut summaries: std::collections::HashMap<String, [i64; 4]> =
+                std::collections::HashMap::new();
+            for idx in 0..2 {
+                let rows: Vec<StatusCount> = summary_result.take(idx)?;
+                for row in rows {
+                    let status = row.gallery_status.unwrap_or_default();
+                    let slot = if self.cfg().pending_statuses.contains(&status) {
+                        0
+                    } else if status == "sent" {
+                        1
+                    } else if status == "purchased" {
+                        2
+                    } else {
+                        3
+                    };
+                    summaries.entry(row.family.to_string()).or_default()[slot] += row.count;
+                }
+            }
+
+            for (family, entry) in families.iter().zip(family_list.iter_mut()) {
+                let [pending, sent, purchased, other] = summaries
+                    .get(&family.id.to_string())
+                    .copied()
+                    .unwrap_or_default();
+                entry["status_summary"] = serde_json::json!({
+                    "pending": pending,
+                    "sent": sent,
+                    "purchased": purchased,
+                    "other": other,
+                });
+            }
+        }
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "count": families.len(),
+            "limit": limit,
+            "offset": if cursor_mode { None } else { Some(offset) },
+            "total_count": total_count,
+            "has_more": has_more,
+            "search": search,
+            "tag": tag,
+            "at_competition": at_competition,
+            "at_shoot": at_shoot,
+            "sort_by": if cursor_mode { "id" } else { sort_by },
+            "sort_dir": if cursor_mode { "asc" } else { sort_dir },
+            "families": family_list,
+            "next_cursor": next_cursor,
+        })))
+    }
+
+    /// Families with neither `last_name` nor `name`, which other tools label "Unknown"
+    pub async fn handle_list_incomplete_families(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let limit = self.limit_arg(&mut args);
+        args.finish()?;
+
+        let query = r#"
+            SELECT <string> id AS id, delivery_email, tags, created_at,
+                count(<-belongs_to) AS skater_count,
+                count(->family_shoot) AS shoot_count,
+                count(->family_competition) AS competition_count
+            FROM type::table($family_table)
+            WHERE string::trim(last_name ?? '') = '' AND string::trim(name ?? '') = ''
+            ORDER BY created_at
+            LIMIT $limit;
+            SELECT count() FROM type::table($family_table)
+            WHERE string::trim(last_name ?? '') = '' AND string::trim(name ?? '') = ''
+            GROUP ALL;
+        "#;
+
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct IncompleteFamily {
+            id: String,
+            delivery_email: Option<String>,
+            tags: Option<Vec<String>>,
+            created_at: Option<String>,
+            skater_count: i64,
+            shoot_count: i64,
+            competition_count: i64,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct CountRow {
+            count: i64,
+        }
+
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .bind(("limit", limit as i64))
+            .await?;
+        let families: Vec<IncompleteFamily> = result.take(0)?;
+        let total = result
+            .take::<Vec<CountRow>>(1)?
+            .first()
+            .map(|r| r.count)
+            .unwrap_or(0);
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "total": total,
+            "count": families.len(),
+            "has_more": (families.len() as i64) < total,
+            "families": families,
+        })))
+    }
+
+    /// Append a dated entry to a family's notes timeline (`note` table)
+    pub async fn handle_add_note(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        let text = args.required_str("text");
+        let author = args.optional_str("author");
+        args.finish()?;
+
+        let mut family_result = self.query_family("VALUE id", &last_name).await?;
+        let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
+        let Some(family_id) = family_ids.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": format!("Family not found: {}", self.family_id_label(&last_name))
+            })));
+        };
+
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!("Would add a note to {}", family_id),
+                serde_json::json!({
+                    "create": "note",
+                    "content": { "family": family_id.to_string(), "text": text, "author": author },
+                }),
+            ));
+        }
+
+        let mut result = self
+            .db
+            .query(
+                "CREATE note CONTENT { family: $family, text: $text, author: $author, created_at: time::now() } \
+                 RETURN id, <string> created_at AS created_at;",
+            )
+            .bind(("family", family_id.clone()))
+            .bind(("text", text.clone()))
+            .bind(("author", author.clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct Created {
+            id: surrealdb::sql::Thing,
+            created_at: Option<String>,
+        }
+        let created: Vec<Created> = result.take(0)?;
+        let Some(note) = created.into_iter().next() else {
+            anyhow::bail!("Failed to create note for {}", family_id);
+        };
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "note_id": note.id.to_string(),
+            "family_id": family_id.to_string(),
+            "created_at": note.created_at,
+            "author": author,
+        })))
+    }
+
+    /// A family's notes timeline, oldest first. The legacy `notes` string, if set, is shown
+    /// as the first entry (`legacy: true`); `limit` keeps the most recent timeline entries.
+    pub async fn handle_list_notes(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        let limit = self.limit_arg(&mut args);
+        args.finish()?;
+
+        #[derive(serde::Deserialize)]
+        struct FamilyNotes {
+            id: surrealdb::sql::Thing,
+            notes: Option<String>,
+        }
+
+        let mut family_result = self.query_family("id, notes", &last_name).await?;
+        let families: Vec<FamilyNotes> = family_result.take(0)?;
+        let Some(family) = families.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "found": false,
+                "message": format!("Family not found: {}", self.family_id_label(&last_name))
+            })));
+        };
+
+        #[derive(serde::Deserialize)]
+        struct NoteRow {
+            id: surrealdb::sql::Thing,
+            text: String,
+            author: Option<String>,
+            created_at: Option<String>,
+        }
+
+        let mut result = self
+            .db
+            .query(
+                "SELECT count() FROM note WHERE family = $family GROUP ALL; \
+                 SELECT id, text, author, created_at FROM note WHERE family = $family \
+                 ORDER BY created_at DESC LIMIT $limit;",
+            )
+            .bind(("family", family.id.clone()))
+            .bind(("limit", limit as i64))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct CountResult {
+            count: i64,
+        }
+        let total = result
+            .take::<Vec<CountResult>>(0)?
+            .first()
+            .map(|r| r.count)
+            .unwrap_or(0);
+        let mut rows: Vec<NoteRow> = result.take(1)?;
+        rows.reverse();
+
+        let mut entries = Vec::new();
+        if let Some(legacy) = family
+            .notes
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+        {
+            entries.push(serde_json::json!({
+                "legacy": true,
+                "text": legacy,
+                "author": null,
+                "created_at": null,
+            }));
+        }
+        entries.extend(rows.iter().map(|n| {
+            serde_json::json!({
+                "id": n.id.to_string(),
+                "legacy": false,
+                "text": n.text,
+                "author": n.author,
+                "created_at": n.created_at,
+            })
+        }));
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "found": true,
+            "family_id": family.id.to_string(),
+            "total": total,
+            "has_more": (rows.len() as i64) < total,
+            "notes": entries,
+        })))
+    }
+
+    /// Create a new family
+    pub async fn handle_create_family(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        let email = args.required_str("delivery_email");
+        if !email.is_empty()
+            && let Err(reason) = validate_email(&email)
+        {
+            args.invalid("delivery_email", &reason);
+        }
+        let notes = args.optional_str("notes");
+        let referred_by_name = args.optional_str("referred_by");
+        args.finish()?;
+        let (last_name, last_name_raw) = self.stored_surname(last_name);
+
+        if bool_arg(&req, "check_duplicates") && !bool_arg(&req, "force") {
+            #[derive(serde::Deserialize)]
+            struct Existing {
+                id: surrealdb::sql::Thing,
+                name: Option<String>,
+                last_name: Option<String>,
+                delivery_email: Option<String>,
+            }
+
+            let mut existing_result = self
+                .db
+                .query(
+                    "SELECT id, name, last_name, delivery_email FROM type::table($family_table);",
+                )
+                .bind(("family_table", self.cfg().family_table.clone()))
+                .await?;
+            let existing: Vec<Existing> = existing_result.take(0)?;
+
+            let closest = existing
+                .iter()
+                .filter_map(|f| {
+                    let surname = f.last_name.as_deref()?;
+                    Some((f, surname_similarity(surname, &last_name)))
+                })
+                .filter(|(_, score)| *score >= DUPLICATE_SURNAME_THRESHOLD)
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            if let Some((family, score)) = closest {
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "success": false,
+                    "possible_duplicate": {
+                        "family_id": family.id.to_string(),
+                        "last_name": family.last_name,
+                        "display_name": self.family_display_name(family.last_name.as_deref(), family.name.as_deref()),
+                        "email": family.delivery_email,
+                        "similarity": (score * 100.0).round() / 100.0,
+                    },
+                    "message": format!(
+                        "'{}' looks like existing family {}; pass force: true to create anyway",
+                        last_name, family.id
+                    ),
+                })));
+            }
+        }
+
+        // Generate ID (name slug matches CLI format; uuid if configured) and display name
+        let family_id = self.upsert_family_key(&last_name, &email).await?;
+        let family_name = format!("Family {}", last_name);
+
+        // The referring family must already exist; its record id is what gets stored
+        let referred_by = match &referred_by_name {
+            Some(referrer) => {
+                let mut referrer_result = self.query_family("VALUE id", referrer).await?;
+                let referrer_ids: Vec<surrealdb::sql::Thing> = referrer_result.take(0)?;
+                match referrer_ids.as_slice() {
+                    [id] if id.to_string()
+                        == format_record_id(&self.cfg().family_table, &family_id) =>
+                    {
+                        return Err(anyhow::anyhow!("A family cannot refer itself"));
+                    }
+                    [id] => Some(id.clone()),
+                    [] => {
+                        return Ok(CallToolResult::structured(serde_json::json!({
+                            "success": false,
+                            "reason": "referrer_not_found",
+                            "message": format!(
+                                "No referring family found with last name: {} (ID: {})",
+                                referrer,
+                                self.family_id_label(referrer)
+                            ),
+                        })));
+                    }
+                    ids => {
+                        return Ok(CallToolResult::structured(serde_json::json!({
+                            "success": false,
+                            "reason": "referrer_ambiguous",
+                            "message": format!("{} families match referred_by '{}'", ids.len(), referrer),
+                            "candidates": ids.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+                        })));
+                    }
+                }
+            }
+            None => None,
+        };
+        let referred_by_label = referred_by.as_ref().map(|id| id.to_string());
+
+        // Read the record first so the result can say whether this was a new client
+        #[derive(serde::Deserialize)]
+        struct Prior {
+            delivery_email: Option<String>,
+            notes: Option<String>,
+        }
+        let prior: Option<Prior> = self
+            .db
+            .query("SELECT delivery_email, notes FROM type::thing($family_table, $family_id);")
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .bind(("family_id", family_id.clone()))
+            .await?
+            .take(0)?;
+        let was_created = prior.is_none();
+        let previous = prior.map(|p| {
+            serde_json::json!({
+                "delivery_email": p.delivery_email,
+                "notes": p.notes,
+            })
+        });
+
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!(
+                    "Would {} family {}",
+                    if was_created { "create" } else { "update" },
+                    family_name
+                ),
+                serde_json::json!({
+                    "was_created": was_created,
+                    "previous": previous,
+                    "upsert": format_record_id(&self.cfg().family_table, &family_id),
+                    "content": {
+                        "name": family_name,
+                        "last_name": last_name,
+                        "last_name_raw": last_name_raw,
+                        "delivery_email": email,
+                        "notes": notes,
+                        "referred_by": referred_by_label,
+                    },
+                }),
+            ));
+        }
+
+        // Match CLI format: includes name, first_name, last_name for compatibility
+        // Table name is validated as a plain identifier in Config::load
+        let create_query = format!(
+            "INSERT INTO {table} (id, name, first_name, last_name, last_name_raw, delivery_email, notes, referred_by, created_at)
+            VALUES (type::thing('{table}', $family_id), $name, 'Family', $last_name, $last_name_raw, $email, $notes, $referred_by, time::now())
+            ON DUPLICATE KEY UPDATE delivery_email = $email, notes = $notes, referred_by = $referred_by ?? referred_by",
+            table = self.cfg().family_table
+        );
+
+        let result = self
+            .db
+            .query(create_query)
+            .bind(("family_id", family_id.clone()))
+            .bind(("name", family_name.clone()))
+            .bind(("last_name", last_name.clone()))
+            .bind(("last_name_raw", last_name_raw.clone()))
+            .bind(("email", email.clone()))
+            .bind(("notes", notes))
+            .bind(("referred_by", referred_by))
+            .await?;
+
+        // Check query result
+        result.check()?;
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "was_created": was_created,
+            "previous": previous,
+            "family_id": format_record_id(&self.cfg().family_table, &family_id),
+            "name": family_name,
+            "last_name": last_name,
+            "last_name_raw": last_name_raw,
+            "email": email,
+            "referred_by": referred_by_label,
+        })))
+    }
+
+    /// Link a family to a shoot (creates family_shoot edge)
+    pub async fn handle_link_family_shoot(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        let shoot_name = args.required_str("shoot_name");
+        let at = self.clock_arg(&mut args);
+        args.finish()?;
+
+        let dry_run = bool_arg(&req, "dry_run");
+
+        // Family lookup honours PHOTO_FAMILY_TABLE / PHOTO_FAMILY_ID_STRATEGY
+        let family_id_str = self.family_id_label(&last_name);
+        let mut family_result = self.query_family("VALUE id", &last_name).await?;
+        let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
+
+        if family_ids.is_empty() {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": format!("No family found with last name: {} (ID: {})", last_name, family_id_str)
+            })));
+        }
+
+        // Find shoot
+        #[derive(serde::Deserialize)]
+        struct ShootMatch {
+            id: surrealdb::sql::Thing,
+            name: Option<String>,
+        }
+        let shoot_query = format!("SELECT id, name FROM shoot WHERE {};", shoot_match("shoot"));
+        let mut shoot_result = self
+            .db
+            .query(shoot_query)
+            .bind(("shoot", shoot_name.clone()))
+            .await?;
+        let shoots: Vec<ShootMatch> = shoot_result.take(0)?;
+
+        if shoots.is_empty() {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": format!("No shoot found matching: {}", shoot_name)
+            })));
+        }
+        let shoot_ids: Vec<surrealdb::sql::Thing> = shoots.iter().map(|s| s.id.clone()).collect();
+
+        // A live call links the first match; a dry run surfaces the ambiguity instead
+        if dry_run && (family_ids.len() > 1 || shoots.len() > 1) {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "dry_run": true,
+                "ambiguous": true,
+                "message": format!(
+                    "Ambiguous: '{}' matches {} families and '{}' matches {} shoots; a live call would link the first of each",
+                    last_name,
+                    family_ids.len(),
+                    shoot_name,
+                    shoots.len()
+                ),
+                "family_candidates": family_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+                "shoot_candidates": shoots
+                    .iter()
+                    .map(|s| serde_json::json!({ "id": s.id.to_string(), "name": s.name }))
+                    .collect::<Vec<_>>(),
+                "family_id": family_ids[0].to_string(),
+                "shoot_id": shoot_ids[0].to_string(),
+            })));
+        }
+
+        // Check if edge already exists
+        let check_query = r#"
+            SELECT id FROM family_shoot
+            WHERE in = $family_id AND out = $shoot_id
+            LIMIT 1
+        "#;
+        let mut check_result = self
+            .db
+            .query(check_query)
+            .bind(("family_id", family_ids[0].clone()))
+            .bind(("shoot_id", shoot_ids[0].clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct EdgeCheck {
+            id: surrealdb::sql::Thing,
+        }
+        let existing: Vec<EdgeCheck> = check_result.take(0)?;
+
+        if !existing.is_empty() {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "dry_run": dry_run,
+                "message": format!("{} is already linked to shoot {}", last_name, shoot_name),
+                "family_id": family_ids[0].to_string(),
+                "shoot_id": shoot_ids[0].to_string(),
+                "existing_edge_id": existing[0].id.to_string(),
+            })));
+        }
+
+        if dry_run {
+            return Ok(dry_run_result(
+                format!("Would link {} to shoot {}", last_name, shoot_name),
+                serde_json::json!({
+                    "relate": "family_shoot",
+                    "family_id": family_ids[0].to_string(),
+                    "shoot_id": shoot_ids[0].to_string(),
+                    "shoot_name": shoots[0].name,
+                    "set": { "gallery_status": "pending", "created_at": at.as_deref().unwrap_or("now") },
+                }),
+            ));
+        }
+
+        // Create family_shoot edge using RELATE
+        let relate_query = format!(
+            r#"
+            RELATE $family_id->family_shoot->$shoot_id
+            SET gallery_status = 'pending', created_at = {NOW}
+        "#
+        );
+
+        self.db
+            .query(relate_query)
+            .bind(("family_id", family_ids[0].clone()))
+            .bind(("shoot_id", shoot_ids[0].clone()))
+            .bind(("now", at))
+            .await?;
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "message": format!("Linked {} to shoot {}", last_name, shoot_name),
+            "family_id": family_ids[0].to_string(),
+            "shoot_id": shoot_ids[0].to_string(),
+        })))
+    }
+
+    /// Book a session in one step: create (or update) the family, create the shoot, and link
+    /// them with an initial gallery status, all in a single transaction.
+    pub async fn handle_book_client(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        let email = args.required_str("delivery_email");
+        if !email.is_empty()
+            && let Err(reason) = validate_email(&email)
+        {
+            args.invalid("delivery_email", &reason);
+        }
+        let family_notes = args.optional_str("family_notes");
+        let shoot_name = args.required_str("shoot_name");
+        let shoot_type = args.required_str("shoot_type");
+        let shoot_date = args.optional_str("date");
+        let location = args.optional_str("location");
+        let shoot_notes = args.optional_str("shoot_notes");
+        let status = args
+            .optional_str("gallery_status")
+            .unwrap_or_else(|| "pending".to_string());
+        if !GALLERY_STATUSES.contains(&status.as_str()) {
+            args.invalid(
+                "gallery_status",
+                &format!("must be one of: {}", GALLERY_STATUSES.join(", ")),
+            );
+        }
+        args.finish()?;
+        let (last_name, last_name_raw) = self.stored_surname(last_name);
+
+        let family_table = self.cfg().family_table.clone();
+        let family_key = self.upsert_family_key(&last_name, &email).await?;
+        let family_name = format!("Family {}", last_name);
+        // Ids are chosen up front so they can be returned without reading back the transaction
+        let family_id = surrealdb::sql::Thing::from((family_table.as_str(), family_key.as_str()));
+        let shoot_id = surrealdb::sql::Thing::from(("shoot", surrealdb::sql::Id::rand()));
+
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!("Would book {} for shoot {}", family_name, shoot_name),
+                serde_json::json!({
+                    "upsert_family": family_id.to_string(),
+                    "create_shoot": {
+                        "name": shoot_name,
+                        "shoot_type": shoot_type,
+                        "shoot_date": shoot_date.clone().unwrap_or_else(|| "now".to_string()),
+                        "location": location,
+                        "notes": shoot_notes,
+                    },
+                    "relate": "family_shoot",
+                    "set": { "gallery_status": status },
+                }),
+            ));
+        }
+
+        let slug = self
+            .next_shoot_slug(&shoot_name, shoot_date.as_deref())
+            .await?;
+
+        // Same family upsert as create_family; table name is validated in Config::load
+        let book_query = format!(
+            r#"
+            BEGIN TRANSACTION;
+            INSERT INTO {family_table} (id, name, first_name, last_name, last_name_raw, delivery_email, notes, created_at)
+            VALUES ($family, $family_name, 'Family', $last_name, $last_name_raw, $email, $family_notes, time::now())
+            ON DUPLICATE KEY UPDATE delivery_email = $email, notes = $family_notes ?? notes;
+            CREATE $shoot CONTENT {{
+                name: $shoot_name,
+                shoot_type: $shoot_type,
+                shoot_date: IF $shoot_date {{ type::datetime($shoot_date) }} ELSE {{ time::now() }},
+                location: $location,
+                notes: $shoot_notes,
+                slug: $shoot_slug
+            }};
+            RELATE $family->family_shoot->$shoot
+            SET gallery_status = $status, created_at = time::now();
+            COMMIT TRANSACTION;
+        "#
+        );
+
+        self.db
+            .query(book_query)
+            .bind(("family", family_id.clone()))
+            .bind(("family_name", family_name.clone()))
+            .bind(("last_name", last_name.clone()))
+            .bind(("last_name_raw", last_name_raw))
+            .bind(("email", email.clone()))
+            .bind(("family_notes", family_notes))
+            .bind(("shoot", shoot_id.clone()))
+            .bind(("shoot_name", shoot_name.clone()))
+            .bind(("shoot_type", shoot_type.clone()))
+            .bind(("shoot_date", shoot_date))
+            .bind(("location", location))
+            .bind(("shoot_notes", shoot_notes))
+            .bind(("shoot_slug", slug.clone()))
+            .bind(("status", status.clone()))
+            .await?
+            .check()?;
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "message": format!("Booked {} for shoot {}", family_name, shoot_name),
+            "family_id": family_id.to_string(),
+            "shoot_id": shoot_id.to_string(),
+            "shoot_slug": slug,
+            "gallery_status": status,
+        })))
+    }
+
+    /// Record a purchase for a family at a shoot
+    pub async fn handle_record_purchase(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        let shoot_name = args.required_str("shoot_name");
+        let explicit_amount = args.optional_f64("amount");
+        let package = args.optional_str("package");
+        if explicit_amount.is_none() && package.is_none() {
+            args.invalid("amount", "required unless package is given");
+        }
+        if let Some(amount) = explicit_amount
+            && let Err(reason) = validate_purchase_amount(
+                amount,
+                self.cfg().max_purchase_amount,
+                bool_arg(&req, "force"),
+            )
+        {
+            args.invalid("amount", &reason);
+        }
+        // Money and delivery state can differ (a deposit on a gallery that stays 'sent')
+        let set_status = args
+            .optional_value("set_status")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let status_override = args
+            .optional_str("status_override")
+            .map(|s| s.to_lowercase());
+        if let Some(status) = &status_override {
+            if !GALLERY_STATUSES.contains(&status.as_str()) {
+                args.invalid(
+                    "status_override",
+                    &format!("must be one of: {}", GALLERY_STATUSES.join(", ")),
+                );
+            } else if !set_status {
+                args.invalid(
+                    "status_override",
+                    "cannot be combined with set_status: false",
+                );
+            }
+        }
+        let at = self.clock_arg(&mut args);
+        args.finish()?;
+        let new_status = match status_override {
+            Some(status) => Some(status),
+            None if set_status => Some("purchased".to_string()),
+            None => None,
+        };
+
+        let dry_run = bool_arg(&req, "dry_run");
+
+        // Family lookup honours PHOTO_FAMILY_TABLE / PHOTO_FAMILY_ID_STRATEGY
+        let family_id_str = self.family_id_label(&last_name);
+        let mut family_result = self.query_family("VALUE id", &last_name).await?;
+        let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
+
+        if family_ids.is_empty() {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "family_not_found",
+                "message": format!("No family found with last name: {} (ID: {})", last_name, family_id_str)
+            })));
+        }
+
+        // Find shoot (with its package price list)
+        let shoot_query = format!(
+            "SELECT id, packages FROM shoot WHERE {};",
+            shoot_match("shoot")
+        );
+        let mut shoot_result = self
+            .db
+            .query(shoot_query)
+            .bind(("shoot", shoot_name.clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct ShootPricing {
+            id: surrealdb::sql::Thing,
+            packages: Option<Vec<crate::photography::models::ShootPackage>>,
+        }
+
+        let shoots: Vec<ShootPricing> = shoot_result.take(0)?;
+
+        let Some(shoot) = shoots.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "shoot_not_found",
+                "message": format!("No shoot found matching: {}", shoot_name)
+            })));
+        };
+        let shoot_ids = [shoot.id];
+
+        // Without this check the UPDATE below would match nothing and still report success
+        let mut link_result = self
+            .db
+            .query("SELECT VALUE id FROM family_shoot WHERE in = $family_id AND out = $shoot_id LIMIT 1;")
+            .bind(("family_id", family_ids[0].clone()))
+            .bind(("shoot_id", shoot_ids[0].clone()))
+            .await?;
+        let links: Vec<surrealdb::sql::Thing> = link_result.take(0)?;
+        if links.is_empty() {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "not_linked",
+                "message": format!("No family_shoot edge exists for {} at {}. Family may not be linked to this shoot.", last_name, shoot_name),
+                "family_id": family_ids[0].to_string(),
+                "shoot_id": shoot_ids[0].to_string(),
+            })));
+        }
+
+        // Resolve the package price; an explicit amount still wins
+        let packages = shoot.packages.unwrap_or_default();
+        let package_price = match &package {
+            Some(package_name) => {
+                match packages
+                    .iter()
+                    .find(|p| p.name.eq_ignore_ascii_case(package_name.trim()))
+                {
+                    Some(p) => Some(p.price_cents as f64 / 100.0),
+                    None => {
+                        let available: Vec<_> = packages.iter().map(|p| p.name.as_str()).collect();
+                        return Ok(CallToolResult::structured(serde_json::json!({
+                            "success": false,
+                            "message": format!("No package '{}' on shoot {}", package_name, shoot_name),
+                            "available_packages": available,
+                        })));
+                    }
+                }
+            }
+            None => None,
+        };
+        let amount = explicit_amount.or(package_price).unwrap_or_default();
+        // An explicit amount was validated with the other arguments; a package price is only
+        // known now, so it gets the same positive/ceiling check here
+        if explicit_amount.is_none()
+            && let Err(reason) = validate_purchase_amount(
+                amount,
+                self.cfg().max_purchase_amount,
+                bool_arg(&req, "force"),
+            )
+        {
+            return Err(crate::args::InvalidParams {
+                invalid: vec![("package".to_string(), reason)],
+                ..Default::default()
+            }
+            .into());
+        }
+
+        if dry_run {
+            return Ok(dry_run_result(
+                format!(
+                    "Would record ${:.2} purchase for {} at {}",
+                    amount, last_name, shoot_name
+                ),
+                serde_json::json!({
+                    "edge": "family_shoot",
+                    "family_id": family_ids[0].to_string(),
+                    "shoot_id": shoot_ids[0].to_string(),
+                    "set": {
+                        "gallery_status": new_status.as_deref().unwrap_or("unchanged"),
+                        "purchase_amount": amount,
+                        "purchase_date": at.as_deref().unwrap_or("now"),
+                        "package": package,
+                    },
+                }),
+            ));
+        }
+
+        // Update family_shoot edge with purchase info
+        let update_query = format!(
+            r#"
+            UPDATE family_shoot
+            SET gallery_status = $status ?? gallery_status, purchase_amount = $amount,
+                purchase_date = {NOW}, package = $package
+            WHERE in = $family_id AND out = $shoot_id
+            RETURN VALUE gallery_status
+        "#
+        );
+
+        let statuses: Vec<String> = self
+            .db
+            .query(update_query)
+            .bind(("family_id", family_ids[0].clone()))
+            .bind(("shoot_id", shoot_ids[0].clone()))
+            .bind(("amount", amount))
+            .bind(("package", package.clone()))
+            .bind(("status", new_status))
+            .bind(("now", at))
+            .await?
+            .take(0)?;
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "message": format!("Recorded ${:.2} purchase for {} at {}", amount, last_name, shoot_name),
+            "amount": amount,
+            "gallery_status": statuses.into_iter().next(),
+            "package": package,
+            "package_price": package_price,
+            "amount_matches_package": package_price.map(|p| (p - amount).abs() < 0.005),
+        })))
+    }
+
+    /// Move every edge of a shoot or competition from one gallery status to another
+    pub async fn handle_bulk_transition(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let from_status = args.required_str("from_status").to_lowercase();
+        let to_status = args.required_str("to_status").to_lowercase();
+        let shoot_name = args.optional_str("shoot_name");
+        let competition_name = args.optional_str("competition_name");
+        let at = self.clock_arg(&mut args);
+        args.finish()?;
+
+        for (key, status) in [("from_status", &from_status), ("to_status", &to_status)] {
+            if !GALLERY_STATUSES.contains(&status.as_str()) {
+                return Err(anyhow::anyhow!(
+                    "Invalid {} '{}'. Valid statuses: {}",
+                    key,
+                    status,
+                    GALLERY_STATUSES.join(", ")
+                ));
+            }
+        }
+
+        if from_status == to_status {
+            return Err(anyhow::anyhow!(
+                "from_status and to_status are both '{}'",
+                from_status
+            ));
+        }
+
+        // Edge/target tables come from this fixed match, never from input
+        let (edge_table, target_table, target_name) = match (shoot_name, competition_name) {
+            (Some(name), None) => ("family_shoot", "shoot", name),
+            (None, Some(name)) => ("family_competition", "competition", name),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Provide exactly one of: shoot_name or competition_name"
+                ));
+            }
+        };
+
+        let target_query = format!(
+            "SELECT VALUE id FROM {} WHERE string::lowercase(name ?? '') CONTAINS string::lowercase($name);",
+            target_table
+        );
+        let mut target_result = self
+            .db
+            .query(target_query)
+            .bind(("name", target_name.clone()))
+            .await?;
+        let target_ids: Vec<surrealdb::sql::Thing> = target_result.take(0)?;
+
+        let Some(target_id) = target_ids.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": format!("No {} found matching: {}", target_table, target_name)
+            })));
+        };
+
+        let edges_query = format!(
+            "SELECT id, in.last_name as family FROM {} WHERE out = $target AND gallery_status = $from ORDER BY family;",
+            edge_table
+        );
+        let mut edges_result = self
+            .db
+            .query(edges_query)
+            .bind(("target", target_id.clone()))
+            .bind(("from", from_status.clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct MatchedEdge {
+            id: surrealdb::sql::Thing,
+            family: Option<String>,
+        }
+
+        let edges: Vec<MatchedEdge> = edges_result.take(0)?;
+        let families: Vec<String> = edges
+            .iter()
+            .map(|e| self.family_display_name(e.family.as_deref(), None))
+            .collect();
+        let edge_ids: Vec<surrealdb::sql::Thing> = edges.into_iter().map(|e| e.id).collect();
+
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!(
+                    "Would move {} {} edge(s) at {} from '{}' to '{}'",
+                    edge_ids.len(),
+                    edge_table,
+                    target_name,
+                    from_status,
+                    to_status
+                ),
+                serde_json::json!({
+                    "edge": edge_table,
+                    "target_id": target_id.to_string(),
+                    "count": edge_ids.len(),
+                    "families": families,
+                    "set": { "gallery_status": to_status },
+                }),
+            ));
+        }
+
+        if !edge_ids.is_empty() {
+            // Only the edges listed above are touched; sent_date is stamped like mark_*_sent
+            let update_query = format!(
+                r#"
+                UPDATE $edge_ids SET
+                    gallery_status = $to,
+                    sent_date = IF $to = 'sent' {{ sent_date ?? {NOW} }} ELSE {{ sent_date }}
+                RETURN NONE;
+            "#
+            );
+            self.db
+                .query(update_query)
+                .bind(("edge_ids", edge_ids.clone()))
+                .bind(("to", to_status.clone()))
+                .bind(("now", at))
+                .await?
+                .check()?;
+        }
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "message": format!(
+                "Moved {} famil{} at {} from '{}' to '{}'",
+                edge_ids.len(),
+                if edge_ids.len() == 1 { "y" } else { "ies" },
+                target_name,
+                from_status,
+                to_status
+            ),
+            "target_id": target_id.to_string(),
+            "changed": edge_ids.len(),
+            "families": families,
+        })))
+    }
+
+    /// Re-deliver a sent gallery: revert it to the redo status, clear sent_date, log the reason
+    pub async fn handle_resend_gallery(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        let reason = args.required_str("reason");
+        let shoot_name = args.optional_str("shoot_name");
+        let competition_name = args.optional_str("competition_name");
+        let to_status = args
+            .optional_str("to_status")
+            .map(|s| s.to_lowercase())
+            .unwrap_or_else(|| self.cfg().resend_status.clone());
+        if to_status == "sent" || !GALLERY_STATUSES.contains(&to_status.as_str()) {
+            args.invalid("to_status", "must be a gallery status other than 'sent'");
+        }
+        if shoot_name.is_some() == competition_name.is_some() {
+            args.invalid(
+                "shoot_name",
+                "provide exactly one of shoot_name or competition_name",
+            );
+        }
+        let at = self.clock_arg(&mut args);
+        args.finish()?;
+
+        // Edge/target tables come from this fixed match, never from input
+        let (edge_table, target_table, target_name) = match (shoot_name, competition_name) {
+            (Some(name), _) => ("family_shoot", "shoot", name),
+            (None, Some(name)) => ("family_competition", "competition", name),
+            (None, None) => unreachable!("validated above"),
+        };
+
+        let mut family_result = self.query_family("VALUE id", &last_name).await?;
+        let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
+        let Some(family_id) = family_ids.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "family_not_found",
+                "message": format!("Family not found: {}", self.family_id_label(&last_name))
+            })));
+        };
+
+        let target_query = format!(
+            "SELECT VALUE id FROM {target_table} \
+             WHERE string::lowercase(name ?? '') CONTAINS string::lowercase($name) LIMIT 1;"
+        );
+        let target_ids: Vec<surrealdb::sql::Thing> = self
+            .db
+            .query(target_query)
+            .bind(("name", target_name.clone()))
+            .await?
+            .take(0)?;
+        let Some(target_id) = target_ids.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": format!("{target_table}_not_found"),
+                "message": format!("No {} found matching: {}", target_table, target_name),
+            })));
+        };
+
+        let edge_query = format!(
+            "SELECT id, gallery_status, sent_date FROM {edge_table} \
+             WHERE in = $family_id AND out = $target_id LIMIT 1;"
+        );
+        let mut edge_result = self
+            .db
+            .query(edge_query)
+            .bind(("family_id", family_id.clone()))
+            .bind(("target_id", target_id))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct GalleryEdge {
+            id: surrealdb::sql::Thing,
+            gallery_status: String,
+            sent_date: Option<String>,
+        }
+
+        let edges: Vec<GalleryEdge> = edge_result.take(0)?;
+        let Some(edge) = edges.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "not_linked",
+                "message": format!("No {} edge for {} at {}", edge_table, last_name, target_name),
+            })));
+        };
+
+        if edge.gallery_status != "sent" {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": format!(
+                    "Gallery for {} at {} is '{}', not 'sent'; nothing to resend",
+                    last_name, target_name, edge.gallery_status
+                ),
+                "prior_status": edge.gallery_status,
+            })));
+        }
+
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!(
+                    "Would revert {} at {} from sent to {}",
+                    last_name, target_name, to_status
+                ),
+                serde_json::json!({
+                    "edge": edge.id.to_string(),
+                    "set": { "gallery_status": to_status, "sent_date": null },
+                    "log": { "reason": reason, "prior_sent_date": edge.sent_date },
+                }),
+            ));
+        }
+
+        // resends is assigned first so it captures the pre-update status and sent_date
+        let update_query = format!(
+            r#"
+            UPDATE $edge SET
+                resends = array::append(resends ?? [], {{
+                    at: {NOW},
+                    reason: $reason,
+                    prior_status: gallery_status,
+                    prior_sent_date: sent_date
+                }}),
+                gallery_status = $to_status,
+                sent_date = NONE
+            RETURN NONE;
+        "#
+        );
+        self.db
+            .query(update_query)
+            .bind(("edge", edge.id.clone()))
+            .bind(("now", at))
+            .bind(("reason", reason.clone()))
+            .bind(("to_status", to_status.clone()))
+            .await?
+            .check()?;
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "message": format!("Reverted {} at {} from sent to {}", last_name, target_name, to_status),
+            "edge": edge.id.to_string(),
+            "prior_status": edge.gallery_status,
+            "prior_sent_date": edge.sent_date,
+            "new_status": to_status,
+            "reason": reason,
+        })))
+    }
+
+    /// Move a recorded purchase from one shoot to another for the same family. Refuses to
+    /// overwrite a purchase already on the destination unless `force` is set.
+    pub async fn handle_move_purchase(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        let from_event = args.required_str("from_event");
+        let to_event = args.required_str("to_event");
+        args.finish()?;
+
+        let dry_run = bool_arg(&req, "dry_run");
+        let force = bool_arg(&req, "force");
+
+        let family_id_str = self.family_id_label(&last_name);
+        let mut family_result = self.query_family("VALUE id", &last_name).await?;
+        let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
+
+        let lookup_query = format!(
+            "SELECT VALUE id FROM shoot WHERE {}; SELECT VALUE id FROM shoot WHERE {};",
+            shoot_match("from"),
+            shoot_match("to")
+        );
+        let mut lookup = self
+            .db
+            .query(lookup_query)
+            .bind(("from", from_event.clone()))
+            .bind(("to", to_event.clone()))
+            .await?;
+
+        let from_ids: Vec<surrealdb::sql::Thing> = lookup.take(0)?;
+        let to_ids: Vec<surrealdb::sql::Thing> = lookup.take(1)?;
+
+        let Some(family_id) = family_ids.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "family_not_found",
+                "message": format!("No family found with last name: {} (ID: {})", last_name, family_id_str)
+            })));
+        };
+        let Some(from_id) = from_ids.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "shoot_not_found",
+                "message": format!("No shoot found matching: {}", from_event)
+            })));
+        };
+        let Some(to_id) = to_ids.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "shoot_not_found",
+                "message": format!("No shoot found matching: {}", to_event)
+            })));
+        };
+
+        if from_id == to_id {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": format!("'{}' and '{}' resolve to the same shoot ({})", from_event, to_event, from_id)
+            })));
+        }
+
+        let before_source = self.purchase_edge_state(&family_id, &from_id).await?;
+        let before_dest = self.purchase_edge_state(&family_id, &to_id).await?;
+
+        let has_purchase = before_source
+            .as_ref()
+            .is_some_and(|e| !e["purchase_amount"].is_null());
+        if !has_purchase {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": if before_source.is_none() { "not_linked" } else { "no_purchase" },
+                "message": format!("No purchase recorded for {} at {}", last_name, from_event),
+                "source": before_source,
+            })));
+        }
+
+        let overwrites_purchase = before_dest
+            .as_ref()
+            .is_some_and(|e| !e["purchase_amount"].is_null());
+        if overwrites_purchase && !force {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "reason": "destination_has_purchase",
+                "message": format!(
+                    "{} already has a purchase recorded at {}; pass force: true to overwrite it",
+                    last_name, to_event
+                ),
+                "source": before_source,
+                "destination": before_dest,
+            })));
+        }
+
+        if dry_run {
+            return Ok(dry_run_result(
+                format!(
+                    "Would move purchase for {} from {} to {}",
+                    last_name, from_event, to_event
+                ),
+                serde_json::json!({
+                    "edge": "family_shoot",
+                    "family_id": family_id.to_string(),
+                    "from_shoot_id": from_id.to_string(),
+                    "to_shoot_id": to_id.to_string(),
+                    "creates_link": before_dest.is_none(),
+                    "overwrites_purchase": overwrites_purchase,
+                    "before": { "source": before_source, "destination": before_dest },
+                }),
+            ));
+        }
+
+        // Single transaction: copy amount/date to destination (linking if needed), then clear source
+        let move_query = r#"
+            BEGIN TRANSACTION;
+            LET $src = (SELECT purchase_amount, purchase_date FROM family_shoot
+                WHERE in = $family_id AND out = $from_id)[0];
+            IF array::len((SELECT id FROM family_shoot WHERE in = $family_id AND out = $to_id)) = 0 {
+                RELATE $family_id->family_shoot->$to_id
+                SET gallery_status = 'pending', created_at = time::now();
+            };
+            UPDATE family_shoot
+            SET gallery_status = 'purchased',
+                purchase_amount = $src.purchase_amount,
+                purchase_date = $src.purchase_date
+            WHERE in = $family_id AND out = $to_id;
+            UPDATE family_shoot
+            SET purchase_amount = NONE,
+                purchase_date = NONE,
+                gallery_status = IF gallery_status = 'purchased' { 'sent' } ELSE { gallery_status }
+            WHERE in = $family_id AND out = $from_id;
+            COMMIT TRANSACTION;
+        "#;
+
+        self.db
+            .query(move_query)
+            .bind(("family_id", family_id.clone()))
+            .bind(("from_id", from_id.clone()))
+            .bind(("to_id", to_id.clone()))
+            .await?
+            .check()?;
+
+        let after_source = self.purchase_edge_state(&family_id, &from_id).await?;
+        let after_dest = self.purchase_edge_state(&family_id, &to_id).await?;
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "message": format!("Moved purchase for {} from {} to {}", last_name, from_event, to_event),
+            "created_link": before_dest.is_none(),
+            "overwrote_purchase": overwrites_purchase,
+            "before": { "source": before_source, "destination": before_dest },
+            "after": { "source": after_source, "destination": after_dest },
+        })))
+    }
+
+    /// Purchase-related fields of a family_shoot edge, or None if the edge doesn't exist
+    async fn purchase_edge_state(
+        &self,
+        family_id: &surrealdb::sql::Thing,
+        shoot_id: &surrealdb::sql::Thing,
+    ) -> Result<Option<serde_json::Value>> {
+        let query = r#"
+            SELECT gallery_status, purchase_amount, purchase_date
+            FROM family_shoot WHERE in = $family_id AND out = $shoot_id LIMIT 1;
+        "#;
+
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("family_id", family_id.clone()))
+            .bind(("shoot_id", shoot_id.clone()))
+            .await?;
+
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct PurchaseEdge {
+            gallery_status: Option<String>,
+            purchase_amount: Option<f64>,
+            purchase_date: Option<String>,
+        }
+
+        let edges: Vec<PurchaseEdge> = result.take(0)?;
+        Ok(edges
+            .into_iter()
+            .next()
+            .map(|e| serde_json::to_value(e).unwrap_or_default()))
+    }
+
+    /// Add a tag to a family (by last name) or a shoot (by name)
+    pub async fn handle_add_tag(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        self.update_tags(req, true).await
+    }
+
+    /// Remove a tag from a family (by last name) or a shoot (by name)
+    pub async fn handle_remove_tag(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        self.update_tags(req, false).await
+    }
+
+    /// Look up a family by last name in the configured table. Under `name_slug` this is a
+    /// direct id lookup; under `uuid` it matches the `last_name` field (case-insensitive).
+    /// `fields` is the SELECT projection, e.g. `"VALUE id"` or `"*"`.
+    async fn query_family(&self, fields: &str, last_name: &str) -> Result<surrealdb::Response> {
+        let query = match self.cfg().family_id_strategy {
+            FamilyIdStrategy::NameSlug => {
+                format!("SELECT {fields} FROM type::thing($family_table, $family_key);")
+            }
+            FamilyIdStrategy::Uuid => format!(
+                "SELECT {fields} FROM type::table($family_table) \
+                 WHERE string::lowercase(last_name ?? '') = string::lowercase($last_name);"
+            ),
+        };
+
+        Ok(self
+            .db
+            .query(query)
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .bind(("family_key", family_slug(last_name)))
+            .bind(("last_name", last_name.trim().to_string()))
+            .await?)
+    }
+
+    /// Resolves a family from user input: the exact id lookup of `query_family`, then
+    /// "First Last" (surname plus a member skater's first name), then a partial match on
+    /// last name, display name, or id. More than one hit at any step is `Ambiguous`.
+    async fn resolve_family(&self, last_name: &str) -> Result<FamilyResolution> {
+        let fields = "id, name, last_name, delivery_email, delivery_emails, tags";
+        let mut families: Vec<FamilyMatch> = self.query_family(fields, last_name).await?.take(0)?;
+        let mut matched_by = "id";
+
+        // "First Last": the last token is the surname, the first picks among same-surname families
+        if families.is_empty()
+            && let Some((first_name, surname)) = split_first_last(last_name)
+        {
+            let first_last_query = format!(
+                r#"
+                SELECT {fields},
+                    (SELECT VALUE in.first_name FROM belongs_to WHERE out = $parent.id) as skater_first_names
+                FROM type::table($family_table)
+                WHERE id = type::thing($family_table, $surname_key)
+                OR string::lowercase(last_name ?? '') = string::lowercase($surname)
+                ORDER BY last_name;
+            "#
+            );
+
+            let same_surname: Vec<FamilyMatch> = self
+                .db
+                .query(first_last_query)
+                .bind(("family_table", self.cfg().family_table.clone()))
+                .bind(("surname_key", surname.to_lowercase()))
+                .bind(("surname", surname.to_string()))
+                .await?
+                .take(0)?;
+
+            let first = fold_name(first_name);
+            let (with_skater, others): (Vec<_>, Vec<_>) = same_surname
+                .into_iter()
+                .partition(|f| f.skater_first_names.iter().any(|n| fold_name(n) == first));
+            families = if with_skater.is_empty() {
+                others
+            } else {
+                with_skater
+            };
+            matched_by = "first_last";
+        }
+
+        // Fall back to a partial-name search when the exact id misses ("O'Brien", "Van Dyke")
+        if families.is_empty() {
+            let fuzzy_query = format!(
+                r#"
+                SELECT {fields} FROM type::table($family_table)
+                WHERE string::lowercase(last_name ?? '') CONTAINS string::lowercase($search)
+                OR string::lowercase(name ?? '') CONTAINS string::lowercase($search)
+                OR string::lowercase(<string> meta::id(id)) CONTAINS $slug
+                ORDER BY last_name
+                LIMIT 20;
+            "#
+            );
+
+            let slug = last_name.trim().to_lowercase().replace([' ', '-'], "_");
+            families = self
+                .db
+                .query(fuzzy_query)
+                .bind(("family_table", self.cfg().family_table.clone()))
+                .bind(("search", last_name.trim().to_string()))
+                .bind(("slug", slug))
+                .await?
+                .take(0)?;
+            matched_by = "fuzzy";
+        }
+
+        Ok(match families.len() {
+            0 => FamilyResolution::NotFound,
+            1 => FamilyResolution::Found(families.remove(0), matched_by),
+            _ => FamilyResolution::Ambiguous(families),
+        })
+    }
+
+    /// `candidates` entries for an ambiguous `resolve_family` result.
+    fn family_candidates(&self, families: &[FamilyMatch]) -> Vec<serde_json::Value> {
+        families
+            .iter()
+            .map(|f| {
+                serde_json::json!({
+                    "id": f.id.to_string(),
+                    "name": f.last_name.clone().or_else(|| f.name.clone()),
+                    "display_name": self.family_display_name(f.last_name.as_deref(), f.name.as_deref()),
+                    "email": f.delivery_email,
+                })
+            })
+            .collect()
+    }
+
+    /// Record key for a family upsert (`create_family`, `book_client`). Name-slug keys are
+    /// deterministic; under `uuid` an existing family with this last name (else this delivery
+    /// email) keeps its key, so repeat calls update it instead of adding a duplicate.
+    async fn upsert_family_key(&self, last_name: &str, email: &str) -> Result<String> {
+        if matches!(self.cfg().family_id_strategy, FamilyIdStrategy::NameSlug) {
+            return Ok(new_family_key(last_name, FamilyIdStrategy::NameSlug));
+        }
+        let query = r#"
+            SELECT VALUE <string> meta::id(id) FROM type::table($family_table)
+            WHERE string::lowercase(last_name ?? '') = string::lowercase($last_name)
+            ORDER BY created_at LIMIT 1;
+            SELECT VALUE <string> meta::id(id) FROM type::table($family_table)
+            WHERE string::lowercase(delivery_email ?? '') = string::lowercase($email)
+            ORDER BY created_at LIMIT 1;
+        "#;
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .bind(("last_name", last_name.to_string()))
+            .bind(("email", email.trim().to_string()))
+            .await?;
+        let by_name: Vec<String> = result.take(0)?;
+        let by_email: Vec<String> = result.take(1)?;
+        Ok(by_name
+            .into_iter()
+            .chain(by_email)
+            .next()
+            .unwrap_or_else(|| new_family_key(last_name, FamilyIdStrategy::Uuid)))
+    }
+
+    /// Family name as shown in tool output (see `PHOTO_FAMILY_NAME_TEMPLATE`).
+    fn family_display_name(&self, last_name: Option<&str>, name: Option<&str>) -> String {
+        family_display_name(last_name, name, self.cfg().family_name_template.as_deref())
+    }
+
+    /// A free slug for a new shoot derived from its name and date (`-2`, `-3`, ... on collision).
+    async fn next_shoot_slug(&self, name: &str, date: Option<&str>) -> Result<String> {
+        let base = shoot_slug(name, date);
+        let taken: Vec<String> = self
+            .db
+            .query("SELECT VALUE slug FROM shoot WHERE slug = $base OR string::starts_with(slug ?? '', $prefix);")
+            .bind(("base", base.clone()))
+            .bind(("prefix", format!("{}-", base)))
+            .await?
+            .take(0)?;
+        Ok(unique_slug(&base, &taken))
+    }
+
+    /// The optional `at` argument of a mutating tool, bound as `$now` for `NOW`. Only
+    /// accepted with `PHOTO_ALLOW_CLOCK_OVERRIDE=1` and as an RFC 3339 timestamp; anything
+    /// else is recorded as invalid.
+    fn clock_arg(&self, args: &mut Args) -> Option<String> {
+        let at = args.optional_str("at")?;
+        if !self.cfg().allow_clock_override {
+            args.invalid("at", "requires PHOTO_ALLOW_CLOCK_OVERRIDE=1");
+        } else if chrono::DateTime::parse_from_rfc3339(&at).is_err() {
+            args.invalid(
+                "at",
+                "must be an RFC 3339 timestamp such as 2025-11-02T18:00:00Z",
+            );
+        }
+        Some(at)
+    }
+
+    /// Surname to store for a new family and, when `PHOTO_NORMALIZE_NAME_CASE` changed it,
+    /// the raw input (kept as `last_name_raw`).
+    fn stored_surname(&self, raw: String) -> (String, Option<String>) {
+        let cfg = self.cfg();
+        if !cfg.normalize_name_case {
+            return (raw, None);
+        }
+        let normalized = normalize_surname(&raw, &cfg.name_case_overrides);
+        if normalized == raw {
+            (raw, None)
+        } else {
+            (normalized, Some(raw))
+        }
+    }
+
+    /// Runs one sync row's DB work, retrying a failure up to `PHOTO_SYNC_RETRIES` more times
+    /// with a backoff that starts at `PHOTO_SYNC_RETRY_BACKOFF_MS` and doubles. The last
+    /// error is returned so the caller can set the row aside and keep going.
+    async fn with_sync_retry<T, F, Fut>(&self, mut op: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let cfg = self.cfg();
+        let mut attempt = 0;
+        loop {
+            match op().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < cfg.sync_retries => {
+                    let delay_ms = cfg
+                        .sync_retry_backoff_ms
+                        .saturating_mul(1 << attempt.min(10));
+                    attempt += 1;
+                    tracing::warn!(attempt, delay_ms, error = %e, "sync row failed; retrying");
+                    tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// How a family lookup is described in not-found messages.
+    fn family_id_label(&self, last_name: &str) -> String {
+        match self.cfg().family_id_strategy {
+            FamilyIdStrategy::NameSlug => {
+                format_record_id(&self.cfg().family_table, &family_slug(last_name))
+            }
+            FamilyIdStrategy::Uuid => {
+                format!("{} with last_name = {}", self.cfg().family_table, last_name)
+            }
+        }
+    }
+
+    /// First competition whose name contains `name` (case-insensitive).
+    async fn find_competition(
+        &self,
+        name: &str,
+    ) -> Result<Option<crate::photography::models::Competition>> {
+        let query = "SELECT * FROM competition \
+                     WHERE string::lowercase(name ?? '') CONTAINS string::lowercase($comp) LIMIT 1;";
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("comp", name.to_string()))
+            .await?;
+        let competitions: Vec<crate::photography::models::Competition> = result.take(0)?;
+        Ok(competitions.into_iter().next())
+    }
+
+    /// Reads an import file, refusing it up front if it exceeds `PHOTO_MAX_IMPORT_BYTES`.
+    async fn read_import_file(&self, path: &str) -> Result<String> {
+        let metadata = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read file {}: {}", path, e))?;
+        check_import_size(path, metadata.len(), self.cfg().max_import_bytes)
+            .map_err(anyhow::Error::msg)?;
+        tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read file {}: {}", path, e))
+    }
+
+    /// Explicit `limit` argument (at least 1), else `PHOTO_DEFAULT_LIMIT`.
+    fn limit_arg(&self, args: &mut Args) -> usize {
+        limit_arg_or(args, self.cfg().default_limit)
+    }
+
+    /// (response key, table) pairs counted by `status` and `dashboard`.
+    /// `PHOTO_STATUS_TABLES` entries are keyed by their own name.
+    fn status_tables(&self) -> Vec<(String, String)> {
+        if let Some(tables) = &self.cfg().status_tables {
+            return tables.iter().map(|t| (t.clone(), t.clone())).collect();
+        }
+        [
+            ("family", self.cfg().family_table.as_str()),
+            ("skater", "skater"),
+            ("competition", "competition"),
+            ("event", "event"),
+            ("family_competition", "family_competition"),
+            ("shoot", "shoot"),
+            ("family_shoot", "family_shoot"),
+        ]
+        .into_iter()
+        .map(|(key, table)| (key.to_string(), table.to_string()))
+        .collect()
+    }
+
+    /// Pending-status set for a request: the `statuses` argument if given, else config.
+    fn pending_statuses(&self, req: &CallToolRequestParam) -> Vec<String> {
+        let from_arg: Vec<String> = match req.arguments.as_ref().and_then(|a| a.get("statuses")) {
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.trim().to_lowercase())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            Some(serde_json::Value::String(raw)) => crate::config::parse_status_list(raw),
+            _ => Vec::new(),
+        };
+
+        if from_arg.is_empty() {
+            self.cfg().pending_statuses.clone()
+        } else {
+            from_arg
+        }
+    }
+
+    /// Shared implementation for add_tag/remove_tag.
+    /// Tags are stored lowercase on `family.tags` / `shoot.tags`.
+    async fn update_tags(&self, req: CallToolRequestParam, add: bool) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let tag = normalize_tag(&args.required_str("tag"));
+        let last_name = args.optional_str("last_name");
+        let shoot_name = args.optional_str("shoot_name");
+        args.finish()?;
+
+        let (target_id, target_label) = match (last_name, shoot_name) {
+            (Some(last_name), None) => {
+                let family_id_str = self.family_id_label(&last_name);
+                let mut family_result = self.query_family("VALUE id", &last_name).await?;
+                let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
+
+                match family_ids.into_iter().next() {
+                    Some(id) => (id, last_name),
+                    None => {
+                        return Ok(CallToolResult::structured(serde_json::json!({
+                            "success": false,
+                            "message": format!("No family found with last name: {} (ID: {})", last_name, family_id_str)
+                        })));
+                    }
+                }
+            }
+            (None, Some(shoot_name)) => {
+                let shoot_query =
+                    format!("SELECT VALUE id FROM shoot WHERE {};", shoot_match("shoot"));
+                let mut shoot_result = self
+                    .db
+                    .query(shoot_query)
+                    .bind(("shoot", shoot_name.clone()))
+                    .await?;
+                let shoot_ids: Vec<surrealdb::sql::Thing> = shoot_result.take(0)?;
+
+                match shoot_ids.into_iter().next() {
+                    Some(id) => (id, shoot_name),
+                    None => {
+                        return Ok(CallToolResult::structured(serde_json::json!({
+                            "success": false,
+                            "message": format!("No shoot found matching: {}", shoot_name)
+                        })));
+                    }
+                }
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Provide exactly one of: last_name (family) or shoot_name (shoot)"
+                ));
+            }
+        };
+
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!(
+                    "Would {} tag '{}' {} {}",
+                    if add { "add" } else { "remove" },
+                    tag,
+                    if add { "to" } else { "from" },
+                    target_label
+                ),
+                serde_json::json!({
+                    "id": target_id.to_string(),
+                    "op": if add { "add" } else { "remove" },
+                    "tag": tag,
+                }),
+            ));
+        }
+
+        let update_query = if add {
+            "UPDATE $target SET tags = array::union(tags ?? [], [$tag]) RETURN tags;"
+        } else {
+            "UPDATE $target SET tags = array::complement(tags ?? [], [$tag]) RETURN tags;"
+        };
+
+        let mut result = self
+            .db
+            .query(update_query)
+            .bind(("target", target_id.clone()))
+            .bind(("tag", tag.clone()))
+            .await?;
+
+        #[derive(serde::Deserialize)]
+        struct TagsRow {
+            tags: Option<Vec<String>>,
+        }
+
+        let rows: Vec<TagsRow> = result.take(0)?;
+        let tags = rows
+            .into_iter()
+            .next()
+            .and_then(|r| r.tags)
+            .unwrap_or_default();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "message": format!(
+                "{} tag '{}' {} {}",
+                if add { "Added" } else { "Removed" },
+                tag,
+                if add { "to" } else { "from" },
+                target_label
+            ),
+            "id": target_id.to_string(),
+            "tags": tags,
+        })))
+    }
+
+    /// Summarize tags: family count and revenue attributed to tagged families
+    pub async fn handle_tag_summary(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let args = Args::new(&req);
+        let start_date = args.optional_str("start_date");
+        let end_date = args.optional_str("end_date");
+
+        #[derive(serde::Deserialize)]
+        struct TaggedFamily {
+            id: surrealdb::sql::Thing,
+            tags: Option<Vec<String>>,
+        }
+
+        let mut family_result = self
+            .db
+            .query(
+                "SELECT id, tags FROM type::table($family_table) WHERE array::len(tags ?? []) > 0;",
+            )
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .await?;
+        let families: Vec<TaggedFamily> = family_result.take(0)?;
+
+        // Revenue per family across both edge types, optionally limited by purchase_date
+        let mut conditions = vec!["purchase_amount IS NOT NONE".to_string()];
+        push_date_range(&mut conditions, "purchase_date", &start_date, &end_date);
+        let where_clause = conditions.join(" AND ");
+
+        #[derive(serde::Deserialize)]
+        struct FamilyRevenue {
+            #[serde(rename = "in")]
+            family: surrealdb::sql::Thing,
+            revenue: Option<f64>,
+        }
+
+        let mut revenue_by_family: std::collections::HashMap<String, f64> =
+            std::collections::HashMap::new();
+        for edge_table in ["family_shoot", "family_competition"] {
+            let query = format!(
+                "SELECT in, math::sum(purchase_amount) as revenue FROM {} WHERE {} GROUP BY in;",
+                edge_table, where_clause
+            );
+            let mut result = self
+                .db
+                .query(query)
+                .bind(("start_date", start_date.clone().unwrap_or_default()))
+                .bind(("end_date", end_date.clone().unwrap_or_default()))
+                .await?;
+            let rows: Vec<FamilyRevenue> = result.take(0).unwrap_or_default();
+            for row in rows {
+                *revenue_by_family
+                    .entry(row.family.to_string())
+                    .or_insert(0.0) += row.revenue.unwrap_or(0.0);
+            }
+        }
+
+        // Aggregate per tag
+        let mut by_tag: std::collections::BTreeMap<String, (i64, f64)> =
+            std::collections::BTreeMap::new();
+        for family in &families {
+            let revenue = revenue_by_family
+                .get(&family.id.to_string())
+                .copied()
+                .unwrap_or(0.0);
+            let mut tags = family.tags.clone().unwrap_or_default();
+            tags.sort();
+            tags.dedup();
+            for tag in tags {
+                let entry = by_tag.entry(tag).or_insert((0, 0.0));
+                entry.0 += 1;
+                entry.1 += revenue;
+            }
+        }
+
+        let mut tags: Vec<_> = by_tag
+            .into_iter()
+            .map(|(tag, (family_count, revenue))| (tag, family_count, revenue))
+            .collect();
+        tags.sort_by(|a, b| b.2.total_cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+
+        let tag_list: Vec<_> = tags
+            .iter()
+            .map(|(tag, family_count, revenue)| {
+                let average = if *family_count > 0 {
+                    revenue / *family_count as f64
+                } else {
+                    0.0
+                };
+                serde_json::json!({
+                    "tag": tag,
+                    "family_count": family_count,
+                    "total_revenue": revenue,
+                    "average_revenue": average,
+                })
+            })
+            .collect();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "start_date": start_date,
+            "end_date": end_date,
+            "tag_count": tag_list.len(),
+            "tags": tag_list,
+        })))
+    }
+
+    /// Who a family referred (and who those families referred, down to `depth` levels), with
+    /// each referral's own revenue and the downstream total
+    pub async fn handle_referral_tree(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        args.finish()?;
+        let depth = req
+            .arguments
+            .as_ref()
+            .and_then(|args| args.get("depth"))
+            .and_then(|v| v.as_u64())
+            .map(|d| d.clamp(1, MAX_REFERRAL_DEPTH as u64) as usize)
+            .unwrap_or(MAX_REFERRAL_DEPTH);
+
+        #[derive(serde::Deserialize)]
+        struct FamilyRow {
+            id: surrealdb::sql::Thing,
+            name: Option<String>,
+            last_name: Option<String>,
+            referred_by: Option<surrealdb::sql::Thing>,
+        }
+
+        let mut family_result = self
+            .query_family("id, name, last_name, referred_by", &last_name)
+            .await?;
+        let families: Vec<FamilyRow> = family_result.take(0)?;
+        let Some(root) = families.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": format!(
+                    "No family found with last name: {} (ID: {})",
+                    last_name,
+                    self.family_id_label(&last_name)
+                )
+            })));
+        };
+
+        // Every referral edge in one pass; the tree is assembled in memory
+        let referred: Vec<FamilyRow> = self
+            .db
+            .query(
+                "SELECT id, name, last_name, referred_by FROM type::table($family_table) \
+                 WHERE referred_by IS NOT NONE;",
+            )
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .await?
+            .take(0)?;
+        let mut children: std::collections::HashMap<String, Vec<(String, String)>> =
+            std::collections::HashMap::new();
+        for family in &referred {
+            if let Some(referrer) = &family.referred_by {
+                let label =
+                    self.family_display_name(family.last_name.as_deref(), family.name.as_deref());
+                children
+                    .entry(referrer.to_string())
+                    .or_default()
+                    .push((family.id.to_string(), label));
+            }
+        }
+
+        #[derive(serde::Deserialize)]
+        struct FamilyRevenue {
+            #[serde(rename = "in")]
+            family: surrealdb::sql::Thing,
+            revenue: Option<f64>,
+        }
+
+        let mut revenue_by_family: std::collections::HashMap<String, f64> =
+            std::collections::HashMap::new();
+        for (edge_table, _) in SALE_EDGES {
+            let query = format!(
+                "SELECT in, math::sum(purchase_amount) as revenue FROM {} \
+                 WHERE purchase_amount IS NOT NONE GROUP BY in;",
+                edge_table
+            );
+            let rows: Vec<FamilyRevenue> = self.db.query(query).await?.take(0)?;
+            for row in rows {
+                *revenue_by_family
+                    .entry(row.family.to_string())
+                    .or_insert(0.0) += row.revenue.unwrap_or(0.0);
+            }
+        }
+
+        let root_id = root.id.to_string();
+        let mut seen = std::collections::HashSet::from([root_id.clone()]);
+        let branch = referral_branch(&root_id, &children, &revenue_by_family, depth, &mut seen);
+        let direct = children.get(&root_id).map_or(0, Vec::len);
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "family": self.family_display_name(root.last_name.as_deref(), root.name.as_deref()),
+            "family_id": root_id,
+            "referred_by": root.referred_by.map(|id| id.to_string()),
+            "revenue": revenue_by_family.get(&root_id).copied().unwrap_or(0.0),
+            "depth": depth,
+            "direct_referrals": direct,
+            "total_referred": branch.count,
+            "downstream_revenue": branch.revenue,
+            "referrals": branch.nodes,
+        })))
+    }
+
+    /// Families ranked by how much they need attention, with the reasons behind each score
+    pub async fn handle_attention_list(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let arg_i64 = |key: &str| {
+            req.arguments
+                .as_ref()
+                .and_then(|args| args.get(key))
+                .and_then(|v| v.as_i64())
+        };
+        let thresholds = AttentionThresholds {
+            stale_days: arg_i64("stale_days").unwrap_or(14),
+            contact_days: arg_i64("contact_days").unwrap_or(60),
+        };
+        let mut args = Args::new(&req);
+        let limit = limit_arg_or(&mut args, 25).min(self.cfg().max_detail_items);
+        args.finish()?;
+
+        // Per-call weights override PHOTO_ATTENTION_WEIGHTS
+        let weights = match req.arguments.as_ref().and_then(|args| args.get("weights")) {
+            Some(serde_json::Value::Object(map)) => {
+                let raw: Vec<String> = map.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                self.cfg()
+                    .attention_weights
+                    .with_overrides(&raw.join(","))
+                    .map_err(|e| crate::args::InvalidParams {
+                        invalid: vec![("weights".to_string(), e.to_string())],
+                        ..Default::default()
+                    })?
+            }
+            _ => self.cfg().attention_weights,
+        };
+
+        #[derive(serde::Deserialize)]
+        struct FamilyRow {
+            id: surrealdb::sql::Thing,
+            last_name: Option<String>,
+            delivery_email: Option<String>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct EdgeRow {
+            family: surrealdb::sql::Thing,
+            event: Option<String>,
+            gallery_status: Option<String>,
+            age_days: Option<i64>,
+            sent_days: Option<i64>,
+            purchase_days: Option<i64>,
+            ty_days: Option<i64>,
+        }
 
-        // Use ID-based lookup for family (family:lastname_lowercase)
-        let family_id_str = format!("family:{}", last_name.to_lowercase().replace(' ', "_"));
-        let family_query = "SELECT VALUE id FROM type::thing($family_id);";
         let mut family_result = self
             .db
-            .query(family_query)
-            .bind(("family_id", family_id_str.clone()))
+            .query("SELECT id, last_name, delivery_email FROM type::table($family_table);")
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .await?;
+        let families: Vec<FamilyRow> = family_result.take(0)?;
+
+        let mut signals: std::collections::HashMap<String, FamilySignals> =
+            std::collections::HashMap::new();
+        for edge_table in ["family_shoot", "family_competition"] {
+            let query = format!(
+                "SELECT in AS family, out.name AS event, gallery_status, \
+                 duration::days(time::now() - created_at) AS age_days, \
+                 IF sent_date IS NOT NONE {{ duration::days(time::now() - sent_date) }} AS sent_days, \
+                 IF purchase_date IS NOT NONE {{ duration::days(time::now() - purchase_date) }} AS purchase_days, \
+                 IF ty_sent_date IS NOT NONE {{ duration::days(time::now() - ty_sent_date) }} AS ty_days \
+                 FROM {};",
+                edge_table
+            );
+            let mut result = self.db.query(query).await?;
+            let rows: Vec<EdgeRow> = result.take(0)?;
+            for row in rows {
+                let entry = signals.entry(row.family.to_string()).or_default();
+                entry.has_events = true;
+                let status = row.gallery_status.unwrap_or_default();
+                let event = row.event.unwrap_or_else(|| "Unknown".to_string());
+                if self.cfg().pending_statuses.contains(&status) {
+                    entry.pending.push((event, row.age_days.unwrap_or(0)));
+                } else if status == "sent" {
+                    entry.sent.push((event, row.sent_days.unwrap_or(0)));
+                }
+                for days in [row.sent_days, row.purchase_days, row.ty_days]
+                    .into_iter()
+                    .flatten()
+                {
+                    entry.last_contact_days =
+                        Some(entry.last_contact_days.map_or(days, |d| d.min(days)));
+                }
+            }
+        }
+
+        let mut ranked: Vec<_> = families
+            .into_iter()
+            .filter_map(|family| {
+                let mut family_signals = signals.remove(&family.id.to_string()).unwrap_or_default();
+                family_signals.missing_email = family
+                    .delivery_email
+                    .as_deref()
+                    .is_none_or(|e| e.trim().is_empty());
+                let (score, reasons) = attention_score(&family_signals, &weights, &thresholds);
+                (score > 0.0).then(|| (score, family, reasons))
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let total = ranked.len();
+        let list: Vec<_> = ranked
+            .into_iter()
+            .take(limit)
+            .map(|(score, family, reasons)| {
+                serde_json::json!({
+                    "family_id": family.id.to_string(),
+                    "last_name": family.last_name,
+                    "email": family.delivery_email,
+                    "score": (score * 100.0).round() / 100.0,
+                    "reasons": reasons,
+                })
+            })
+            .collect();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "weights": {
+                "stale_pending": weights.stale_pending,
+                "sent_not_purchased": weights.sent_not_purchased,
+                "missing_email": weights.missing_email,
+                "no_recent_contact": weights.no_recent_contact,
+            },
+            "stale_days": thresholds.stale_days,
+            "contact_days": thresholds.contact_days,
+            "total": total,
+            "families": list,
+        })))
+    }
+
+    /// Events with pending galleries, most urgent first: the event-level counterpart of
+    /// `attention_list`. Score = oldest pending age in days + number of pending galleries.
+    pub async fn handle_delivery_queue(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let statuses = self.pending_statuses(&req);
+        let mut args = Args::new(&req);
+        let limit = limit_arg_or(&mut args, 25).min(self.cfg().max_detail_items);
+        args.finish()?;
+
+        #[derive(serde::Deserialize)]
+        struct EventRow {
+            event_id: surrealdb::sql::Thing,
+            event: Option<String>,
+            event_date: Option<String>,
+            pending: i64,
+            oldest_days: Option<i64>,
+            undated: Option<i64>,
+        }
+
+        let mut queue = Vec::new();
+        for (edge_table, event_type) in SALE_EDGES {
+            // Edges without created_at count as pending but add no age
+            let query = format!(
+                "SELECT out AS event_id, out.name AS event, \
+                 out.shoot_date ?? out.date ?? out.start_date AS event_date, count() AS pending, \
+                 math::max(duration::days(time::now() - (created_at ?? time::now()))) AS oldest_days, \
+                 count(created_at IS NONE) AS undated \
+                 FROM {edge_table} WHERE gallery_status IN $statuses \
+                 GROUP BY event_id, event, event_date;"
+            );
+            let rows: Vec<EventRow> = self
+                .db
+                .query(query)
+                .bind(("statuses", statuses.clone()))
+                .await?
+                .take(0)?;
+            queue.extend(rows.into_iter().map(|row| (event_type, row)));
+        }
+
+        let score = |row: &EventRow| row.oldest_days.unwrap_or(0) + row.pending;
+        // Highest score first; ties go to the earlier event
+        queue.sort_by(|a, b| {
+            score(&b.1)
+                .cmp(&score(&a.1))
+                .then_with(|| a.1.event_date.cmp(&b.1.event_date))
+        });
+
+        let total_events = queue.len();
+        let total_pending: i64 = queue.iter().map(|(_, row)| row.pending).sum();
+        let events: Vec<_> = queue
+            .iter()
+            .take(limit)
+            .map(|(event_type, row)| {
+                serde_json::json!({
+                    "event_id": row.event_id.to_string(),
+                    "event": row.event,
+                    "event_type": event_type,
+                    "event_date": row.event_date,
+                    "pending": row.pending,
+                    "oldest_pending_days": row.oldest_days.unwrap_or(0),
+                    "undated_edges": row.undated.unwrap_or(0),
+                    "score": score(row),
+                })
+            })
+            .collect();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "statuses": statuses,
+            "total_events": total_events,
+            "total_pending": total_pending,
+            "count": events.len(),
+            "truncated": total_events > events.len(),
+            "events": events,
+        })))
+    }
+
+    /// Pre-send check for one event: which linked families can actually receive a gallery
+    /// (a delivery email plus a gallery url on the family or one of its skaters' edges)
+    pub async fn handle_delivery_checklist(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let shoot_name = args.optional_str("shoot_name");
+        let competition_name = args.optional_str("competition_name");
+        args.finish()?;
+
+        // Edge tables come from this fixed match, never from input
+        let (edge_table, target_table, skater_urls, target_name) =
+            match (shoot_name, competition_name) {
+                (Some(name), None) => (
+                    "family_shoot",
+                    "shoot",
+                    "->shot_in[WHERE out = $target]",
+                    name,
+                ),
+                (None, Some(name)) => (
+                    "family_competition",
+                    "competition",
+                    "->competed_in[WHERE out.competition = $target]",
+                    name,
+                ),
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "Provide exactly one of: shoot_name or competition_name"
+                    ));
+                }
+            };
+
+        // Shoots resolve slug-first like the other shoot tools; every match is fetched so an
+        // ambiguous name returns candidates instead of silently picking one
+        let target_match = if target_table == "shoot" {
+            shoot_match("name")
+        } else {
+            "string::lowercase(name ?? '') CONTAINS string::lowercase($name)".to_string()
+        };
+        let target_query =
+            format!("SELECT id, name FROM {target_table} WHERE {target_match} ORDER BY name, id;");
+
+        #[derive(serde::Deserialize)]
+        struct Target {
+            id: surrealdb::sql::Thing,
+            name: Option<String>,
+        }
+        let targets: Vec<Target> = self
+            .db
+            .query(target_query)
+            .bind(("name", target_name.clone()))
+            .await?
+            .take(0)?;
+        let target = match targets.as_slice() {
+            [] => {
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "success": false,
+                    "message": format!("No {} found matching: {}", target_table, target_name)
+                })));
+            }
+            [target] => target,
+            _ => {
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "success": false,
+                    "ambiguous": true,
+                    "message": format!(
+                        "{} {}s match '{}'; use a more specific name",
+                        targets.len(),
+                        target_table,
+                        target_name
+                    ),
+                    "candidates": targets
+                        .iter()
+                        .map(|t| serde_json::json!({ "id": t.id.to_string(), "name": t.name }))
+                        .collect::<Vec<_>>(),
+                })));
+            }
+        };
+
+        // Family-level ShootProof url first, then any url on a member skater's edge
+        let query = format!(
+            r#"
+            SELECT <string> in AS family_id, in.last_name AS family, in.delivery_email AS email,
+                {IN_DELIVERY_EMAILS} AS emails, gallery_status,
+                in.shootproof_url ?? array::first(array::compact(array::flatten(
+                    in<-belongs_to<-skater{skater_urls}.gallery_url
+                ))) AS gallery_url
+            FROM {edge_table}
+            WHERE out = $target
+            ORDER BY family
+        "#
+        );
+
+        #[derive(serde::Deserialize)]
+        struct Row {
+            family_id: String,
+            family: Option<String>,
+            email: Option<String>,
+            #[serde(
+                default,
+                deserialize_with = "crate::photography::utils::deserialize_delivery_emails"
+            )]
+            emails: Vec<String>,
+            gallery_status: Option<String>,
+            gallery_url: Option<String>,
+        }
+        let rows: Vec<Row> = self
+            .db
+            .query(query)
+            .bind(("target", target.id.clone()))
+            .await?
+            .take(0)?;
+
+        let mut ready = Vec::new();
+        let mut not_ready = Vec::new();
+        for row in rows {
+            let has_email = !row.emails.is_empty();
+            let has_gallery_url = row
+                .gallery_url
+                .as_deref()
+                .is_some_and(|u| !u.trim().is_empty());
+            let missing: Vec<&str> = [(!has_email, "email"), (!has_gallery_url, "gallery_url")]
+                .into_iter()
+                .filter_map(|(absent, field)| absent.then_some(field))
+                .collect();
+            let entry = serde_json::json!({
+                "family": row.family,
+                "family_id": row.family_id,
+                "gallery_status": row.gallery_status,
+                "email": row.email,
+                "emails": row.emails,
+                "gallery_url": row.gallery_url,
+                "has_email": has_email,
+                "has_gallery_url": has_gallery_url,
+                "missing": missing,
+            });
+            if missing.is_empty() {
+                ready.push(entry);
+            } else {
+                not_ready.push(entry);
+            }
+        }
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "success": true,
+            "message": format!(
+                "{} of {} families at {} ready to deliver",
+                ready.len(),
+                ready.len() + not_ready.len(),
+                target.name.as_deref().unwrap_or(&target_name)
+            ),
+            "event": &target.name,
+            "event_id": target.id.to_string(),
+            "event_type": target_table,
+            "total_families": ready.len() + not_ready.len(),
+            "ready_count": ready.len(),
+            "not_ready_count": not_ready.len(),
+            "ready": ready,
+            "not_ready": not_ready,
+        })))
+    }
+
+    /// Delivered-but-unpurchased galleries across shoots and competitions (warm leads)
+    pub async fn handle_sent_not_purchased(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let days = req
+            .arguments
+            .as_ref()
+            .and_then(|args| args.get("days"))
+            .and_then(|v| v.as_i64());
+
+        let mut conditions = vec!["gallery_status = 'sent'".to_string()];
+        if days.is_some() {
+            conditions.push(
+                "sent_date IS NOT NONE AND duration::days(time::now() - sent_date) >= $days"
+                    .to_string(),
+            );
+        }
+        let where_clause = conditions.join(" AND ");
+
+        #[derive(serde::Deserialize)]
+        struct SentEdge {
+            family: Option<String>,
+            email: Option<String>,
+            #[serde(
+                default,
+                deserialize_with = "crate::photography::utils::deserialize_delivery_emails"
+            )]
+            emails: Vec<String>,
+            event: Option<String>,
+            sent_date: Option<String>,
+            days_since_sent: Option<i64>,
+        }
+
+        let mut leads = Vec::new();
+        for (edge_table, event_type) in [
+            ("family_shoot", "shoot"),
+            ("family_competition", "competition"),
+        ] {
+            let query = format!(
+                "SELECT in.last_name as family, in.delivery_email as email, \
+                 {IN_DELIVERY_EMAILS} as emails, out.name as event, sent_date, \
+                 IF sent_date IS NOT NONE {{ duration::days(time::now() - sent_date) }} as days_since_sent \
+                 FROM {} WHERE {};",
+                edge_table, where_clause
+            );
+            let mut result = self
+                .db
+                .query(query)
+                .bind(("days", days.unwrap_or(0)))
+                .await?;
+            let rows: Vec<SentEdge> = result.take(0)?;
+            leads.extend(rows.into_iter().map(|r| (event_type, r)));
+        }
+
+        // Longest-waiting first; unknown sent dates last
+        leads.sort_by(|a, b| b.1.days_since_sent.cmp(&a.1.days_since_sent));
+
+        let lead_list: Vec<_> = leads
+            .iter()
+            .map(|(event_type, r)| {
+                serde_json::json!({
+                    "family": self.family_display_name(r.family.as_deref(), None),
+                    "email": r.email,
+                    "emails": r.emails,
+                    "event": r.event,
+                    "event_type": event_type,
+                    "sent_date": r.sent_date,
+                    "days_since_sent": r.days_since_sent,
+                })
+            })
+            .collect();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "days": days,
+            "count": lead_list.len(),
+            "families": lead_list,
+        })))
+    }
+
+    /// Delivered-but-unpaid shoot galleries priced from the shoot's packages, per family
+    pub async fn handle_outstanding_balances(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let args = Args::new(&req);
+        let package = args.optional_str("package");
+        args.finish()?;
+
+        #[derive(serde::Deserialize)]
+        struct UnpaidEdge {
+            family_id: surrealdb::sql::Thing,
+            family: Option<String>,
+            name: Option<String>,
+            shoot_id: surrealdb::sql::Thing,
+            shoot: Option<String>,
+            #[serde(default)]
+            packages: Vec<crate::photography::models::ShootPackage>,
+            package: Option<String>,
+            sent_date: Option<String>,
+        }
+
+        // Purchases are always positive, so an unset amount means nothing was recorded
+        let query = "SELECT in AS family_id, in.last_name AS family, in.name AS name, \
+                     out AS shoot_id, out.name AS shoot, out.packages ?? [] AS packages, \
+                     package, sent_date \
+                     FROM family_shoot \
+                     WHERE gallery_status = 'sent' AND purchase_amount IS NONE \
+                     AND array::len(out.packages ?? []) > 0;";
+        let edges: Vec<UnpaidEdge> = self.db.query(query).await?.take(0)?;
+
+        struct Balance {
+            family: String,
+            cents: i64,
+            galleries: Vec<serde_json::Value>,
+        }
+
+        let mut by_family: std::collections::BTreeMap<String, Balance> =
+            std::collections::BTreeMap::new();
+        for edge in &edges {
+            let Some((priced, source)) =
+                expected_package(&edge.packages, edge.package.as_deref(), package.as_deref())
+            else {
+                continue;
+            };
+            let balance = by_family
+                .entry(edge.family_id.to_string())
+                .or_insert_with(|| Balance {
+                    family: self.family_display_name(edge.family.as_deref(), edge.name.as_deref()),
+                    cents: 0,
+                    galleries: Vec::new(),
+                });
+            balance.cents += priced.price_cents;
+            balance.galleries.push(serde_json::json!({
+                "shoot_id": edge.shoot_id.to_string(),
+                "shoot": edge.shoot,
+                "sent_date": edge.sent_date,
+                "package": priced.name,
+                "price_source": source,
+                "outstanding": priced.price_cents as f64 / 100.0,
+            }));
+        }
+
+        // Largest balances first
+        let mut families: Vec<(String, Balance)> = by_family.into_iter().collect();
+        families.sort_by(|a, b| b.1.cents.cmp(&a.1.cents));
+        let total_cents: i64 = families.iter().map(|(_, b)| b.cents).sum();
+        let gallery_count: usize = families.iter().map(|(_, b)| b.galleries.len()).sum();
+        let family_list: Vec<_> = families
+            .into_iter()
+            .map(|(family_id, balance)| {
+                serde_json::json!({
+                    "family_id": family_id,
+                    "family": balance.family,
+                    "outstanding": balance.cents as f64 / 100.0,
+                    "galleries": balance.galleries,
+                })
+            })
+            .collect();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "package": package,
+            "count": family_list.len(),
+            "gallery_count": gallery_count,
+            "total_outstanding": total_cents as f64 / 100.0,
+            "families": family_list,
+        })))
+    }
+
+    /// Cross-check revenue: edge totals vs per-event totals, and purchase status vs amount
+    pub async fn handle_reconcile_revenue(
+        &self,
+        _req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        #[derive(serde::Deserialize)]
+        struct TotalRow {
+            total: Option<f64>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct EventTotal {
+            out: surrealdb::sql::Thing,
+            total: Option<f64>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct EventName {
+            id: surrealdb::sql::Thing,
+            name: String,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct SuspectEdge {
+            id: surrealdb::sql::Thing,
+            family: Option<String>,
+            event: Option<String>,
+            gallery_status: Option<String>,
+            purchase_amount: Option<f64>,
+        }
+
+        let mut anomalies = Vec::new();
+        let mut tables = Vec::new();
+        let mut grand_total = 0.0;
+
+        for (edge_table, event_type) in SALE_EDGES {
+            let query = format!(
+                "SELECT math::sum(purchase_amount ?? 0) AS total FROM {edge_table} GROUP ALL; \
+                 SELECT out, math::sum(purchase_amount ?? 0) AS total FROM {edge_table} GROUP BY out; \
+                 SELECT id, name FROM {event_type}; \
+                 SELECT id, in.last_name AS family, out.name AS event, gallery_status, purchase_amount \
+                 FROM {edge_table} \
+                 WHERE (gallery_status = 'purchased' AND (purchase_amount IS NONE OR purchase_amount = 0)) \
+                    OR (gallery_status != 'purchased' AND purchase_amount > 0);"
+            );
+            let mut result = self.db.query(query).await?;
+            let edge_total = result
+                .take::<Vec<TotalRow>>(0)?
+                .first()
+                .and_then(|r| r.total)
+                .unwrap_or(0.0);
+            let event_totals: Vec<EventTotal> = result.take(1)?;
+            let events: Vec<EventName> = result.take(2)?;
+            let suspects: Vec<SuspectEdge> = result.take(3)?;
+
+            let names: std::collections::HashMap<_, _> = events
+                .iter()
+                .map(|e| (e.id.clone(), e.name.clone()))
+                .collect();
+            let per_event: Vec<(Option<&String>, &EventTotal)> = event_totals
+                .iter()
+                .map(|t| (names.get(&t.out), t))
+                .collect();
+            let per_event_sum: f64 = per_event.iter().map(|(_, t)| t.total.unwrap_or(0.0)).sum();
+
+            for (name, t) in &per_event {
+                let total = t.total.unwrap_or(0.0);
+                match name {
+                    // Purchases on an edge whose event record is gone never show in status tools
+                    None if total != 0.0 => anomalies.push(serde_json::json!({
+                        "kind": "missing_event",
+                        "event_type": event_type,
+                        "event_id": t.out.to_string(),
+                        "amount": total,
+                    })),
+                    None => {}
+                    Some(name) => {
+                        // shoot_status/competition_status match by name substring, so
+                        // overlapping names make them report more than this event earned
+                        let needle = name.to_lowercase();
+                        let status_total: f64 = per_event
+                            .iter()
+                            .filter(|(other, _)| {
+                                other.is_some_and(|o| o.to_lowercase().contains(&needle))
+                            })
+                            .map(|(_, o)| o.total.unwrap_or(0.0))
+                            .sum();
+                        if (status_total - total).abs() >= 0.005 {
+                            anomalies.push(serde_json::json!({
+                                "kind": "status_total_mismatch",
+                                "event_type": event_type,
+                                "event_id": t.out.to_string(),
+                                "event": name,
+                                "edge_total": total,
+                                "status_tool_total": status_total,
+                                "message": "Event name is a substring of another event, so the status tool over-reports",
+                            }));
+                        }
+                    }
+                }
+            }
+
+            for edge in suspects {
+                let kind = if edge.gallery_status.as_deref() == Some("purchased") {
+                    "purchased_without_amount"
+                } else {
+                    "amount_without_purchase"
+                };
+                anomalies.push(serde_json::json!({
+                    "kind": kind,
+                    "event_type": event_type,
+                    "edge": edge.id.to_string(),
+                    "family": edge.family,
+                    "event": edge.event,
+                    "gallery_status": edge.gallery_status,
+                    "purchase_amount": edge.purchase_amount,
+                }));
+            }
+
+            if (edge_total - per_event_sum).abs() >= 0.005 {
+                anomalies.push(serde_json::json!({
+                    "kind": "grouping_mismatch",
+                    "event_type": event_type,
+                    "edge_total": edge_total,
+                    "per_event_total": per_event_sum,
+                }));
+            }
+
+            grand_total += edge_total;
+            tables.push(serde_json::json!({
+                "edge_table": edge_table,
+                "edge_total": edge_total,
+                "per_event_total": per_event_sum,
+                "event_count": per_event.len(),
+            }));
+        }
+
+        let anomaly_count = anomalies.len();
+        let (anomalies, omitted) = cap_details(anomalies, self.cfg().max_detail_items);
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "consistent": anomaly_count == 0,
+            "total_revenue": grand_total,
+            "tables": tables,
+            "anomaly_count": anomaly_count,
+            "anomalies": anomalies,
+            "truncated": omitted > 0,
+            "omitted_count": omitted,
+        })))
+    }
+
+    /// Monthly revenue across both edge types for charting: one entry per calendar month
+    /// between `start_date` and `end_date`, with quiet months filled in as zero.
+    pub async fn handle_revenue_trend(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let start_date = args.required_str("start_date");
+        let end_date = args.required_str("end_date");
+        let months = match calendar_months(&start_date, &end_date) {
+            Ok(months) => months,
+            Err(reason) => {
+                args.invalid("start_date", &reason);
+                Vec::new()
+            }
+        };
+        args.finish()?;
+
+        #[derive(serde::Deserialize)]
+        struct MonthRevenue {
+            month: String,
+            revenue: Option<f64>,
+            purchases: i64,
+        }
+
+        let where_clause = sales_where(&Some(start_date.clone()), &Some(end_date.clone()));
+        let mut by_month: std::collections::HashMap<String, (f64, i64)> =
+            std::collections::HashMap::new();
+        for (edge_table, _) in SALE_EDGES {
+            let query = format!(
+                "SELECT time::format(purchase_date, '%Y-%m') AS month, \
+                 math::sum(purchase_amount) AS revenue, count() AS purchases \
+                 FROM {edge_table} WHERE {where_clause} GROUP BY month;"
+            );
+            let rows: Vec<MonthRevenue> = self
+                .db
+                .query(query)
+                .bind(("start_date", start_date.clone()))
+                .bind(("end_date", end_date.clone()))
+                .await?
+                .take(0)?;
+            for row in rows {
+                let entry = by_month.entry(row.month).or_insert((0.0, 0));
+                entry.0 += row.revenue.unwrap_or(0.0);
+                entry.1 += row.purchases;
+            }
+        }
+
+        let series: Vec<_> = months
+            .iter()
+            .map(|month| {
+                let (revenue, purchases) = by_month.get(month).copied().unwrap_or((0.0, 0));
+                serde_json::json!({
+                    "month": month,
+                    "revenue": revenue,
+                    "purchases": purchases,
+                })
+            })
+            .collect();
+        let total_revenue: f64 = by_month.values().map(|(revenue, _)| revenue).sum();
+        let total_purchases: i64 = by_month.values().map(|(_, purchases)| purchases).sum();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "start_date": start_date,
+            "end_date": end_date,
+            "month_count": series.len(),
+            "months": series,
+            "total_revenue": total_revenue,
+            "total_purchases": total_purchases,
+        })))
+    }
+
+    /// Days from delivery (`sent_date`) to purchase (`purchase_date`) on purchased edges,
+    /// as median/average overall and per event type. Edges missing either date are excluded.
+    pub async fn handle_purchase_latency(
+        &self,
+        _req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        #[derive(serde::Deserialize)]
+        struct LatencyRow {
+            days: Option<f64>,
+        }
+
+        fn latency_stats(days: &[f64]) -> serde_json::Value {
+            let average = (!days.is_empty()).then(|| days.iter().sum::<f64>() / days.len() as f64);
+            serde_json::json!({
+                "count": days.len(),
+                "median_days": median(days).map(|d| (d * 10.0).round() / 10.0),
+                "average_days": average.map(|d| (d * 10.0).round() / 10.0),
+            })
+        }
+
+        let mut all_days = Vec::new();
+        let mut by_event_type = serde_json::Map::new();
+        let mut excluded = 0;
+        for (edge_table, event_type) in SALE_EDGES {
+            // Fractional days so same-day purchases don't all collapse to zero
+            let query = format!(
+                "SELECT IF sent_date IS NOT NONE AND purchase_date IS NOT NONE \
+                     {{ duration::secs(purchase_date - sent_date) / 86400.0 }} AS days \
+                 FROM {edge_table} WHERE gallery_status = 'purchased';"
+            );
+            let rows: Vec<LatencyRow> = self.db.query(query).await?.take(0)?;
+            let days: Vec<f64> = rows.iter().filter_map(|r| r.days).collect();
+            excluded += rows.len() - days.len();
+            by_event_type.insert(event_type.to_string(), latency_stats(&days));
+            all_days.extend(days);
+        }
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "overall": latency_stats(&all_days),
+            "by_event_type": by_event_type,
+            "excluded_missing_dates": excluded,
+        })))
+    }
+
+    /// Every distinct `gallery_status` value on the family edges with its count, flagging
+    /// values outside `GALLERY_STATUSES` (typos, legacy strings) before they are cleaned up.
+    pub async fn handle_distinct_statuses(
+        &self,
+        _req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        #[derive(serde::Deserialize)]
+        struct StatusCount {
+            gallery_status: String,
+            count: i64,
+        }
+
+        let query = r#"
+            SELECT gallery_status ?? '(none)' AS gallery_status, count() AS count
+            FROM family_shoot GROUP BY gallery_status;
+            SELECT gallery_status ?? '(none)' AS gallery_status, count() AS count
+            FROM family_competition GROUP BY gallery_status;
+        "#;
+        let mut result = self.db.query(query).await?;
+        let shoot_counts: Vec<StatusCount> = result.take(0)?;
+        let competition_counts: Vec<StatusCount> = result.take(1)?;
+
+        // status -> (family_shoot, family_competition)
+        let mut by_status: std::collections::BTreeMap<String, (i64, i64)> =
+            std::collections::BTreeMap::new();
+        for row in shoot_counts {
+            by_status.entry(row.gallery_status).or_default().0 += row.count;
+        }
+        for row in competition_counts {
+            by_status.entry(row.gallery_status).or_default().1 += row.count;
+        }
+
+        let statuses: Vec<_> = by_status
+            .iter()
+            .map(|(status, (shoots, competitions))| {
+                serde_json::json!({
+                    "status": status,
+                    "count": shoots + competitions,
+                    "family_shoot": shoots,
+                    "family_competition": competitions,
+                    "known": GALLERY_STATUSES.contains(&status.as_str()),
+                })
+            })
+            .collect();
+        let unknown: Vec<&String> = by_status
+            .keys()
+            .filter(|s| !GALLERY_STATUSES.contains(&s.as_str()))
+            .collect();
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "distinct_count": statuses.len(),
+            "statuses": statuses,
+            "unknown_statuses": unknown,
+        })))
+    }
+
+    /// Funnel view of the whole business: how many galleries sit in each `gallery_status`
+    /// across shoots and competitions combined, with pending/delivered/purchased totals.
+    pub async fn handle_pipeline_status(
+        &self,
+        _req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        #[derive(serde::Deserialize)]
+        struct StatusCount {
+            gallery_status: Option<String>,
+            count: i64,
+        }
+
+        let mut by_status: std::collections::BTreeMap<String, i64> =
+            std::collections::BTreeMap::new();
+        let mut by_event_type = serde_json::Map::new();
+        for (edge_table, event_type) in SALE_EDGES {
+            let query = format!(
+                "SELECT gallery_status, count() AS count FROM {edge_table} GROUP BY gallery_status;"
+            );
+            let rows: Vec<StatusCount> = self.db.query(query).await?.take(0)?;
+            let mut table_total = 0;
+            for row in rows {
+                let status = row.gallery_status.unwrap_or_else(|| "(none)".to_string());
+                *by_status.entry(status).or_default() += row.count;
+                table_total += row.count;
+            }
+            by_event_type.insert(event_type.to_string(), serde_json::json!(table_total));
+        }
+
+        // Every known status in workflow order, zeros included, then anything unexpected
+        let mut stages: Vec<_> = GALLERY_STATUSES
+            .iter()
+            .map(|status| {
+                serde_json::json!({
+                    "status": status,
+                    "count": by_status.get(*status).copied().unwrap_or(0),
+                })
+            })
+            .collect();
+        stages.extend(
+            by_status
+                .iter()
+                .filter(|(status, _)| !GALLERY_STATUSES.contains(&status.as_str()))
+                .map(|(status, count)| {
+                    serde_json::json!({ "status": status, "count": count, "known": false })
+                }),
+        );
+
+        let count_of = |status: &str| by_status.get(status).copied().unwrap_or(0);
+        let total: i64 = by_status.values().sum();
+        let pending: i64 = self
+            .cfg()
+            .pending_statuses
+            .iter()
+            .map(|s| count_of(s))
+            .sum();
+        let purchased = count_of("purchased");
+        let delivered = count_of("sent") + purchased;
+        let conversion_rate = (delivered > 0).then(|| purchased as f64 / delivered as f64);
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "total": total,
+            "stages": stages,
+            "by_event_type": by_event_type,
+            "totals": {
+                "pending": pending,
+                "delivered": delivered,
+                "purchased": purchased,
+                "conversion_rate": conversion_rate,
+            },
+        })))
+    }
+
+    /// Admin escape hatch: run a read-only SurrealQL query (`PHOTO_ENABLE_RAW_QUERY=1`).
+    /// Anything that could write is refused before it reaches the database.
+    pub async fn handle_raw_query(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let query = args.required_str("query");
+        let statements = match check_read_only_query(&query) {
+            Ok(count) => count,
+            Err(reason) => {
+                args.invalid("query", &reason);
+                0
+            }
+        };
+        args.finish()?;
+
+        tracing::info!(statements, "raw_query");
+        let mut response = self.db.query(query).await?;
+        let mut results = Vec::new();
+        for index in 0..statements {
+            let value: surrealdb::Value = response.take(index)?;
+            results.push(match value.into_inner().into_json() {
+                serde_json::Value::Array(rows) => {
+                    let count = rows.len();
+                    let (rows, omitted) = cap_details(rows, self.cfg().max_detail_items);
+                    serde_json::json!({
+                        "count": count,
+                        "rows": rows,
+                        "truncated": omitted > 0,
+                    })
+                }
+                other => serde_json::json!({ "value": other }),
+            });
+        }
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "statements": statements,
+            "results": results,
+        })))
+    }
+
+    /// Flat CSV of every recorded purchase (shoots and competitions) for bookkeeping
+    pub async fn handle_export_sales(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let start_date = args.optional_str("start_date");
+        let end_date = args.optional_str("end_date");
+        // optional_str drops non-strings, which would quietly widen the export to all dates
+        for key in ["start_date", "end_date"] {
+            if args.optional_value(key).is_some_and(|v| !v.is_string()) {
+                args.invalid(key, "must be a date string");
+            }
+        }
+        args.finish()?;
+
+        // Large exports go through the streaming HTTP route instead of one big message
+        let row_count = self.count_sales(&start_date, &end_date).await?;
+        if bool_arg(&req, "stream") || row_count > self.cfg().export_max_inline_rows {
+            let path = format!(
+                "{}{}",
+                self.cfg().base_path,
+                crate::export::sales_csv_path(&start_date, &end_date)
+            );
+            let message = if self.cfg().http_addr.is_some() {
+                "Fetch the CSV from path on the HTTP server (same bearer token as /mcp)"
+            } else {
+                "Too many rows to return inline; streaming exports need the HTTP transport (PHOTO_HTTP_ADDR)"
+            };
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "streamed": true,
+                "row_count": row_count,
+                "max_inline_rows": self.cfg().export_max_inline_rows,
+                "http_enabled": self.cfg().http_addr.is_some(),
+                "path": path,
+                "message": message,
+            })));
+        }
+
+        let mut sales = Vec::new();
+        for (edge_table, event_type) in SALE_EDGES {
+            sales.extend(
+                self.fetch_sales(edge_table, event_type, &start_date, &end_date, None)
+                    .await?,
+            );
+        }
+
+        sales.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.family.cmp(&b.family)));
+
+        let csv = sales_csv(&sales)?;
+        Ok(CallToolResult::success(vec![Content::text(csv)]))
+    }
+
+    /// One CSV for a studio status meeting: every family on every shoot and competition with
+    /// its gallery status and purchase amount, grouped by event with subtotals and a grand
+    /// total. `active_only` drops events with no gallery left in a pending status.
+    pub async fn handle_export_status_report(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let active_only = bool_arg(&req, "active_only");
+        let pending = self.pending_statuses(&req);
+
+        #[derive(serde::Deserialize)]
+        struct ReportEdge {
+            event_id: surrealdb::sql::Thing,
+            event: Option<String>,
+            family: Option<String>,
+            family_name: Option<String>,
+            gallery_status: Option<String>,
+            amount: Option<f64>,
+        }
+
+        let mut rows = Vec::new();
+        for (edge_table, event_type) in SALE_EDGES {
+            let query = format!(
+                "SELECT out AS event_id, out.name AS event, in.last_name AS family, \
+                 in.name AS family_name, gallery_status, purchase_amount AS amount \
+                 FROM {edge_table};"
+            );
+            let edges: Vec<ReportEdge> = self.db.query(query).await?.take(0)?;
+
+            let active_events: std::collections::HashSet<String> = edges
+                .iter()
+                .filter(|e| {
+                    e.gallery_status
+                        .as_ref()
+                        .is_some_and(|s| pending.contains(s))
+                })
+                .map(|e| e.event_id.to_string())
+                .collect();
+
+            rows.extend(
+                edges
+                    .into_iter()
+                    .filter(|e| !active_only || active_events.contains(&e.event_id.to_string()))
+                    .map(|e| StatusReportRow {
+                        event_type,
+                        event_id: e.event_id.to_string(),
+                        event: e.event.unwrap_or_else(|| e.event_id.to_string()),
+                        family: self
+                            .family_display_name(e.family.as_deref(), e.family_name.as_deref()),
+                        status: e.gallery_status.unwrap_or_default(),
+                        amount: e.amount,
+                    }),
+            );
+        }
+
+        // Name order for reading; the id keeps same-named events in separate groups
+        rows.sort_by(|a, b| {
+            a.event_type
+                .cmp(b.event_type)
+                .then_with(|| a.event.cmp(&b.event))
+                .then_with(|| a.event_id.cmp(&b.event_id))
+                .then_with(|| a.family.cmp(&b.family))
+        });
+
+        let csv = status_report_csv(&rows)?;
+        Ok(CallToolResult::success(vec![Content::text(csv)]))
+    }
+
+    /// Purchases from one edge table ordered by (date, edge id). `page` is (after, limit) for
+    /// keyset paging: only rows after the given (date, id) of the previous page's last row,
+    /// where an empty date stands for an undated purchase (those sort first).
+    pub(crate) async fn fetch_sales(
+        &self,
+        edge_table: &str,
+        event_type: &'static str,
+        start_date: &Option<String>,
+        end_date: &Option<String>,
+        page: Option<(Option<(&str, &str)>, usize)>,
+    ) -> Result<Vec<SaleRow>> {
+        #[derive(serde::Deserialize)]
+        struct SaleEdge {
+            id: surrealdb::sql::Thing,
+            date: Option<String>,
+            family: Option<String>,
+            event: Option<String>,
+            amount: Option<f64>,
+            email: Option<String>,
+        }
+
+        let mut where_clause = sales_where(start_date, end_date);
+        let (after, paging) = match page {
+            Some((after, limit)) => (after, format!(" LIMIT {}", limit)),
+            None => (None, String::new()),
+        };
+        match after {
+            Some(("", _)) => where_clause.push_str(
+                " AND (purchase_date IS NOT NONE \
+                 OR (purchase_date IS NONE AND id > type::thing($after_id)))",
+            ),
+            Some(_) => where_clause.push_str(
+                " AND (purchase_date > type::datetime($after_date) \
+                 OR (purchase_date = type::datetime($after_date) AND id > type::thing($after_id)))",
+            ),
+            None => {}
+        }
+        // id breaks ties so rows sharing a purchase_date can't repeat or vanish across pages
+        let query = format!(
+            "SELECT id, purchase_date as date, in.last_name as family, \
+             out.name as event, purchase_amount as amount, in.delivery_email as email \
+             FROM {} WHERE {} ORDER BY date ASC, id ASC{};",
+            edge_table, where_clause, paging
+        );
+        let (after_date, after_id) = after.unwrap_or_default();
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("start_date", start_date.clone().unwrap_or_default()))
+            .bind(("end_date", end_date.clone().unwrap_or_default()))
+            .bind(("after_date", after_date.to_string()))
+            .bind(("after_id", after_id.to_string()))
             .await?;
-        let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
+        let rows: Vec<SaleEdge> = result.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|r| SaleRow {
+                id: r.id.to_string(),
+                date: r.date.unwrap_or_default(),
+                family: r.family.unwrap_or_default(),
+                event: r.event.unwrap_or_default(),
+                event_type,
+                amount: r.amount.unwrap_or(0.0),
+                email: r.email.unwrap_or_default(),
+            })
+            .collect())
+    }
 
-        if family_ids.is_empty() {
-            return Ok(CallToolResult::structured(serde_json::json!({
-                "success": false,
-                "message": format!("No family found with last name: {} (ID: {})", last_name, family_id_str)
-            })));
+    /// Number of purchase rows `export_sales` would emit for the date range.
+    async fn count_sales(
+        &self,
+        start_date: &Option<String>,
+        end_date: &Option<String>,
+    ) -> Result<usize> {
+        #[derive(serde::Deserialize)]
+        struct CountResult {
+            count: usize,
         }
 
-        // Find shoot
-        let shoot_query = "SELECT VALUE id FROM shoot WHERE string::lowercase(name ?? '') CONTAINS string::lowercase($shoot);";
-        let mut shoot_result = self
+        let where_clause = sales_where(start_date, end_date);
+        let query: String = SALE_EDGES
+            .iter()
+            .map(|(edge_table, _)| {
+                format!(
+                    "SELECT count() FROM {} WHERE {} GROUP ALL;",
+                    edge_table, where_clause
+                )
+            })
+            .collect();
+        let mut result = self
             .db
-            .query(shoot_query)
-            .bind(("shoot", shoot_name.clone()))
+            .query(query)
+            .bind(("start_date", start_date.clone().unwrap_or_default()))
+            .bind(("end_date", end_date.clone().unwrap_or_default()))
             .await?;
-        let shoot_ids: Vec<surrealdb::sql::Thing> = shoot_result.take(0)?;
 
-        if shoot_ids.is_empty() {
+        let mut total = 0;
+        for idx in 0..SALE_EDGES.len() {
+            total += result
+                .take::<Vec<CountResult>>(idx)?
+                .first()
+                .map(|r| r.count)
+                .unwrap_or(0);
+        }
+        Ok(total)
+    }
+
+    /// Get contact info for a family by last name
+    pub async fn handle_get_contact(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        args.finish()?;
+
+        let family = match self.resolve_family(&last_name).await? {
+            FamilyResolution::Found(family, _) => family,
+            FamilyResolution::Ambiguous(candidates) => {
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "found": false,
+                    "ambiguous": true,
+                    "message": format!("{} families match '{}'; use one of the candidate names", candidates.len(), last_name),
+                    "candidates": self.family_candidates(&candidates),
+                })));
+            }
+            FamilyResolution::NotFound => {
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "found": false,
+                    "message": format!("No family found with last name: {} (ID: {})", last_name, self.family_id_label(&last_name))
+                })));
+            }
+        };
+        let display_name =
+            self.family_display_name(family.last_name.as_deref(), family.name.as_deref());
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "found": true,
+            "family_id": family.id.to_string(),
+            "family": display_name,
+            "email": family.delivery_email,
+            "emails": merge_delivery_emails(
+                family.delivery_email.as_deref(),
+                family.delivery_emails.as_deref().unwrap_or_default(),
+            ),
+        })))
+    }
+
+    /// Contacts for many families in one query; output follows input order
+    pub async fn handle_get_contacts_bulk(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_names: Vec<String> = match args.optional_value("last_names") {
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            Some(_) => {
+                args.invalid("last_names", "must be an array of strings");
+                Vec::new()
+            }
+            None => {
+                args.missing("last_names");
+                Vec::new()
+            }
+        };
+        args.finish()?;
+
+        #[derive(serde::Deserialize)]
+        struct ContactRow {
+            id: surrealdb::sql::Thing,
+            last_name: Option<String>,
+            delivery_email: Option<String>,
+            delivery_emails: Option<Vec<String>>,
+            phone: Option<String>,
+        }
+
+        const FIELDS: &str =
+            "id, last_name, delivery_email, delivery_emails, primary_contact.phone AS phone";
+        let rows: Vec<ContactRow> = match self.cfg().family_id_strategy {
+            FamilyIdStrategy::NameSlug => {
+                let ids: Vec<surrealdb::sql::Thing> = last_names
+                    .iter()
+                    .map(|n| {
+                        surrealdb::sql::Thing::from((
+                            self.cfg().family_table.as_str(),
+                            family_slug(n).as_str(),
+                        ))
+                    })
+                    .collect();
+                self.db
+                    .query(format!("SELECT {FIELDS} FROM $ids;"))
+                    .bind(("ids", ids))
+                    .await?
+                    .take(0)?
+            }
+            FamilyIdStrategy::Uuid => {
+                let lowered: Vec<String> = last_names.iter().map(|n| n.to_lowercase()).collect();
+                self.db
+                    .query(format!(
+                        "SELECT {FIELDS} FROM type::table($family_table) \
+                         WHERE string::lowercase(last_name ?? '') IN $names;"
+                    ))
+                    .bind(("family_table", self.cfg().family_table.clone()))
+                    .bind(("names", lowered))
+                    .await?
+                    .take(0)?
+            }
+        };
+
+        let mut contacts = Vec::new();
+        let mut not_found = Vec::new();
+        for name in &last_names {
+            let found = rows.iter().find(|r| match self.cfg().family_id_strategy {
+                FamilyIdStrategy::NameSlug => r.id.id.to_raw() == family_slug(name),
+                FamilyIdStrategy::Uuid => r
+                    .last_name
+                    .as_deref()
+                    .is_some_and(|l| l.eq_ignore_ascii_case(name)),
+            });
+            match found {
+                Some(row) => contacts.push(serde_json::json!({
+                    "last_name": name,
+                    "family_id": row.id.to_string(),
+                    "email": row.delivery_email,
+                    "emails": merge_delivery_emails(
+                        row.delivery_email.as_deref(),
+                        row.delivery_emails.as_deref().unwrap_or_default(),
+                    ),
+                    "phone": row.phone,
+                })),
+                None => not_found.push(name.clone()),
+            }
+        }
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "requested": last_names.len(),
+            "found": contacts.len(),
+            "contacts": contacts,
+            "not_found": not_found,
+        })))
+    }
+
+    /// Add a delivery address to a family (`make_primary` also sets `delivery_email`)
+    pub async fn handle_add_delivery_email(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        self.update_delivery_emails(req, true).await
+    }
+
+    /// Remove a delivery address; removing the primary promotes the next one
+    pub async fn handle_remove_delivery_email(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        self.update_delivery_emails(req, false).await
+    }
+
+    /// Shared implementation for add_delivery_email/remove_delivery_email. `delivery_emails`
+    /// is written as the full list, primary first.
+    async fn update_delivery_emails(
+        &self,
+        req: CallToolRequestParam,
+        add: bool,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        let raw_email = args.required_str("email");
+        let email = if add && !raw_email.is_empty() {
+            match validate_email(&raw_email) {
+                Ok(email) => email,
+                Err(reason) => {
+                    args.invalid("email", &reason);
+                    raw_email
+                }
+            }
+        } else {
+            raw_email
+        };
+        args.finish()?;
+
+        #[derive(serde::Deserialize)]
+        struct FamilyEmails {
+            id: surrealdb::sql::Thing,
+            delivery_email: Option<String>,
+            delivery_emails: Option<Vec<String>>,
+        }
+
+        let mut family_result = self
+            .query_family("id, delivery_email, delivery_emails", &last_name)
+            .await?;
+        let families: Vec<FamilyEmails> = family_result.take(0)?;
+        let Some(family) = families.into_iter().next() else {
             return Ok(CallToolResult::structured(serde_json::json!({
                 "success": false,
-                "message": format!("No shoot found matching: {}", shoot_name)
+                "message": format!("Family not found: {}", self.family_id_label(&last_name))
             })));
-        }
+        };
 
-        // Update family_shoot edge with purchase info
-        let update_query = r#"
-            UPDATE family_shoot
-            SET gallery_status = 'purchased', purchase_amount = $amount, purchase_date = time::now()
-            WHERE in = $family_id AND out = $shoot_id
-        "#;
+        let current = merge_delivery_emails(
+            family.delivery_email.as_deref(),
+            family.delivery_emails.as_deref().unwrap_or_default(),
+        );
+        let present = current.iter().any(|e| e.eq_ignore_ascii_case(&email));
+        let is_primary = current
+            .first()
+            .is_some_and(|e| e.eq_ignore_ascii_case(&email));
+        let make_primary = add && bool_arg(&req, "make_primary");
+
+        let unchanged = |message: String| {
+            Ok(CallToolResult::structured(serde_json::json!({
+                "success": true,
+                "changed": false,
+                "message": message,
+                "family_id": family.id.to_string(),
+                "emails": current,
+            })))
+        };
+
+        let emails: Vec<String> = if add {
+            if present && (!make_primary || is_primary) {
+                return unchanged(format!("{} is already a delivery email", email));
+            }
+            let others = current.iter().filter(|e| !e.eq_ignore_ascii_case(&email));
+            if make_primary || current.is_empty() {
+                std::iter::once(&email).chain(others).cloned().collect()
+            } else {
+                others.chain(std::iter::once(&email)).cloned().collect()
+            }
+        } else {
+            if !present {
+                return unchanged(format!("{} is not a delivery email", email));
+            }
+            if current.len() == 1 {
+                return Ok(CallToolResult::structured(serde_json::json!({
+                    "success": false,
+                    "message": format!(
+                        "{} is the only delivery email for {}; add another before removing it",
+                        email, family.id
+                    ),
+                })));
+            }
+            current
+                .iter()
+                .filter(|e| !e.eq_ignore_ascii_case(&email))
+                .cloned()
+                .collect()
+        };
+        let primary = emails[0].clone();
+        let (verb, done) = if add {
+            ("add", "Added")
+        } else {
+            ("remove", "Removed")
+        };
+
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!("Would {} {} for {}", verb, email, family.id),
+                serde_json::json!({
+                    "update": family.id.to_string(),
+                    "set": { "delivery_email": primary, "delivery_emails": emails },
+                }),
+            ));
+        }
 
         self.db
-            .query(update_query)
-            .bind(("family_id", family_ids[0].clone()))
-            .bind(("shoot_id", shoot_ids[0].clone()))
-            .bind(("amount", amount))
-            .await?;
+            .query("UPDATE $family SET delivery_email = $primary, delivery_emails = $emails RETURN NONE;")
+            .bind(("family", family.id.clone()))
+            .bind(("primary", primary.clone()))
+            .bind(("emails", emails.clone()))
+            .await?
+            .check()?;
 
         Ok(CallToolResult::structured(serde_json::json!({
             "success": true,
-            "message": format!("Recorded ${:.2} purchase for {} at {}", amount, last_name, shoot_name),
+            "changed": true,
+            "message": format!("{} {} for {}", done, email, family.id),
+            "family_id": family.id.to_string(),
+            "primary": primary,
+            "emails": emails,
         })))
     }
 
-    /// Get contact info for a family by last name
-    pub async fn handle_get_contact(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
-        let last_name = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("last_name"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: last_name"))?;
-
-        // Use ID-based lookup like CLI does (family:lastname_lowercase)
-        let family_id = format!("family:{}", last_name.to_lowercase().replace(' ', "_"));
+    /// Everything stored about one family as a single self-contained document (data-access
+    /// requests, client migration). `format: "text"` returns pretty-printed JSON as text.
+    pub async fn handle_export_family(&self, req: CallToolRequestParam) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let last_name = args.required_str("last_name");
+        let format = args.optional_str("format");
+        if let Some(f) = format.as_deref()
+            && f != "text"
+            && f != "structured"
+        {
+            args.invalid("format", "must be 'text' or 'structured'");
+        }
+        args.finish()?;
 
-        let query = "SELECT * FROM type::thing($family_id);";
+        let mut family_result = self.query_family("VALUE id", &last_name).await?;
+        let family_ids: Vec<surrealdb::sql::Thing> = family_result.take(0)?;
+        let Some(family_id) = family_ids.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "found": false,
+                "message": format!(
+                    "No family found with last name: {} (ID: {})",
+                    last_name,
+                    self.family_id_label(&last_name)
+                ),
+            })));
+        };
 
-        let mut result = self
-            .db
-            .query(query)
-            .bind(("family_id", family_id.clone()))
-            .await?;
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct ExportContact {
+            first_name: Option<String>,
+            last_name: Option<String>,
+            email: Option<String>,
+            phone: Option<String>,
+        }
 
         #[derive(serde::Deserialize)]
-        struct FamilyRecord {
-            id: surrealdb::sql::Thing,
+        struct ExportFamily {
             name: Option<String>,
             last_name: Option<String>,
             delivery_email: Option<String>,
+            delivery_emails: Option<Vec<String>>,
+            notes: Option<String>,
+            tags: Option<Vec<String>>,
+            primary_contact: Option<ExportContact>,
+        }
+
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct ExportSkater {
+            id: String,
+            first_name: Option<String>,
+            last_name: Option<String>,
+            birth_date: Option<String>,
+            notes: Option<String>,
+            created_at: Option<String>,
         }
 
-        let families: Vec<FamilyRecord> = result.take(0)?;
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct ExportResend {
+            at: Option<String>,
+            reason: Option<String>,
+            prior_status: Option<String>,
+            prior_sent_date: Option<String>,
+        }
 
-        if families.is_empty() {
-            return Ok(CallToolResult::structured(serde_json::json!({
-                "found": false,
-                "message": format!("No family found with last name: {} (ID: {})", last_name, family_id)
-            })));
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct ExportEdge {
+            id: String,
+            event_id: String,
+            event_name: Option<String>,
+            event_date: Option<String>,
+            gallery_status: Option<String>,
+            sent_date: Option<String>,
+            request_status: Option<String>,
+            ty_requested: Option<bool>,
+            ty_sent: Option<bool>,
+            ty_sent_date: Option<String>,
+            purchase_amount: Option<f64>,
+            purchase_date: Option<String>,
+            package: Option<String>,
+            resends: Option<Vec<ExportResend>>,
+            created_at: Option<String>,
         }
 
-        let family = &families[0];
-        let display_name = family
-            .last_name
-            .clone()
-            .or_else(|| family.name.clone())
-            .unwrap_or_else(|| last_name.clone());
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct ExportNote {
+            id: String,
+            text: String,
+            author: Option<String>,
+            created_at: Option<String>,
+        }
 
-        Ok(CallToolResult::structured(serde_json::json!({
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct ExportEntry {
+            id: String,
+            skater_id: String,
+            competition: Option<String>,
+            event_number: Option<i64>,
+            level: Option<String>,
+            discipline: Option<String>,
+            request_status: Option<String>,
+            gallery_status: Option<String>,
+            gallery_url: Option<String>,
+            gallery_sent_at: Option<String>,
+            purchase_amount: Option<f64>,
+            purchase_date: Option<String>,
+            notes: Option<String>,
+        }
+
+        const EDGE_FIELDS: &str = "<string> id AS id, <string> out AS event_id, out.name AS event_name, \
+             gallery_status, sent_date, request_status, ty_requested, ty_sent, ty_sent_date, \
+             purchase_amount, purchase_date, package, resends, created_at";
+        let query = format!(
+            r#"
+            SELECT name, last_name, delivery_email, delivery_emails, notes, tags,
+                primary_contact.{{first_name, last_name, email, phone}} AS primary_contact
+            FROM $family;
+            SELECT <string> in AS id, in.first_name AS first_name, in.last_name AS last_name,
+                in.birth_date AS birth_date, in.notes AS notes, in.created_at AS created_at
+            FROM belongs_to WHERE out = $family ORDER BY first_name;
+            SELECT <string> id AS id, <string> in AS skater_id, out.competition.name AS competition,
+                out.event_number AS event_number, out.level AS level, out.discipline AS discipline,
+                request_status, gallery_status, gallery_url, gallery_sent_at,
+                purchase_amount, purchase_date, notes
+            FROM competed_in WHERE in IN (SELECT VALUE in FROM belongs_to WHERE out = $family)
+            ORDER BY competition, event_number;
+            SELECT {EDGE_FIELDS}, out.shoot_date AS event_date
+            FROM family_shoot WHERE in = $family ORDER BY event_date;
+            SELECT {EDGE_FIELDS}, out.date ?? out.start_date AS event_date
+            FROM family_competition WHERE in = $family ORDER BY event_date;
+            RETURN <string> time::now();
+            SELECT <string> id AS id, text, author, created_at FROM note
+            WHERE family = $family ORDER BY created_at;
+        "#
+        );
+
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("family", family_id.clone()))
+            .await?;
+        let family: Option<ExportFamily> = result.take::<Vec<ExportFamily>>(0)?.into_iter().next();
+        let skaters: Vec<ExportSkater> = result.take(1)?;
+        let entries: Vec<ExportEntry> = result.take(2)?;
+        let shoots: Vec<ExportEdge> = result.take(3)?;
+        let competitions: Vec<ExportEdge> = result.take(4)?;
+        let exported_at: Option<String> = result.take(5)?;
+        let notes_log: Vec<ExportNote> = result.take(6)?;
+
+        let Some(family) = family else {
+            anyhow::bail!("Family {} disappeared during export", family_id);
+        };
+
+        let purchase_total: f64 = shoots
+            .iter()
+            .chain(competitions.iter())
+            .filter_map(|e| e.purchase_amount)
+            .sum();
+
+        let document = serde_json::json!({
             "found": true,
-            "family_id": family.id.to_string(),
-            "family": display_name,
-            "email": family.delivery_email,
-        })))
+            "exported_at": exported_at,
+            "family": {
+                "id": family_id.to_string(),
+                "name": family.name,
+                "last_name": family.last_name,
+                "display_name": self.family_display_name(
+                    family.last_name.as_deref(),
+                    family.name.as_deref(),
+                ),
+                "delivery_email": family.delivery_email,
+                "delivery_emails": family.delivery_emails.unwrap_or_default(),
+                "notes": family.notes,
+                "tags": family.tags.unwrap_or_default(),
+                "primary_contact": family.primary_contact,
+            },
+            "skaters": skaters,
+            "competition_entries": entries,
+            "shoots": shoots,
+            "competitions": competitions,
+            "notes_log": notes_log,
+            "purchase_total": purchase_total,
+        });
+
+        if format.as_deref() == Some("text") {
+            return Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&document)?,
+            )]));
+        }
+        Ok(CallToolResult::structured(document))
     }
 
     /// Sync ShootProof galleries - match gallery names to family records
     pub async fn handle_sync_shootproof_galleries(
         &self,
         req: CallToolRequestParam,
+        ct: &CancellationToken,
     ) -> Result<CallToolResult> {
-        let json_path = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("json_path"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: json_path"))?;
+        let mut args = Args::new(&req);
+        let json_path = args.required_str("json_path");
+        args.finish()?;
 
         let dry_run = req
             .arguments
@@ -1179,10 +7390,7 @@ impl PhotoMindServer {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        // Read the JSON file
-        let content = tokio::fs::read_to_string(&json_path)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to read file {}: {}", json_path, e))?;
+        let content = self.read_import_file(&json_path).await?;
 
         let data: serde_json::Value = serde_json::from_str(&content)
             .map_err(|e| anyhow::anyhow!("Failed to parse JSON: {}", e))?;
@@ -1191,11 +7399,34 @@ impl PhotoMindServer {
             .as_array()
             .ok_or_else(|| anyhow::anyhow!("Expected 'galleries' array in JSON"))?;
 
+        #[derive(serde::Deserialize)]
+        struct FamilyCheck {
+            id: surrealdb::sql::Thing,
+            _name: Option<String>,
+            shootproof_gallery_id: Option<i64>,
+        }
+
         let mut matched = Vec::new();
         let mut unmatched = Vec::new();
+        let mut failed = Vec::new();
+        let mut failed_galleries = Vec::new();
         let mut updated = 0;
 
+        // Stop writing as soon as the client cancels or disconnects
+        let mut processed = 0;
+        let mut cancelled = false;
         for gallery in galleries {
+            if ct.is_cancelled() {
+                cancelled = true;
+                tracing::warn!(
+                    processed,
+                    total = galleries.len(),
+                    "sync_shootproof_galleries cancelled"
+                );
+                break;
+            }
+            processed += 1;
+
             let sp_id = gallery["id"].as_i64().unwrap_or(0);
             let name = gallery["name"].as_str().unwrap_or("").to_string();
             let url = gallery["url"].as_str().unwrap_or("").to_string();
@@ -1206,65 +7437,274 @@ impl PhotoMindServer {
                 .last()
                 .unwrap_or(&name)
                 .to_lowercase();
-            let family_id_str = format!("family:{}", last_name.replace(' ', "_"));
+            let family_id_str = self.family_id_label(&last_name);
+
+            // Lookup and update are retried together; both are safe to repeat
+            let (last_name_ref, url_ref) = (&last_name, &url);
+            let outcome = self
+                .with_sync_retry(move || async move {
+                    let mut result = self
+                        .query_family("id, name, shootproof_gallery_id", last_name_ref)
+                        .await?;
+                    let families: Vec<FamilyCheck> = result.take(0).unwrap_or_default();
+                    let Some(family) = families.into_iter().next() else {
+                        return Ok(None);
+                    };
+                    if !dry_run && family.shootproof_gallery_id.is_none() {
+                        // Update the family just found; its id holds under either id strategy
+                        let update_query = "UPDATE $family SET shootproof_gallery_id = $sp_id, shootproof_url = $url;";
+                        self.db
+                            .query(update_query)
+                            .bind(("family", family.id.clone()))
+                            .bind(("sp_id", sp_id))
+                            .bind(("url", url_ref.clone()))
+                            .await?
+                            .check()?;
+                    }
+                    Ok::<_, anyhow::Error>(Some(family))
+                })
+                .await;
+
+            match outcome {
+                Ok(Some(family)) => {
+                    if !dry_run && family.shootproof_gallery_id.is_none() {
+                        updated += 1;
+                    }
+                    matched.push(serde_json::json!({
+                        "gallery_name": name,
+                        "gallery_id": sp_id,
+                        "family_id": family.id.to_string(),
+                        "family_name": family._name,
+                        "existing_sp_id": family.shootproof_gallery_id,
+                        "url": url,
+                    }));
+                }
+                Ok(None) => unmatched.push(serde_json::json!({
+                    "gallery_name": name,
+                    "gallery_id": sp_id,
+                    "attempted_family_id": family_id_str,
+                })),
+                Err(e) => {
+                    tracing::error!(gallery = %name, error = %e, "sync_shootproof_galleries row failed");
+                    failed.push(serde_json::json!({
+                        "index": processed - 1,
+                        "gallery_name": name,
+                        "gallery_id": sp_id,
+                        "error": e.to_string(),
+                    }));
+                    failed_galleries.push(gallery.clone());
+                }
+            }
+        }
+
+        let matched_count = matched.len();
+        let unmatched_count = unmatched.len();
+        let (matched, matched_omitted) = cap_details(matched, self.cfg().max_detail_items);
+        let (unmatched, unmatched_omitted) = cap_details(unmatched, self.cfg().max_detail_items);
+        let failed_count = failed.len();
+        let (failed, failed_omitted) = cap_details(failed, self.cfg().max_detail_items);
+        let omitted = matched_omitted + unmatched_omitted + failed_omitted;
+
+        Ok(CallToolResult::structured(serde_json::json!({
+            "dry_run": dry_run,
+            "cancelled": cancelled,
+            "processed": processed,
+            "total_galleries": galleries.len(),
+            "matched": matched_count,
+            "unmatched": unmatched_count,
+            "failed": failed_count,
+            "updated": updated,
+            "matched_details": matched,
+            "unmatched_details": unmatched,
+            "failed_details": failed,
+            // Uncapped, in the input shape: save as {"galleries": [...]} to re-run them
+            "failed_galleries": failed_galleries,
+            "truncated": omitted > 0,
+            "omitted_count": omitted,
+        })))
+    }
+
+    /// Look a family up by `shootproof_gallery_id` (orders that only carry the gallery id)
+    pub async fn handle_find_family_by_sp_gallery(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let sp_id = args.required_i64("gallery_id");
+        args.finish()?;
+
+        #[derive(serde::Deserialize)]
+        struct GalleryFamily {
+            id: surrealdb::sql::Thing,
+            name: Option<String>,
+            last_name: Option<String>,
+            delivery_email: Option<String>,
+            delivery_emails: Option<Vec<String>>,
+            shootproof_url: Option<String>,
+        }
+
+        let mut result = self
+            .db
+            .query(
+                "SELECT id, name, last_name, delivery_email, delivery_emails, shootproof_url \
+                 FROM type::table($family_table) WHERE shootproof_gallery_id = $sp_id ORDER BY id;",
+            )
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .bind(("sp_id", sp_id))
+            .await?;
+        let families: Vec<GalleryFamily> = result.take(0)?;
+
+        let family_json = |f: &GalleryFamily| {
+            serde_json::json!({
+                "family_id": f.id.to_string(),
+                "name": self.family_display_name(f.last_name.as_deref(), f.name.as_deref()),
+                "last_name": f.last_name,
+                "email": f.delivery_email,
+                "emails": merge_delivery_emails(
+                    f.delivery_email.as_deref(),
+                    f.delivery_emails.as_deref().unwrap_or_default(),
+                ),
+                "shootproof_url": f.shootproof_url,
+            })
+        };
+
+        match families.as_slice() {
+            [] => Ok(CallToolResult::structured(serde_json::json!({
+                "found": false,
+                "gallery_id": sp_id,
+                "message": format!("No family has ShootProof gallery {}", sp_id),
+            }))),
+            [family] => Ok(CallToolResult::structured(serde_json::json!({
+                "found": true,
+                "gallery_id": sp_id,
+                "family": family_json(family),
+            }))),
+            // A gallery should belong to one family; reassign_gallery fixes duplicates
+            _ => Ok(CallToolResult::structured(serde_json::json!({
+                "found": false,
+                "ambiguous": true,
+                "gallery_id": sp_id,
+                "message": format!(
+                    "{} families share ShootProof gallery {}; use reassign_gallery to pick one",
+                    families.len(),
+                    sp_id
+                ),
+                "candidates": families.iter().map(family_json).collect::<Vec<_>>(),
+            }))),
+        }
+    }
+
+    /// Move a ShootProof gallery assignment to another family (fixes shared-surname mismatches)
+    pub async fn handle_reassign_gallery(
+        &self,
+        req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        let mut args = Args::new(&req);
+        let sp_id = args.required_i64("gallery_id");
+        let last_name = args.required_str("last_name");
+        let url_arg = args.optional_str("url");
+        args.finish()?;
+
+        #[derive(serde::Deserialize)]
+        struct GalleryHolder {
+            id: surrealdb::sql::Thing,
+            last_name: Option<String>,
+            shootproof_gallery_id: Option<i64>,
+            shootproof_url: Option<String>,
+        }
+
+        let mut target_result = self
+            .query_family(
+                "id, last_name, shootproof_gallery_id, shootproof_url",
+                &last_name,
+            )
+            .await?;
+        let targets: Vec<GalleryHolder> = target_result.take(0)?;
+        let Some(target) = targets.into_iter().next() else {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": false,
+                "message": format!("Family not found: {}", self.family_id_label(&last_name))
+            })));
+        };
+
+        let mut holder_result = self
+            .db
+            .query(
+                "SELECT id, last_name, shootproof_gallery_id, shootproof_url \
+                 FROM type::table($family_table) WHERE shootproof_gallery_id = $sp_id;",
+            )
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .bind(("sp_id", sp_id))
+            .await?;
+        let holders: Vec<GalleryHolder> = holder_result.take(0)?;
+        let previous: Vec<&GalleryHolder> = holders.iter().filter(|h| h.id != target.id).collect();
+
+        if previous.is_empty() && target.shootproof_gallery_id == Some(sp_id) {
+            return Ok(CallToolResult::structured(serde_json::json!({
+                "success": true,
+                "changed": false,
+                "message": format!("Gallery {} is already assigned to {}", sp_id, target.id),
+                "new_family": target.id.to_string(),
+            })));
+        }
 
-            // Check if family exists
-            let family_query =
-                "SELECT id, name, shootproof_gallery_id FROM type::thing($family_id);";
-            let mut result = self
-                .db
-                .query(family_query)
-                .bind(("family_id", family_id_str.clone()))
-                .await?;
+        // Keep the existing gallery URL unless a new one was supplied
+        let url = url_arg.or_else(|| holders.iter().find_map(|h| h.shootproof_url.clone()));
 
-            #[derive(serde::Deserialize)]
-            struct FamilyCheck {
-                id: surrealdb::sql::Thing,
-                _name: Option<String>,
-                shootproof_gallery_id: Option<i64>,
-            }
+        let old_families: Vec<_> = previous
+            .iter()
+            .map(|h| {
+                serde_json::json!({
+                    "family_id": h.id.to_string(),
+                    "last_name": h.last_name,
+                })
+            })
+            .collect();
+        let new_family = serde_json::json!({
+            "family_id": target.id.to_string(),
+            "last_name": target.last_name,
+            "replaced_gallery_id": target.shootproof_gallery_id.filter(|id| *id != sp_id),
+        });
 
-            let families: Vec<FamilyCheck> = result.take(0).unwrap_or_default();
+        if bool_arg(&req, "dry_run") {
+            return Ok(dry_run_result(
+                format!("Would assign gallery {} to {}", sp_id, target.id),
+                serde_json::json!({
+                    "clear": old_families,
+                    "set": {
+                        "family_id": target.id.to_string(),
+                        "shootproof_gallery_id": sp_id,
+                        "shootproof_url": url,
+                    },
+                }),
+            ));
+        }
 
-            if !families.is_empty() {
-                let family = &families[0];
-                matched.push(serde_json::json!({
-                    "gallery_name": name,
-                    "gallery_id": sp_id,
-                    "family_id": family.id.to_string(),
-                    "family_name": family._name,
-                    "existing_sp_id": family.shootproof_gallery_id,
-                    "url": url,
-                }));
+        let reassign_query = r#"
+            BEGIN TRANSACTION;
+            UPDATE type::table($family_table) SET shootproof_gallery_id = NONE, shootproof_url = NONE
+                WHERE shootproof_gallery_id = $sp_id RETURN NONE;
+            UPDATE $target SET shootproof_gallery_id = $sp_id, shootproof_url = $url RETURN NONE;
+            COMMIT TRANSACTION;
+        "#;
 
-                if !dry_run && family.shootproof_gallery_id.is_none() {
-                    // Update family with ShootProof gallery ID
-                    let update_query = "UPDATE type::thing($family_id) SET shootproof_gallery_id = $sp_id, shootproof_url = $url;";
-                    self.db
-                        .query(update_query)
-                        .bind(("family_id", family_id_str))
-                        .bind(("sp_id", sp_id))
-                        .bind(("url", url))
-                        .await?;
-                    updated += 1;
-                }
-            } else {
-                unmatched.push(serde_json::json!({
-                    "gallery_name": name,
-                    "gallery_id": sp_id,
-                    "attempted_family_id": family_id_str,
-                }));
-            }
-        }
+        self.db
+            .query(reassign_query)
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .bind(("sp_id", sp_id))
+            .bind(("target", target.id.clone()))
+            .bind(("url", url.clone()))
+            .await?
+            .check()?;
 
         Ok(CallToolResult::structured(serde_json::json!({
-            "dry_run": dry_run,
-            "total_galleries": galleries.len(),
-            "matched": matched.len(),
-            "unmatched": unmatched.len(),
-            "updated": updated,
-            "matched_details": matched,
-            "unmatched_details": unmatched,
+            "success": true,
+            "changed": true,
+            "message": format!("Assigned gallery {} to {}", sp_id, target.id),
+            "gallery_id": sp_id,
+            "url": url,
+            "old_families": old_families,
+            "new_family": new_family,
         })))
     }
 
@@ -1272,14 +7712,11 @@ impl PhotoMindServer {
     pub async fn handle_sync_shootproof_orders(
         &self,
         req: CallToolRequestParam,
+        ct: &CancellationToken,
     ) -> Result<CallToolResult> {
-        let json_path = req
-            .arguments
-            .as_ref()
-            .and_then(|args| args.get("json_path"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: json_path"))?;
+        let mut args = Args::new(&req);
+        let json_path = args.required_str("json_path");
+        args.finish()?;
 
         let dry_run = req
             .arguments
@@ -1288,10 +7725,7 @@ impl PhotoMindServer {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        // Read the JSON file
-        let content = tokio::fs::read_to_string(&json_path)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to read file {}: {}", json_path, e))?;
+        let content = self.read_import_file(&json_path).await?;
 
         let data: serde_json::Value = serde_json::from_str(&content)
             .map_err(|e| anyhow::anyhow!("Failed to parse JSON: {}", e))?;
@@ -1300,16 +7734,42 @@ impl PhotoMindServer {
             .as_array()
             .ok_or_else(|| anyhow::anyhow!("Expected 'orders' array in JSON"))?;
 
+        #[derive(serde::Deserialize)]
+        struct FamilyCheck {
+            id: surrealdb::sql::Thing,
+            _name: Option<String>,
+            delivery_email: Option<String>,
+        }
+
         let mut emails_updated = 0;
         let mut matched_orders = Vec::new();
         let mut unmatched_orders = Vec::new();
+        let mut failed = Vec::new();
+        let mut failed_orders = Vec::new();
 
+        // Stop writing as soon as the client cancels or disconnects
+        let mut processed = 0;
+        let mut cancelled = false;
         for order in orders {
+            if ct.is_cancelled() {
+                cancelled = true;
+                tracing::warn!(
+                    processed,
+                    total = orders.len(),
+                    "sync_shootproof_orders cancelled"
+                );
+                break;
+            }
+            processed += 1;
+
             let customer_email = order["customer_email"].as_str().unwrap_or("").to_string();
             let customer_name = order["customer_name"].as_str().unwrap_or("").to_string();
             let event_name = order["event_name"].as_str().unwrap_or("").to_string();
             let grand_total = order["grand_total"].as_f64().unwrap_or(0.0);
             let event_id = order["event_id"].as_i64().unwrap_or(0);
+            // Same sanity check as record_purchase, so bad totals are visible before import
+            let amount_warning =
+                validate_purchase_amount(grand_total, self.cfg().max_purchase_amount, false).err();
 
             // Extract last name from event name (gallery name = family name usually)
             let last_name = event_name
@@ -1317,71 +7777,1157 @@ impl PhotoMindServer {
                 .last()
                 .unwrap_or(&event_name)
                 .to_lowercase();
-            let family_id_str = format!("family:{}", last_name.replace(' ', "_"));
-
-            // Check if family exists
-            let family_query = "SELECT id, name, delivery_email FROM type::thing($family_id);";
-            let mut result = self
-                .db
-                .query(family_query)
-                .bind(("family_id", family_id_str.clone()))
-                .await?;
-
-            #[derive(serde::Deserialize)]
-            struct FamilyCheck {
-                id: surrealdb::sql::Thing,
-                _name: Option<String>,
-                delivery_email: Option<String>,
-            }
-
-            let families: Vec<FamilyCheck> = result.take(0).unwrap_or_default();
-
-            if !families.is_empty() {
-                let family = &families[0];
-                let needs_email = family.delivery_email.is_none() && !customer_email.is_empty();
-                let customer_email_clone = customer_email.clone();
-
-                matched_orders.push(serde_json::json!({
-                    "event_name": event_name,
-                    "event_id": event_id,
-                    "customer_name": customer_name,
-                    "customer_email": customer_email,
-                    "amount": grand_total,
-                    "family_id": family.id.to_string(),
-                    "existing_email": family.delivery_email,
-                    "will_update_email": needs_email,
-                }));
-
-                if !dry_run && needs_email {
-                    // Update family with email from order
-                    let update_query =
-                        "UPDATE type::thing($family_id) SET delivery_email = $email;";
-                    self.db
-                        .query(update_query)
-                        .bind(("family_id", family_id_str))
-                        .bind(("email", customer_email_clone))
+            let family_id_str = self.family_id_label(&last_name);
+
+            // Lookup and email fill are retried together; both are safe to repeat
+            let (last_name_ref, email_ref) = (&last_name, &customer_email);
+            let outcome = self
+                .with_sync_retry(move || async move {
+                    let mut result = self
+                        .query_family("id, name, delivery_email", last_name_ref)
                         .await?;
-                    emails_updated += 1;
+                    let families: Vec<FamilyCheck> = result.take(0).unwrap_or_default();
+                    let Some(family) = families.into_iter().next() else {
+                        return Ok(None);
+                    };
+                    let needs_email = family.delivery_email.is_none() && !email_ref.is_empty();
+                    if !dry_run && needs_email {
+                        // Update the family just found with the email from the order
+                        let update_query = "UPDATE $family SET delivery_email = $email;";
+                        self.db
+                            .query(update_query)
+                            .bind(("family", family.id.clone()))
+                            .bind(("email", email_ref.clone()))
+                            .await?
+                            .check()?;
+                    }
+                    Ok::<_, anyhow::Error>(Some((family, needs_email)))
+                })
+                .await;
+
+            match outcome {
+                Ok(Some((family, needs_email))) => {
+                    if !dry_run && needs_email {
+                        emails_updated += 1;
+                    }
+                    matched_orders.push(serde_json::json!({
+                        "event_name": event_name,
+                        "event_id": event_id,
+                        "customer_name": customer_name,
+                        "customer_email": customer_email,
+                        "amount": grand_total,
+                        "amount_warning": amount_warning,
+                        "family_id": family.id.to_string(),
+                        "existing_email": family.delivery_email,
+                        "will_update_email": needs_email,
+                    }));
                 }
-            } else {
-                unmatched_orders.push(serde_json::json!({
+                Ok(None) => unmatched_orders.push(serde_json::json!({
                     "event_name": event_name,
                     "customer_name": customer_name,
                     "customer_email": customer_email,
                     "amount": grand_total,
+                    "amount_warning": amount_warning,
                     "attempted_family_id": family_id_str,
-                }));
+                })),
+                Err(e) => {
+                    tracing::error!(event = %event_name, error = %e, "sync_shootproof_orders row failed");
+                    failed.push(serde_json::json!({
+                        "index": processed - 1,
+                        "event_name": event_name,
+                        "event_id": event_id,
+                        "customer_email": customer_email,
+                        "error": e.to_string(),
+                    }));
+                    failed_orders.push(order.clone());
+                }
             }
         }
 
+        let matched_count = matched_orders.len();
+        let unmatched_count = unmatched_orders.len();
+        let (matched_orders, matched_omitted) =
+            cap_details(matched_orders, self.cfg().max_detail_items);
+        let (unmatched_orders, unmatched_omitted) =
+            cap_details(unmatched_orders, self.cfg().max_detail_items);
+        let failed_count = failed.len();
+        let (failed, failed_omitted) = cap_details(failed, self.cfg().max_detail_items);
+        let omitted = matched_omitted + unmatched_omitted + failed_omitted;
+
         Ok(CallToolResult::structured(serde_json::json!({
             "dry_run": dry_run,
+            "cancelled": cancelled,
+            "processed": processed,
             "total_orders": orders.len(),
-            "matched": matched_orders.len(),
-            "unmatched": unmatched_orders.len(),
+            "matched": matched_count,
+            "unmatched": unmatched_count,
+            "failed": failed_count,
             "emails_updated": emails_updated,
             "matched_details": matched_orders,
             "unmatched_details": unmatched_orders,
+            "failed_details": failed,
+            // Uncapped, in the input shape: save as {"orders": [...]} to re-run them
+            "failed_orders": failed_orders,
+            "truncated": omitted > 0,
+            "omitted_count": omitted,
+        })))
+    }
+
+    /// Export families and purchases as ShootProof-style JSON (`galleries`/`orders`), the
+    /// same shape `sync_shootproof_galleries` / `sync_shootproof_orders` read back in
+    pub async fn handle_export_shootproof(
+        &self,
+        _req: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        #[derive(serde::Deserialize)]
+        struct Contact {
+            first_name: Option<String>,
+            last_name: Option<String>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct SpFamily {
+            id: String,
+            name: Option<String>,
+            last_name: Option<String>,
+            delivery_email: Option<String>,
+            primary_contact: Option<Contact>,
+            shootproof_gallery_id: Option<i64>,
+            shootproof_url: Option<String>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct Purchase {
+            family_id: String,
+            event_name: Option<String>,
+            purchase_amount: f64,
+            purchase_date: Option<String>,
+        }
+
+        let query = r#"
+            SELECT <string> id AS id, name, last_name, delivery_email,
+                primary_contact.{first_name, last_name} AS primary_contact,
+                shootproof_gallery_id, shootproof_url
+            FROM type::table($family_table) ORDER BY last_name;
+            SELECT <string> in AS family_id, out.name AS event_name, purchase_amount, purchase_date
+            FROM family_shoot WHERE purchase_amount IS NOT NONE ORDER BY purchase_date;
+            SELECT <string> in AS family_id, out.name AS event_name, purchase_amount, purchase_date
+            FROM family_competition WHERE purchase_amount IS NOT NONE ORDER BY purchase_date;
+            RETURN <string> time::now();
+        "#;
+
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("family_table", self.cfg().family_table.clone()))
+            .await?;
+        let families: Vec<SpFamily> = result.take(0)?;
+        let mut purchases: Vec<Purchase> = result.take(1)?;
+        purchases.extend(result.take::<Vec<Purchase>>(2)?);
+        let exported_at: Option<String> = result.take(3)?;
+
+        // Gallery name is the family's last name: the sync tools match on its last word
+        let gallery_name = |f: &SpFamily| {
+            f.last_name
+                .clone()
+                .or_else(|| f.name.clone())
+                .unwrap_or_default()
+        };
+
+        // A gallery without a ShootProof id would import as id 0, so only known ones go out
+        let galleries: Vec<serde_json::Value> = families
+            .iter()
+            .filter_map(|f| {
+                let sp_id = f.shootproof_gallery_id?;
+                Some(serde_json::json!({
+                    "id": sp_id,
+                    "name": gallery_name(f),
+                    "url": f.shootproof_url,
+                    "family_id": f.id,
+                }))
+            })
+            .collect();
+        let without_gallery = families.len() - galleries.len();
+
+        let by_id: std::collections::HashMap<&str, &SpFamily> =
+            families.iter().map(|f| (f.id.as_str(), f)).collect();
+        let mut orders = Vec::new();
+        let mut skipped_orders = 0;
+        for purchase in &purchases {
+            let Some(&family) = by_id.get(purchase.family_id.as_str()) else {
+                skipped_orders += 1;
+                continue;
+            };
+            let customer_name = match &family.primary_contact {
+                Some(Contact {
+                    first_name: Some(first),
+                    last_name,
+                }) => format!(
+                    "{} {}",
+                    first,
+                    last_name.as_deref().unwrap_or(&gallery_name(family))
+                ),
+                _ => self.family_display_name(family.last_name.as_deref(), family.name.as_deref()),
+            };
+            orders.push(serde_json::json!({
+                "event_id": family.shootproof_gallery_id,
+                "event_name": gallery_name(family),
+                "customer_name": customer_name,
+                "customer_email": family.delivery_email,
+                "grand_total": purchase.purchase_amount,
+                "order_date": purchase.purchase_date,
+                "source_event": purchase.event_name,
+                "shootproof_gallery_id": family.shootproof_gallery_id,
+                "shootproof_url": family.shootproof_url,
+            }));
+        }
+
+        let summary = serde_json::json!({
+            "gallery_count": galleries.len(),
+            "order_count": orders.len(),
+            "families_without_gallery": without_gallery,
+            "orders_skipped": skipped_orders,
+        });
+        // Returned inline only; the server never writes caller-chosen paths
+        Ok(CallToolResult::structured(serde_json::json!({
+            "exported_at": exported_at,
+            "galleries": galleries,
+            "orders": orders,
+            "summary": summary,
         })))
     }
 }
+
+/// Optional `cursor` argument: a record id from a previous `next_cursor`, which must belong
+/// to `table`.
+fn cursor_arg(args: &mut Args, table: &str) -> Option<String> {
+    let cursor = args.optional_str("cursor")?;
+    if cursor
+        .strip_prefix(table)
+        .is_some_and(|rest| rest.starts_with(':') && rest.len() > 1)
+    {
+        Some(cursor)
+    } else {
+        args.invalid(
+            "cursor",
+            &format!("must be a {} record id from next_cursor", table),
+        );
+        None
+    }
+}
+
+/// Sortable columns of `list_families`: argument value -> field.
+const FAMILY_SORTS: &[(&str, &str)] = &[
+    ("last_name", "last_name"),
+    ("email", "delivery_email"),
+    ("created_at", "created_at"),
+];
+
+/// Sortable columns of `list_shoots`: argument value -> field.
+const SHOOT_SORTS: &[(&str, &str)] = &[
+    ("date", "shoot_date"),
+    ("name", "name"),
+    ("type", "shoot_type"),
+];
+
+/// Optional `sort_by`/`sort_dir` arguments as an ORDER BY term. `sort_by` must be a key of
+/// `columns` and `sort_dir` `asc` or `desc`, so only fixed field names reach the query.
+/// Returns the key, direction, and term; missing arguments fall back to `default`.
+fn sort_arg(
+    args: &mut Args,
+    columns: &[(&'static str, &'static str)],
+    default: (&'static str, &'static str),
+) -> (&'static str, &'static str, String) {
+    let key = match args.optional_str("sort_by").map(|s| s.to_lowercase()) {
+        None => default.0,
+        Some(raw) => match columns.iter().find(|(key, _)| *key == raw) {
+            Some((key, _)) => key,
+            None => {
+                let keys: Vec<_> = columns.iter().map(|(key, _)| *key).collect();
+                args.invalid("sort_by", &format!("must be one of: {}", keys.join(", ")));
+                default.0
+            }
+        },
+    };
+    let dir = match args.optional_str("sort_dir").map(|s| s.to_lowercase()) {
+        None => default.1,
+        Some(raw) if raw == "asc" => "asc",
+        Some(raw) if raw == "desc" => "desc",
+        Some(_) => {
+            args.invalid("sort_dir", "must be asc or desc");
+            default.1
+        }
+    };
+    let field = columns
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map_or(key, |(_, field)| field);
+    (key, dir, format!("{} {}", field, dir.to_uppercase()))
+}
+
+/// Explicit `limit` argument (at least 1), else `default`, for tools whose default differs
+/// from `PHOTO_DEFAULT_LIMIT`. Like `offset_arg`, anything but a non-negative integer is
+/// reported as invalid.
+fn limit_arg_or(args: &mut Args, default: usize) -> usize {
+    match args.optional_value("limit") {
+        None | Some(serde_json::Value::Null) => default,
+        Some(value) => match value.as_u64() {
+            Some(limit) => limit.max(1) as usize,
+            None => {
+                args.invalid("limit", "must be a non-negative integer");
+                default
+            }
+        },
+    }
+}
+
+/// `offset` argument for offset paging (rows to skip, `LIMIT $limit START $offset`); 0 when
+/// absent, reported as invalid unless it is a non-negative integer.
+fn offset_arg(args: &mut Args) -> usize {
+    match args.optional_value("offset") {
+        None | Some(serde_json::Value::Null) => 0,
+        Some(value) => match value.as_u64() {
+            Some(offset) => offset as usize,
+            None => {
+                args.invalid("offset", "must be a non-negative integer");
+                0
+            }
+        },
+    }
+}
+
+/// `next_cursor` for a cursor-paged response: the last id when the page came back full
+/// (more may follow), otherwise `None`.
+fn next_cursor<'a>(
+    cursor_mode: bool,
+    limit: usize,
+    ids: impl ExactSizeIterator<Item = &'a surrealdb::sql::Thing>,
+) -> Option<String> {
+    if !cursor_mode || ids.len() < limit {
+        return None;
+    }
+    ids.last().map(|id| id.to_string())
+}
+
+/// Every delivery address of an edge's family (`in`), primary first; the query-side
+/// counterpart of `merge_delivery_emails`. SurrealQL can't dedupe case-insensitively, so
+/// rows read it through `deserialize_delivery_emails`, which applies the same merge.
+const IN_DELIVERY_EMAILS: &str =
+    "array::compact(array::union([in.delivery_email], in.delivery_emails ?? []))";
+
+/// Write timestamp for mutating tools: the injected `$now` (see `clock_arg`) when given,
+/// otherwise the database clock.
+const NOW: &str = "(IF $now { type::datetime($now) } ELSE { time::now() })";
+
+/// Edge tables checked by `find_dangling_edges` / `prune_dangling_edges` and
+/// `backfill_edge_timestamps`.
+const DANGLING_EDGE_TABLES: [&str; 3] = ["family_shoot", "family_competition", "belongs_to"];
+
+/// A family as returned by `resolve_family`.
+#[derive(serde::Deserialize)]
+struct FamilyMatch {
+    id: surrealdb::sql::Thing,
+    name: Option<String>,
+    last_name: Option<String>,
+    delivery_email: Option<String>,
+    delivery_emails: Option<Vec<String>>,
+    tags: Option<Vec<String>>,
+    /// Only filled by the "First Last" step
+    #[serde(default)]
+    skater_first_names: Vec<String>,
+}
+
+/// Outcome of `resolve_family`; `Found` carries how it matched (`id`, `first_last`, `fuzzy`).
+enum FamilyResolution {
+    Found(FamilyMatch, &'static str),
+    Ambiguous(Vec<FamilyMatch>),
+    NotFound,
+}
+
+#[derive(serde::Deserialize)]
+struct DanglingEdge {
+    id: surrealdb::sql::Thing,
+    in_id: Option<String>,
+    out_id: Option<String>,
+    missing_in: bool,
+    missing_out: bool,
+}
+
+/// Text that marks a write or side effect anywhere in a statement, including subqueries
+/// (`SELECT * FROM (DELETE ...)`) and functions that can write or call out.
+const RAW_QUERY_DENIED: &[&str] = &[
+    "CREATE ", "UPDATE ", "UPSERT ", "DELETE ", "RELATE ", "INSERT ", "DEFINE ", "REMOVE ",
+    "ALTER ", "REBUILD ", "KILL ", "LIVE ", "fn::", "http::",
+];
+
+/// Parses `sql` and accepts it only if every statement is SELECT, INFO, or RETURN with
+/// nothing in `RAW_QUERY_DENIED` in its normalized form. Returns the statement count.
+fn check_read_only_query(sql: &str) -> std::result::Result<usize, String> {
+    use surrealdb::sql::Statement;
+
+    let query = surrealdb::sql::parse(sql).map_err(|e| format!("could not parse: {}", e))?;
+    if query.is_empty() {
+        return Err("no statements".to_string());
+    }
+    for (index, statement) in query.iter().enumerate() {
+        if !matches!(
+            statement,
+            Statement::Select(_) | Statement::Info(_) | Statement::Output(_)
+        ) {
+            return Err(format!(
+                "statement {} is not SELECT, INFO, or RETURN",
+                index + 1
+            ));
+        }
+        let normalized = statement.to_string();
+        if let Some(word) = RAW_QUERY_DENIED.iter().find(|w| normalized.contains(*w)) {
+            return Err(format!(
+                "statement {} contains {}, which is not allowed",
+                index + 1,
+                word.trim()
+            ));
+        }
+    }
+    Ok(query.len())
+}
+
+/// Accepts "skater:jane_smith" or bare "jane_smith" and returns the full record id.
+fn skater_record_id(raw: &str) -> String {
+    if raw.contains(':') {
+        raw.to_string()
+    } else {
+        format!("skater:{}", raw)
+    }
+}
+
+/// Read an optional boolean argument, defaulting to false.
+fn bool_arg(req: &CallToolRequestParam, key: &str) -> bool {
+    req.arguments
+        .as_ref()
+        .and_then(|args| args.get(key))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Standard response for a mutating tool called with `dry_run: true`:
+/// targets are resolved, nothing is written, and `would_change` describes the write.
+fn dry_run_result(message: String, would_change: serde_json::Value) -> CallToolResult {
+    CallToolResult::structured(serde_json::json!({
+        "success": true,
+        "dry_run": true,
+        "message": message,
+        "would_change": would_change,
+    }))
+}
+
+/// Cap a detail array at `max` items. Returns the kept items and how many were omitted,
+/// so callers can report `truncated`/`omitted_count` while keeping totals accurate.
+fn cap_details(mut items: Vec<serde_json::Value>, max: usize) -> (Vec<serde_json::Value>, usize) {
+    let omitted = items.len().saturating_sub(max);
+    items.truncate(max);
+    (items, omitted)
+}
+
+/// Append inclusive date-range conditions on `field`, bound as `$start_date` / `$end_date`
+/// (YYYY-MM-DD). The end date covers the whole day.
+fn push_date_range(
+    conditions: &mut Vec<String>,
+    field: &str,
+    start_date: &Option<String>,
+    end_date: &Option<String>,
+) {
+    if start_date.is_some() {
+        conditions.push(format!("{} >= type::datetime($start_date)", field));
+    }
+    if end_date.is_some() {
+        conditions.push(format!("{} < type::datetime($end_date) + 1d", field));
+    }
+}
+
+/// WHERE clause resolving a shoot from the `$param` binding: an exact `slug` match wins,
+/// otherwise any shoot whose name contains the text (case-insensitive).
+fn shoot_match(param: &str) -> String {
+    format!(
+        "(slug = ${param} OR (string::lowercase(name ?? '') CONTAINS string::lowercase(${param}) \
+         AND array::len((SELECT VALUE id FROM shoot WHERE slug = ${param})) = 0))"
+    )
+}
+
+/// Normalize a tag for storage and comparison (trimmed, lowercase).
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+/// Parses a `packages` argument: `[{ "name": "Mini", "price_cents": 7500 }, ...]`.
+fn parse_packages(
+    value: &serde_json::Value,
+) -> std::result::Result<Vec<crate::photography::models::ShootPackage>, String> {
+    let items = value
+        .as_array()
+        .ok_or_else(|| "must be an array of {name, price_cents}".to_string())?;
+
+    items
+        .iter()
+        .map(|item| {
+            let name = item
+                .get("name")
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .ok_or_else(|| "each package needs a non-empty name".to_string())?;
+            let price_cents = item
+                .get("price_cents")
+                .and_then(|v| v.as_i64())
+                .filter(|c| *c >= 0)
+                .ok_or_else(|| {
+                    format!(
+                        "package '{}' needs a non-negative integer price_cents",
+                        name
+                    )
+                })?;
+            Ok(crate::photography::models::ShootPackage { name, price_cents })
+        })
+        .collect()
+}
+
+/// The package an unpaid gallery is expected to pay for, and why: the edge's own `package`,
+/// else the `requested` one, else the shoot's cheapest. Unknown names fall through.
+fn expected_package<'a>(
+    packages: &'a [crate::photography::models::ShootPackage],
+    edge_package: Option<&str>,
+    requested: Option<&str>,
+) -> Option<(&'a crate::photography::models::ShootPackage, &'static str)> {
+    let named = |name: Option<&str>| {
+        let name = name?.trim();
+        packages.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+    };
+    named(edge_package)
+        .map(|p| (p, "edge_package"))
+        .or_else(|| named(requested).map(|p| (p, "requested_package")))
+        .or_else(|| {
+            packages
+                .iter()
+                .min_by_key(|p| p.price_cents)
+                .map(|p| (p, "cheapest"))
+        })
+}
+
+/// Sample ids `validate_dataset` returns per issue category.
+const VALIDATION_SAMPLES: usize = 5;
+
+/// Likely duplicate families among (id, last_name, delivery_email) rows: the same delivery
+/// email (case-insensitive) or surnames at `DUPLICATE_SURNAME_THRESHOLD` similarity or above.
+fn duplicate_family_pairs<'a>(
+    families: &[(&'a str, &str, &str)],
+) -> Vec<(&'a str, &'a str, &'static str)> {
+    let mut pairs = Vec::new();
+    for (i, (id_a, surname_a, email_a)) in families.iter().enumerate() {
+        for (id_b, surname_b, email_b) in &families[i + 1..] {
+            let email_a = email_a.trim();
+            let reason = if !email_a.is_empty() && email_a.eq_ignore_ascii_case(email_b.trim()) {
+                "same_email"
+            } else if !surname_a.trim().is_empty()
+                && !surname_b.trim().is_empty()
+                && surname_similarity(surname_a, surname_b) >= DUPLICATE_SURNAME_THRESHOLD
+            {
+                "similar_surname"
+            } else {
+                continue;
+            };
+            pairs.push((*id_a, *id_b, reason));
+        }
+    }
+    pairs
+}
+
+/// Deepest level `referral_tree` walks (also its default).
+const MAX_REFERRAL_DEPTH: usize = 5;
+
+/// Referrals below one family: the nested nodes, how many families they cover, and the sum
+/// of those families' revenue.
+#[derive(Debug)]
+struct ReferralBranch {
+    nodes: Vec<serde_json::Value>,
+    count: usize,
+    revenue: f64,
+}
+
+/// Builds the referral branch under `family` from `children` (referrer id -> referred
+/// (id, display name) pairs). Families already in `seen` are skipped, so a referral loop
+/// ends instead of recursing; nothing below `depth` levels is included.
+fn referral_branch(
+    family: &str,
+    children: &std::collections::HashMap<String, Vec<(String, String)>>,
+    revenue: &std::collections::HashMap<String, f64>,
+    depth: usize,
+    seen: &mut std::collections::HashSet<String>,
+) -> ReferralBranch {
+    let mut branch = ReferralBranch {
+        nodes: Vec::new(),
+        count: 0,
+        revenue: 0.0,
+    };
+    if depth == 0 {
+        return branch;
+    }
+    for (id, name) in children.get(family).into_iter().flatten() {
+        if !seen.insert(id.clone()) {
+            continue;
+        }
+        let own = revenue.get(id).copied().unwrap_or(0.0);
+        let below = referral_branch(id, children, revenue, depth - 1, seen);
+        branch.count += 1 + below.count;
+        branch.revenue += own + below.revenue;
+        branch.nodes.push(serde_json::json!({
+            "family": name,
+            "family_id": id,
+            "revenue": own,
+            "downstream_revenue": below.revenue,
+            "referrals": below.nodes,
+        }));
+    }
+    branch
+}
+
+/// Splits "First Last" input into (first token, last token); None for single-token input.
+fn split_first_last(input: &str) -> Option<(&str, &str)> {
+    let mut tokens = input.split_whitespace();
+    let first = tokens.next()?;
+    let last = tokens.last()?;
+    Some((first, last))
+}
+
+/// What `attention_list` knows about one family.
+#[derive(Debug, Default)]
+struct FamilySignals {
+    /// (event, days since linked) for galleries in a pending status
+    pending: Vec<(String, i64)>,
+    /// (event, days since sent) for galleries sent but not purchased
+    sent: Vec<(String, i64)>,
+    missing_email: bool,
+    has_events: bool,
+    /// Days since the most recent send, purchase, or thank-you
+    last_contact_days: Option<i64>,
+}
+
+struct AttentionThresholds {
+    stale_days: i64,
+    contact_days: i64,
+}
+
+/// Weighted attention score plus a human-readable reason per contributing signal.
+fn attention_score(
+    signals: &FamilySignals,
+    weights: &AttentionWeights,
+    thresholds: &AttentionThresholds,
+) -> (f64, Vec<String>) {
+    let mut score = 0.0;
+    let mut reasons = Vec::new();
+
+    let stale: Vec<_> = signals
+        .pending
+        .iter()
+        .filter(|(_, days)| *days >= thresholds.stale_days)
+        .collect();
+    if !stale.is_empty() && weights.stale_pending > 0.0 {
+        score += weights.stale_pending * stale.len() as f64;
+        let oldest = stale.iter().map(|(_, d)| *d).max().unwrap_or(0);
+        reasons.push(format!(
+            "{} pending galler{} older than {} days (oldest {}d)",
+            stale.len(),
+            if stale.len() == 1 { "y" } else { "ies" },
+            thresholds.stale_days,
+            oldest
+        ));
+    }
+
+    if weights.sent_not_purchased > 0.0 {
+        for (event, days) in &signals.sent {
+            score += weights.sent_not_purchased * (*days as f64 / 7.0);
+            reasons.push(format!(
+                "{} gallery sent {} days ago, not purchased",
+                event, days
+            ));
+        }
+    }
+
+    if signals.missing_email && weights.missing_email > 0.0 {
+        score += weights.missing_email;
+        reasons.push("no delivery email".to_string());
+    }
+
+    // Only families we've actually worked with can be "out of touch"
+    if signals.has_events && weights.no_recent_contact > 0.0 {
+        match signals.last_contact_days {
+            Some(days) if days <= thresholds.contact_days => {}
+            Some(days) => {
+                score += weights.no_recent_contact;
+                reasons.push(format!("no contact in {} days", days));
+            }
+            None => {
+                score += weights.no_recent_contact;
+                reasons.push("never contacted".to_string());
+            }
+        }
+    }
+
+    (score, reasons)
+}
+
+/// Tool-facing view of a skater's `shot_in` gallery edge.
+fn shot_in_json(
+    edge: &crate::photography::models::ShotIn,
+    shoot: Option<&str>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "edge_id": edge.id.to_string(),
+        "skater_id": edge.skater.to_string(),
+        "shoot_id": edge.out.to_string(),
+        "shoot": shoot,
+        "gallery_status": edge.gallery_status,
+        "gallery_url": edge.gallery_url,
+    })
+}
+
+/// Tool-facing view of a competition record.
+fn competition_json(competition: &crate::photography::models::Competition) -> serde_json::Value {
+    serde_json::json!({
+        "id": competition.id.to_string(),
+        "name": competition.name,
+        "date": competition.event_date(),
+        "location": competition.event_location(),
+        "notes": competition.notes,
+        "archived": competition.archived.unwrap_or(false),
+    })
+}
+
+/// Edge tables that carry purchases, with the event type reported for each.
+pub(crate) const SALE_EDGES: [(&str, &str); 2] = [
+    ("family_shoot", "shoot"),
+    ("family_competition", "competition"),
+];
+
+/// WHERE clause shared by the sales export queries.
+fn sales_where(start_date: &Option<String>, end_date: &Option<String>) -> String {
+    let mut conditions = vec!["purchase_amount IS NOT NONE".to_string()];
+    push_date_range(&mut conditions, "purchase_date", start_date, end_date);
+    conditions.join(" AND ")
+}
+
+/// One purchase line for `export_sales`.
+pub(crate) struct SaleRow {
+    /// Edge id; the paging tiebreaker, not exported
+    pub(crate) id: String,
+    pub(crate) date: String,
+    pub(crate) family: String,
+    pub(crate) event: String,
+    pub(crate) event_type: &'static str,
+    pub(crate) amount: f64,
+    pub(crate) email: String,
+}
+
+/// Renders sales as CSV (`date,family,event,event_type,amount,email`) with a TOTAL row.
+fn sales_csv(sales: &[SaleRow]) -> Result<String> {
+    let total = sales.iter().map(|s| s.amount).sum();
+    sales_csv_chunk(sales, true, Some((sales.len(), total)))
+}
+
+/// CSV fragment for streaming: optional header, the rows, and the TOTAL row if `totals` is set.
+pub(crate) fn sales_csv_chunk(
+    sales: &[SaleRow],
+    header: bool,
+    totals: Option<(usize, f64)>,
+) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    if header {
+        writer.write_record(["date", "family", "event", "event_type", "amount", "email"])?;
+    }
+
+    for sale in sales {
+        // Keep just YYYY-MM-DD from the stored datetime
+        let date = sale.date.get(..10).unwrap_or(&sale.date);
+        writer.write_record([
+            date,
+            sale.family.as_str(),
+            sale.event.as_str(),
+            sale.event_type,
+            format!("{:.2}", sale.amount).as_str(),
+            sale.email.as_str(),
+        ])?;
+    }
+    if let Some((count, total)) = totals {
+        writer.write_record([
+            "TOTAL",
+            "",
+            format!("{} sales", count).as_str(),
+            "",
+            format!("{:.2}", total).as_str(),
+            "",
+        ])?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| anyhow::anyhow!("Failed to flush CSV: {}", e))?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// One family's gallery on one event, for `export_status_report`.
+struct StatusReportRow {
+    event_type: &'static str,
+    event_id: String,
+    event: String,
+    family: String,
+    status: String,
+    amount: Option<f64>,
+}
+
+/// Renders the status report CSV (`event_type,event,family,gallery_status,purchase_amount`).
+/// Rows must be sorted so each event's rows are adjacent; groups are keyed by event id (the
+/// name is display only), each ends with a SUBTOTAL row and the file with TOTAL.
+fn status_report_csv(rows: &[StatusReportRow]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "event_type",
+        "event",
+        "family",
+        "gallery_status",
+        "purchase_amount",
+    ])?;
+
+    let mut grand_total = 0.0;
+    for group in rows.chunk_by(|a, b| a.event_id == b.event_id) {
+        let mut subtotal = 0.0;
+        for row in group {
+            subtotal += row.amount.unwrap_or(0.0);
+            let amount = row.amount.map(|a| format!("{:.2}", a)).unwrap_or_default();
+            writer.write_record([
+                row.event_type,
+                row.event.as_str(),
+                row.family.as_str(),
+                row.status.as_str(),
+                amount.as_str(),
+            ])?;
+        }
+        writer.write_record([
+            group[0].event_type,
+            group[0].event.as_str(),
+            "SUBTOTAL",
+            format!("{} families", group.len()).as_str(),
+            format!("{:.2}", subtotal).as_str(),
+        ])?;
+        grand_total += subtotal;
+    }
+    writer.write_record([
+        "TOTAL",
+        "",
+        "",
+        format!("{} families", rows.len()).as_str(),
+        format!("{:.2}", grand_total).as_str(),
+    ])?;
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| anyhow::anyhow!("Failed to flush CSV: {}", e))?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AttentionThresholds, FamilySignals, SaleRow, StatusReportRow, attention_score, cap_details,
+        check_read_only_query, duplicate_family_pairs, expected_package, parse_packages,
+        referral_branch, sales_csv, sort_arg, split_first_last, status_report_csv,
+    };
+    use crate::config::AttentionWeights;
+
+    #[test]
+    fn cap_details_keeps_everything_under_the_limit() {
+        let items = vec![serde_json::json!(1), serde_json::json!(2)];
+        let (kept, omitted) = cap_details(items, 5);
+        assert_eq!(kept.len(), 2);
+        assert_eq!(omitted, 0);
+    }
+
+    #[test]
+    fn cap_details_truncates_and_reports_omitted() {
+        let items: Vec<_> = (0..10).map(|i| serde_json::json!(i)).collect();
+        let (kept, omitted) = cap_details(items, 3);
+        assert_eq!(
+            kept,
+            vec![
+                serde_json::json!(0),
+                serde_json::json!(1),
+                serde_json::json!(2)
+            ]
+        );
+        assert_eq!(omitted, 7);
+    }
+
+    #[test]
+    fn sales_csv_writes_rows_and_total() {
+        let sales = vec![
+            SaleRow {
+                id: "family_competition:a".to_string(),
+                date: "2025-11-02T18:00:00Z".to_string(),
+                family: "Smith".to_string(),
+                event: "Fall Fling".to_string(),
+                event_type: "competition",
+                amount: 45.0,
+                email: "smith@example.com".to_string(),
+            },
+            SaleRow {
+                id: "family_shoot:b".to_string(),
+                date: "2025-11-09T12:30:00Z".to_string(),
+                family: "Jones, Jr".to_string(),
+                event: "Holiday Minis".to_string(),
+                event_type: "shoot",
+                amount: 120.5,
+                email: String::new(),
+            },
+        ];
+
+        let csv = sales_csv(&sales).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "date,family,event,event_type,amount,email");
+        assert_eq!(
+            lines[1],
+            "2025-11-02,Smith,Fall Fling,competition,45.00,smith@example.com"
+        );
+        assert_eq!(
+            lines[2],
+            "2025-11-09,\"Jones, Jr\",Holiday Minis,shoot,120.50,"
+        );
+        assert_eq!(lines[3], "TOTAL,,2 sales,,165.50,");
+    }
+
+    #[test]
+    fn status_report_csv_subtotals_each_event() {
+        let row = |id: &str, event: &str, family: &str, status: &str, amount: Option<f64>| {
+            StatusReportRow {
+                event_type: "shoot",
+                event_id: id.to_string(),
+                event: event.to_string(),
+                family: family.to_string(),
+                status: status.to_string(),
+                amount,
+            }
+        };
+        let rows = vec![
+            row("a", "Holiday Minis", "Jones", "purchased", Some(120.0)),
+            row("a", "Holiday Minis", "Smith", "sent", None),
+            row("b", "Holiday Minis", "Ng", "purchased", Some(40.0)),
+            row("c", "Spring Portraits", "Lee", "purchased", Some(80.5)),
+        ];
+
+        let csv = status_report_csv(&rows).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "event_type,event,family,gallery_status,purchase_amount",
+                "shoot,Holiday Minis,Jones,purchased,120.00",
+                "shoot,Holiday Minis,Smith,sent,",
+                "shoot,Holiday Minis,SUBTOTAL,2 families,120.00",
+                "shoot,Holiday Minis,Ng,purchased,40.00",
+                "shoot,Holiday Minis,SUBTOTAL,1 families,40.00",
+                "shoot,Spring Portraits,Lee,purchased,80.50",
+                "shoot,Spring Portraits,SUBTOTAL,1 families,80.50",
+                "TOTAL,,,4 families,240.50",
+            ]
+        );
+    }
+
+    #[test]
+    fn split_first_last_uses_outer_tokens() {
+        assert_eq!(split_first_last("Jane Smith"), Some(("Jane", "Smith")));
+        assert_eq!(
+            split_first_last("  Mary Ann  Smith "),
+            Some(("Mary", "Smith"))
+        );
+        assert_eq!(split_first_last("Smith"), None);
+        assert_eq!(split_first_last("   "), None);
+    }
+
+    #[test]
+    fn parse_packages_accepts_valid_and_rejects_bad_prices() {
+        let packages = parse_packages(&serde_json::json!([
+            { "name": " Mini Digital ", "price_cents": 7500 },
+            { "name": "Full", "price_cents": 0 }
+        ]))
+        .unwrap();
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "Mini Digital");
+        assert_eq!(packages[0].price_cents, 7500);
+
+        assert!(
+            parse_packages(&serde_json::json!([{ "name": "Mini", "price_cents": 12.5 }])).is_err()
+        );
+        assert!(parse_packages(&serde_json::json!([{ "price_cents": 100 }])).is_err());
+        assert!(parse_packages(&serde_json::json!({ "name": "Mini" })).is_err());
+    }
+
+    #[test]
+    fn attention_score_weights_each_signal() {
+        let thresholds = AttentionThresholds {
+            stale_days: 14,
+            contact_days: 60,
+        };
+        let signals = FamilySignals {
+            pending: vec![("Spring Minis".into(), 30), ("Fall Classic".into(), 3)],
+            sent: vec![("Regionals".into(), 14)],
+            missing_email: true,
+            has_events: true,
+            last_contact_days: Some(90),
+        };
+
+        let (score, reasons) = attention_score(&signals, &AttentionWeights::default(), &thresholds);
+        // 1 stale * 3 + 2 weeks * 1 + missing email 5 + no contact 2
+        assert_eq!(score, 12.0);
+        assert_eq!(reasons.len(), 4);
+        assert!(reasons[0].starts_with("1 pending gallery older than 14 days"));
+
+        let muted = AttentionWeights {
+            missing_email: 0.0,
+            ..AttentionWeights::default()
+        };
+        let (score, reasons) = attention_score(&signals, &muted, &thresholds);
+        assert_eq!(score, 7.0);
+        assert!(!reasons.iter().any(|r| r == "no delivery email"));
+    }
+
+    #[test]
+    fn attention_score_skips_contact_for_families_without_events() {
+        let thresholds = AttentionThresholds {
+            stale_days: 14,
+            contact_days: 60,
+        };
+        let (score, reasons) = attention_score(
+            &FamilySignals::default(),
+            &AttentionWeights::default(),
+            &thresholds,
+        );
+        assert_eq!(score, 0.0);
+        assert!(reasons.is_empty());
+    }
+
+    #[test]
+    fn raw_query_accepts_reads_only() {
+        assert_eq!(check_read_only_query("SELECT * FROM family LIMIT 5"), Ok(1));
+        assert_eq!(
+            check_read_only_query("INFO FOR DB; RETURN count((SELECT id FROM shoot));"),
+            Ok(2)
+        );
+        assert!(check_read_only_query("DELETE family").is_err());
+        assert!(check_read_only_query("SELECT * FROM family; UPDATE family SET x = 1").is_err());
+        assert!(check_read_only_query("SELECT * FROM (DELETE shoot RETURN BEFORE)").is_err());
+        assert!(check_read_only_query("RETURN fn::cleanup()").is_err());
+        assert!(check_read_only_query("SELECT * FROM").is_err());
+        assert!(check_read_only_query("").is_err());
+    }
+
+    #[test]
+    fn expected_package_prefers_edge_then_requested_then_cheapest() {
+        use crate::photography::models::ShootPackage;
+        let packages = [
+            ShootPackage {
+                name: "Full".into(),
+                price_cents: 15000,
+            },
+            ShootPackage {
+                name: "Mini".into(),
+                price_cents: 7500,
+            },
+        ];
+        let pick = |edge, requested| {
+            expected_package(&packages, edge, requested).map(|(p, source)| (p.price_cents, source))
+        };
+        assert_eq!(
+            pick(Some("full"), Some("Mini")),
+            Some((15000, "edge_package"))
+        );
+        assert_eq!(pick(None, Some("FULL")), Some((15000, "requested_package")));
+        assert_eq!(
+            pick(Some("Gone"), Some("Also gone")),
+            Some((7500, "cheapest"))
+        );
+        assert_eq!(expected_package(&[], None, None), None);
+    }
+
+    #[test]
+    fn sort_arg_allows_only_listed_columns() {
+        use super::{Args, SHOOT_SORTS};
+        let map = |v: serde_json::Value| v.as_object().cloned();
+
+        let default = map(serde_json::json!({}));
+        let mut args = Args::from_map(default.as_ref());
+        let (key, dir, order) = sort_arg(&mut args, SHOOT_SORTS, ("date", "desc"));
+        assert_eq!(
+            (key, dir, order.as_str()),
+            ("date", "desc", "shoot_date DESC")
+        );
+        assert!(args.finish().is_ok());
+
+        let chosen = map(serde_json::json!({ "sort_by": "Type", "sort_dir": "ASC" }));
+        let mut args = Args::from_map(chosen.as_ref());
+        assert_eq!(
+            sort_arg(&mut args, SHOOT_SORTS, ("date", "desc")).2,
+            "shoot_type ASC"
+        );
+        assert!(args.finish().is_ok());
+
+        let injected =
+            map(serde_json::json!({ "sort_by": "name; DELETE shoot", "sort_dir": "up" }));
+        let mut args = Args::from_map(injected.as_ref());
+        assert_eq!(
+            sort_arg(&mut args, SHOOT_SORTS, ("date", "desc")).2,
+            "shoot_date DESC"
+        );
+        let problems = args.finish().unwrap_err();
+        assert_eq!(problems.invalid.len(), 2);
+    }
+
+    #[test]
+    fn referral_branch_sums_downstream_and_stops_at_loops_and_depth() {
+        let pair = |id: &str| (id.to_string(), id.to_string());
+        let children = std::collections::HashMap::from([
+            ("a".to_string(), vec![pair("b"), pair("c")]),
+            ("b".to_string(), vec![pair("d")]),
+            // d referring a back would loop forever without the seen set
+            ("d".to_string(), vec![pair("a")]),
+        ]);
+        let revenue = std::collections::HashMap::from([
+            ("b".to_string(), 100.0),
+            ("c".to_string(), 50.0),
+            ("d".to_string(), 25.0),
+        ]);
+
+        let mut seen = std::collections::HashSet::from(["a".to_string()]);
+        let branch = referral_branch("a", &children, &revenue, 5, &mut seen);
+        assert_eq!(branch.count, 3);
+        assert_eq!(branch.revenue, 175.0);
+        assert_eq!(branch.nodes[0]["downstream_revenue"], 25.0);
+
+        let mut seen = std::collections::HashSet::from(["a".to_string()]);
+        let shallow = referral_branch("a", &children, &revenue, 1, &mut seen);
+        assert_eq!(shallow.count, 2);
+        assert_eq!(shallow.revenue, 150.0);
+    }
+
+    #[test]
+    fn duplicate_family_pairs_match_email_or_close_surname() {
+        let families = [
+            ("family:smith", "Smith", "a@example.com"),
+            ("family:smyth", "Smith", ""),
+            ("family:jones", "Jones", "A@Example.com "),
+            ("family:brown", "Brown", ""),
+        ];
+        assert_eq!(
+            duplicate_family_pairs(&families),
+            vec![
+                ("family:smith", "family:smyth", "similar_surname"),
+                ("family:smith", "family:jones", "same_email"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Human-readable text for structured tool results.
+//!
+//! The structured JSON stays authoritative, but some MCP clients only render the `content`
+//! text blocks. The router puts this summary first in `content`, ahead of the JSON text.
+
+use crate::envelope::DataShape;
+use serde_json::{Map, Value};
+
+/// Items named in a list summary before "... and N more".
+const MAX_LISTED: usize = 10;
+
+/// Fields shown for a record or a result without a list.
+const MAX_FIELDS: usize = 12;
+
+/// Bookkeeping keys left out of field listings (`message` is shown on its own line).
+const SKIP_KEYS: &[&str] = &["_request_id", "data", "ok", "success", "message"];
+
+/// Fields tried, in order, to name one item in a list.
+const LABEL_KEYS: &[&str] = &[
+    "name",
+    "family",
+    "display_name",
+    "last_name",
+    "event",
+    "shoot",
+    "status",
+    "id",
+];
+
+/// A few lines describing `body`: its `message`, then the listed items for list tools, or
+/// the top-level scalar fields otherwise. Expects the envelope to have been applied.
+pub fn text_summary(tool: &str, body: &Map<String, Value>) -> String {
+    let mut lines = Vec::new();
+    if let Some(Value::String(message)) = body.get("message") {
+        lines.push(message.clone());
+    }
+
+    let data: &[Value] = match body.get("data") {
+        Some(Value::Array(items)) => items.as_slice(),
+        _ => &[],
+    };
+    match crate::envelope::data_shape(tool) {
+        Some(DataShape::List(key)) if !data.is_empty() => {
+            lines.push(format!("{} {}:", data.len(), key));
+            lines.extend(
+                data.iter()
+                    .take(MAX_LISTED)
+                    .map(|item| format!("- {}", label(item))),
+            );
+            if data.len() > MAX_LISTED {
+                lines.push(format!("... and {} more", data.len() - MAX_LISTED));
+            }
+        }
+        Some(DataShape::Record(_) | DataShape::Flat) => {
+            if let Some(Value::Object(record)) = data.first() {
+                lines.extend(field_lines(record));
+            }
+        }
+        Some(DataShape::List(_)) => {}
+        None => lines.extend(field_lines(body)),
+    }
+
+    if lines.is_empty() {
+        format!("{}: done", tool)
+    } else {
+        lines.join("\n")
+    }
+}
+
+/// `key: value` for scalar fields and `key: N items` for arrays; nested objects are skipped.
+fn field_lines(map: &Map<String, Value>) -> Vec<String> {
+    map.iter()
+        .filter(|(key, _)| !SKIP_KEYS.contains(&key.as_str()))
+        .filter_map(|(key, value)| match value {
+            Value::String(s) => Some(format!("{}: {}", key, s)),
+            Value::Number(_) | Value::Bool(_) => Some(format!("{}: {}", key, value)),
+            Value::Array(items) => Some(format!("{}: {} items", key, items.len())),
+            Value::Null | Value::Object(_) => None,
+        })
+        .take(MAX_FIELDS)
+        .collect()
+}
+
+/// The first string among `LABEL_KEYS`, else the item's compact JSON.
+fn label(item: &Value) -> String {
+    LABEL_KEYS
+        .iter()
+        .find_map(|key| item.get(key).and_then(Value::as_str))
+        .map(str::to_string)
+        .unwrap_or_else(|| item.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::text_summary;
+    use serde_json::json;
+
+    fn summary(tool: &str, body: serde_json::Value) -> String {
+        text_summary(tool, body.as_object().unwrap())
+    }
+
+    #[test]
+    fn list_tools_name_their_items() {
+        let text = summary(
+            "list_families",
+            json!({
+                "ok": true,
+                "count": 2,
+                "data": [{ "family": "Smith", "id": "family:smith" }, { "id": "family:jones" }],
+            }),
+        );
+        assert_eq!(text, "2 families:\n- Smith\n- family:jones");
+    }
+
+    #[test]
+    fn other_tools_show_message_and_scalar_fields() {
+        let text = summary(
+            "record_purchase",
+            json!({
+                "success": true,
+                "message": "Recorded $75.00 purchase for Smith at Fall Minis",
+                "gallery_status": "purchased",
+                "would_change": { "nested": true },
+                "_request_id": "abc",
+            }),
+        );
+        assert_eq!(
+            text,
+            "Recorded $75.00 purchase for Smith at Fall Minis\ngallery_status: purchased"
+        );
+        assert_eq!(summary("health", json!({})), "health: done");
+    }
+}